@@ -0,0 +1,286 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use super::{Array, BoolArray, Utf8Array};
+
+/// A compiled pattern of `LIKE`.
+///
+/// In the pattern, `%` matches any sequence of characters, `_` matches any single character, and
+/// `\` escapes the next character. Patterns which only have `%` at their ends are matched by
+/// string searching.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LikePattern {
+    /// A pattern without wildcards.
+    Exact(String),
+    /// A pattern like `abc%`.
+    Prefix(String),
+    /// A pattern like `%abc`.
+    Suffix(String),
+    /// A pattern like `%abc%`.
+    Contains(String),
+    /// Any other pattern.
+    General(Vec<LikeToken>),
+}
+
+/// A token of a general `LIKE` pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LikeToken {
+    Char(char),
+    /// `_`
+    AnyChar,
+    /// `%`
+    AnyString,
+}
+
+impl LikePattern {
+    /// Compile a `LIKE` pattern.
+    pub fn new(pattern: &str) -> Self {
+        let mut tokens = vec![];
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            let token = match c {
+                // a trailing `\` matches itself
+                '\\' => LikeToken::Char(chars.next().unwrap_or('\\')),
+                '_' => LikeToken::AnyChar,
+                '%' if tokens.last() == Some(&LikeToken::AnyString) => continue,
+                '%' => LikeToken::AnyString,
+                c => LikeToken::Char(c),
+            };
+            tokens.push(token);
+        }
+
+        if tokens.contains(&LikeToken::AnyChar) {
+            return Self::General(tokens);
+        }
+        let starts_with_any = tokens.first() == Some(&LikeToken::AnyString);
+        let ends_with_any = tokens.len() > 1 && tokens.last() == Some(&LikeToken::AnyString);
+        let inner = &tokens[starts_with_any as usize..tokens.len() - ends_with_any as usize];
+        if inner.contains(&LikeToken::AnyString) {
+            return Self::General(tokens);
+        }
+        let literal = inner
+            .iter()
+            .map(|token| match token {
+                LikeToken::Char(c) => *c,
+                _ => unreachable!(),
+            })
+            .collect();
+        match (starts_with_any, ends_with_any) {
+            (false, false) => Self::Exact(literal),
+            (false, true) => Self::Prefix(literal),
+            (true, false) => Self::Suffix(literal),
+            (true, true) => Self::Contains(literal),
+        }
+    }
+
+    /// Returns the inclusive range of the strings matching the pattern, if the pattern starts
+    /// with a literal prefix. The upper end is `None` if it is unbounded.
+    pub fn range(&self) -> Option<(String, Option<String>)> {
+        let prefix: String = match self {
+            Self::Exact(literal) => return Some((literal.clone(), Some(literal.clone()))),
+            Self::Prefix(prefix) => prefix.clone(),
+            Self::General(tokens) => tokens
+                .iter()
+                .map_while(|token| match token {
+                    LikeToken::Char(c) => Some(*c),
+                    _ => None,
+                })
+                .collect(),
+            Self::Suffix(_) | Self::Contains(_) => return None,
+        };
+        if prefix.is_empty() {
+            return None;
+        }
+        // the strings with the prefix are less than the prefix with its last character increased
+        let mut end = prefix.clone();
+        while let Some(c) = end.pop() {
+            if let Some(next) = (c as u32 + 1..=char::MAX as u32).find_map(char::from_u32) {
+                end.push(next);
+                return Some((prefix, Some(end)));
+            }
+        }
+        Some((prefix, None))
+    }
+
+    /// Returns true if the string matches the pattern.
+    pub fn matches(&self, s: &str) -> bool {
+        match self {
+            Self::Exact(literal) => s == literal,
+            Self::Prefix(literal) => s.starts_with(literal.as_str()),
+            Self::Suffix(literal) => s.ends_with(literal.as_str()),
+            Self::Contains(literal) => s.contains(literal.as_str()),
+            Self::General(tokens) => matches_tokens(tokens, s),
+        }
+    }
+}
+
+/// Match the string against the tokens, backtracking to the last `%` on mismatch.
+fn matches_tokens(tokens: &[LikeToken], s: &str) -> bool {
+    let s: Vec<char> = s.chars().collect();
+    let (mut p, mut i) = (0, 0);
+    // the position of the token after the last `%`, and the position in the string it starts from
+    let mut backtrack = None;
+    while i < s.len() {
+        match tokens.get(p) {
+            Some(LikeToken::AnyString) => {
+                p += 1;
+                backtrack = Some((p, i));
+                continue;
+            }
+            Some(LikeToken::AnyChar) => {
+                p += 1;
+                i += 1;
+                continue;
+            }
+            Some(LikeToken::Char(c)) if *c == s[i] => {
+                p += 1;
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        match backtrack {
+            Some((bp, bi)) => {
+                // let the last `%` match one more character
+                p = bp;
+                i = bi + 1;
+                backtrack = Some((bp, bi + 1));
+            }
+            None => return false,
+        }
+    }
+    tokens[p..]
+        .iter()
+        .all(|token| *token == LikeToken::AnyString)
+}
+
+impl Utf8Array {
+    /// Perform `LIKE` against the patterns, or `ILIKE` if `case_insensitive` is true.
+    ///
+    /// If all patterns are the same, the pattern is only compiled once.
+    pub fn like(&self, patterns: &Utf8Array, case_insensitive: bool) -> BoolArray {
+        assert_eq!(self.len(), patterns.len());
+        let compile = |pattern: &str| {
+            if case_insensitive {
+                LikePattern::new(&pattern.to_lowercase())
+            } else {
+                LikePattern::new(pattern)
+            }
+        };
+        let matches = |pattern: &LikePattern, s: &str| {
+            if case_insensitive {
+                pattern.matches(&s.to_lowercase())
+            } else {
+                pattern.matches(s)
+            }
+        };
+
+        let first = patterns.iter().flatten().next();
+        if let Some(first) = first {
+            if patterns.iter().all(|p| p.is_none() || p == Some(first)) {
+                let pattern = compile(first);
+                return self
+                    .iter()
+                    .zip(patterns.iter())
+                    .map(|(s, p)| match (s, p) {
+                        (Some(s), Some(_)) => Some(matches(&pattern, s)),
+                        _ => None,
+                    })
+                    .collect();
+            }
+        }
+        // compile the pattern only when it changes
+        let mut last: Option<(&str, LikePattern)> = None;
+        self.iter()
+            .zip(patterns.iter())
+            .map(|(s, p)| {
+                let (s, p) = (s?, p?);
+                if !matches!(&last, Some((last_p, _)) if *last_p == p) {
+                    last = Some((p, compile(p)));
+                }
+                Some(matches(&last.as_ref().unwrap().1, s))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compile_like_pattern() {
+        use LikePattern::*;
+        assert_eq!(LikePattern::new("abc"), Exact("abc".into()));
+        assert_eq!(LikePattern::new("abc%"), Prefix("abc".into()));
+        assert_eq!(LikePattern::new("%abc"), Suffix("abc".into()));
+        assert_eq!(LikePattern::new("%%abc%%"), Contains("abc".into()));
+        assert_eq!(LikePattern::new("%"), Suffix("".into()));
+        assert_eq!(LikePattern::new("a\\%"), Exact("a%".into()));
+        assert!(matches!(LikePattern::new("a%b"), General(_)));
+        assert!(matches!(LikePattern::new("a_"), General(_)));
+    }
+
+    #[test]
+    fn match_like_pattern() {
+        let cases = [
+            ("abc", "abc", true),
+            ("abc", "ab", false),
+            ("ab%", "abc", true),
+            ("%bc", "abc", true),
+            ("%b%", "abc", true),
+            ("%d%", "abc", false),
+            ("a_c", "abc", true),
+            ("a_c", "ac", false),
+            ("a%c", "abbbc", true),
+            ("a%b%c", "aXbXbXc", true),
+            ("a%b%c", "aXcXb", false),
+            ("%a%a", "aaa", true),
+            ("_%_", "a", false),
+            ("", "", true),
+            ("%", "", true),
+            ("a\\_c", "abc", false),
+            ("a\\_c", "a_c", true),
+            ("%中_", "中文", true),
+        ];
+        for (pattern, s, expected) in cases {
+            assert_eq!(
+                LikePattern::new(pattern).matches(s),
+                expected,
+                "{} LIKE {}",
+                s,
+                pattern
+            );
+        }
+    }
+
+    #[test]
+    fn like_pattern_range() {
+        let range = |pattern| LikePattern::new(pattern).range();
+        let some = |begin: &str, end: &str| Some((begin.to_string(), Some(end.to_string())));
+        assert_eq!(range("abc"), some("abc", "abc"));
+        assert_eq!(range("abc%"), some("abc", "abd"));
+        assert_eq!(range("ab_c%"), some("ab", "ac"));
+        assert_eq!(range("a\u{10ffff}%"), some("a\u{10ffff}", "b"));
+        assert_eq!(range("\u{10ffff}%"), Some(("\u{10ffff}".to_string(), None)));
+        assert_eq!(range("%abc"), None);
+        assert_eq!(range("_abc"), None);
+    }
+
+    #[test]
+    fn like_array() {
+        let a: Utf8Array = [Some("Apple"), Some("banana"), None, Some("cherry")]
+            .into_iter()
+            .collect();
+        let p: Utf8Array = [Some("a%"); 4].into_iter().collect();
+        let result: Vec<_> = a.like(&p, false).iter().map(|b| b.cloned()).collect();
+        assert_eq!(result, [Some(false), Some(false), None, Some(false)]);
+        let result: Vec<_> = a.like(&p, true).iter().map(|b| b.cloned()).collect();
+        assert_eq!(result, [Some(true), Some(false), None, Some(false)]);
+
+        let p: Utf8Array = [Some("%e"), Some("%an%"), Some("%"), None]
+            .into_iter()
+            .collect();
+        let result: Vec<_> = a.like(&p, false).iter().map(|b| b.cloned()).collect();
+        assert_eq!(result, [Some(true), Some(true), None, None]);
+    }
+}
@@ -15,12 +15,14 @@ use crate::types::{Blob, ConvertError, DataType, DataValue, Date, Interval, Phys
 mod data_chunk;
 mod data_chunk_builder;
 mod iterator;
+mod like;
 mod primitive_array;
 mod utf8_array;
 
 pub use self::data_chunk::*;
 pub use self::data_chunk_builder::*;
 pub use self::iterator::ArrayIter;
+pub use self::like::*;
 pub use self::primitive_array::*;
 pub use self::utf8_array::*;
 
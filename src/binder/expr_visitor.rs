@@ -16,6 +16,12 @@ pub trait ExprVisitor {
             BoundExpr::IsNull(expr) => self.visit_is_null(expr),
             BoundExpr::ExprWithAlias(expr) => self.visit_expr_with_alias(expr),
             BoundExpr::Alias(expr) => self.visit_alias(expr),
+            BoundExpr::Subquery(expr) => self.visit_subquery(expr),
+            BoundExpr::CorrelatedRef(expr) => self.visit_correlated_ref(expr),
+            BoundExpr::Case(expr) => self.visit_case(expr),
+            BoundExpr::InList(expr) => self.visit_in_list(expr),
+            BoundExpr::FunctionCall(expr) => self.visit_function_call(expr),
+            BoundExpr::WindowCall(expr) => self.visit_window_call(expr),
         }
     }
 
@@ -53,6 +59,51 @@ pub trait ExprVisitor {
     }
 
     fn visit_alias(&mut self, _: &BoundAlias) {}
+
+    /// The subquery itself is planned separately, so only the outer operand of `IN` is visited.
+    fn visit_subquery(&mut self, expr: &BoundSubquery) {
+        if let BoundSubqueryKind::In(expr) = &expr.kind {
+            self.visit_expr(expr.as_ref());
+        }
+    }
+
+    /// The column of a correlated reference belongs to the outer query, so it is not visited.
+    fn visit_correlated_ref(&mut self, _: &BoundCorrelatedRef) {}
+
+    fn visit_case(&mut self, expr: &BoundCase) {
+        for (condition, result) in &expr.branches {
+            self.visit_expr(condition);
+            self.visit_expr(result);
+        }
+        if let Some(else_result) = &expr.else_result {
+            self.visit_expr(else_result.as_ref());
+        }
+    }
+
+    fn visit_in_list(&mut self, expr: &BoundInList) {
+        self.visit_expr(&expr.expr);
+        for item in &expr.list {
+            self.visit_expr(item);
+        }
+    }
+
+    fn visit_function_call(&mut self, expr: &BoundFunctionCall) {
+        for arg in &expr.args {
+            self.visit_expr(arg);
+        }
+    }
+
+    fn visit_window_call(&mut self, expr: &BoundWindowCall) {
+        for arg in &expr.args {
+            self.visit_expr(arg);
+        }
+        for expr in &expr.partition_by {
+            self.visit_expr(expr);
+        }
+        for order in &expr.order_by {
+            self.visit_expr(&order.expr);
+        }
+    }
 }
 
 pub trait ExprRewriter {
@@ -68,6 +119,12 @@ pub trait ExprRewriter {
             BoundExpr::IsNull(_) => self.rewrite_is_null(expr),
             BoundExpr::ExprWithAlias(_) => self.rewrite_expr_with_alias(expr),
             BoundExpr::Alias(_) => self.rewrite_alias(expr),
+            BoundExpr::Subquery(_) => self.rewrite_subquery(expr),
+            BoundExpr::CorrelatedRef(_) => self.rewrite_correlated_ref(expr),
+            BoundExpr::Case(_) => self.rewrite_case(expr),
+            BoundExpr::InList(_) => self.rewrite_in_list(expr),
+            BoundExpr::FunctionCall(_) => self.rewrite_function_call(expr),
+            BoundExpr::WindowCall(_) => self.rewrite_window_call(expr),
         }
     }
 
@@ -127,4 +184,72 @@ pub trait ExprRewriter {
     }
 
     fn rewrite_alias(&self, _: &mut BoundExpr) {}
+
+    fn rewrite_subquery(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::Subquery(expr) => {
+                if let BoundSubqueryKind::In(expr) = &mut expr.kind {
+                    self.rewrite_expr(expr.as_mut());
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_correlated_ref(&self, _: &mut BoundExpr) {}
+
+    fn rewrite_case(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::Case(expr) => {
+                for (condition, result) in &mut expr.branches {
+                    self.rewrite_expr(condition);
+                    self.rewrite_expr(result);
+                }
+                if let Some(else_result) = &mut expr.else_result {
+                    self.rewrite_expr(else_result.as_mut());
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_in_list(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::InList(expr) => {
+                self.rewrite_expr(&mut expr.expr);
+                for item in &mut expr.list {
+                    self.rewrite_expr(item);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_function_call(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::FunctionCall(expr) => {
+                for arg in &mut expr.args {
+                    self.rewrite_expr(arg);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_window_call(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::WindowCall(expr) => {
+                for arg in &mut expr.args {
+                    self.rewrite_expr(arg);
+                }
+                for expr in &mut expr.partition_by {
+                    self.rewrite_expr(expr);
+                }
+                for order in &mut expr.order_by {
+                    self.rewrite_expr(&mut order.expr);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
 }
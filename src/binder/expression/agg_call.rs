@@ -44,15 +44,19 @@ pub struct BoundAggCall {
     pub kind: AggKind,
     pub args: Vec<BoundExpr>,
     pub return_type: DataType,
-    // TODO: add distinct keyword
+    /// Whether only distinct values of the argument are aggregated
+    pub distinct: bool,
 }
 
 impl std::fmt::Debug for BoundAggCall {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{:?}({:?}) -> {:?}",
-            self.kind, self.args, self.return_type
+            "{:?}({}{:?}) -> {:?}",
+            self.kind,
+            if self.distinct { "distinct " } else { "" },
+            self.args,
+            self.return_type
         )
     }
 }
@@ -61,8 +65,9 @@ impl std::fmt::Display for BoundAggCall {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}({}) -> {}",
+            "{}({}{}) -> {}",
             self.kind,
+            if self.distinct { "distinct " } else { "" },
             self.args.iter().map(|x| format!("{}", x)).join(", "),
             self.return_type
         )
@@ -88,7 +93,33 @@ impl Binder {
                 _ => todo!("Support aggregate argument: {:?}", arg),
             }
         }
-        let (kind, return_type) = match func.name.to_string().to_lowercase().as_str() {
+        let name = func.name.to_string().to_lowercase();
+        if let Some(window) = &func.over {
+            if func.distinct {
+                return Err(BindError::InvalidExpression(
+                    "DISTINCT is not supported in window functions".into(),
+                ));
+            }
+            return self.bind_window_call(&name, args, window);
+        }
+        match name.as_str() {
+            "coalesce" => return bind_coalesce(args),
+            "nullif" => return bind_nullif(args),
+            "avg" | "count" | "max" | "min" | "sum" => {}
+            _ => return self.bind_function_call(&name, args),
+        }
+        if args.iter().any(|arg| arg.contains_window_call()) {
+            return Err(BindError::InvalidExpression(
+                "aggregate function calls cannot contain window function calls".into(),
+            ));
+        }
+        let distinct = func.distinct;
+        if distinct && args.is_empty() {
+            return Err(BindError::InvalidExpression(
+                "DISTINCT requires an argument".into(),
+            ));
+        }
+        let (kind, return_type) = match name.as_str() {
             "avg" => (AggKind::Avg, args[0].return_type()),
             "count" => {
                 if args.is_empty() {
@@ -111,7 +142,7 @@ impl Binder {
             "max" => (AggKind::Max, args[0].return_type()),
             "min" => (AggKind::Min, args[0].return_type()),
             "sum" => (AggKind::Sum, args[0].return_type()),
-            _ => panic!("Unsupported function: {}", func.name),
+            _ => unreachable!(),
         };
 
         match kind {
@@ -122,6 +153,7 @@ impl Binder {
                     kind: AggKind::Sum,
                     args: args.clone(),
                     return_type: args[0].return_type().unwrap(),
+                    distinct,
                 })),
                 right_expr: Box::new(BoundExpr::TypeCast(BoundTypeCast {
                     ty: args[0].return_type().unwrap().kind(),
@@ -129,6 +161,7 @@ impl Binder {
                         kind: AggKind::Count,
                         args,
                         return_type: DataType::new(DataTypeKind::Int(None), false),
+                        distinct,
                     })),
                 })),
                 return_type,
@@ -137,6 +170,7 @@ impl Binder {
                 kind,
                 args,
                 return_type: return_type.unwrap(),
+                distinct,
             })),
         }
     }
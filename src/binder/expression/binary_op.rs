@@ -4,7 +4,7 @@ use serde::Serialize;
 
 use super::*;
 use crate::parser::BinaryOperator;
-use crate::types::{DataTypeExt, DataTypeKind};
+use crate::types::{DataTypeExt, DataTypeKind, PhysicalDataTypeKind};
 
 /// A bound binary operation expression.
 #[derive(PartialEq, Clone, Serialize)]
@@ -40,66 +40,27 @@ impl Binder {
     ) -> Result<BoundExpr, BindError> {
         use BinaryOperator as Op;
 
-        use crate::types::PhysicalDataTypeKind::*;
-        let mut left_bound_expr = self.bind_expr(left)?;
-        let mut right_bound_expr = self.bind_expr(right)?;
-
-        // Implicit type cast
-        let left_data_type_kind = match (
-            left_bound_expr.return_type(),
-            right_bound_expr.return_type(),
-        ) {
-            (Some(left_data_type), Some(right_data_type)) => {
-                let left_physical_kind = left_data_type.physical_kind();
-                let right_physical_kind = right_data_type.physical_kind();
-                let mut return_type_tmp = left_data_type.kind();
-                // Check if implicit type conversion is needed
-                if left_physical_kind != right_physical_kind {
-                    // Insert type cast expr
-                    match (left_physical_kind, right_physical_kind) {
-                        (Float64 | Decimal, Int32 | Int64)
-                        | (Int64, Int32)
-                        | (Date, String)
-                        | (Decimal, Float64) => {
-                            right_bound_expr = BoundExpr::TypeCast(BoundTypeCast {
-                                expr: Box::new(right_bound_expr),
-                                ty: left_data_type.kind(),
-                            });
-                        }
-                        (Int32 | Int64, Float64 | Decimal)
-                        | (Int32, Int64)
-                        | (String, Date)
-                        | (Float64, Decimal) => {
-                            left_bound_expr = BoundExpr::TypeCast(BoundTypeCast {
-                                expr: Box::new(left_bound_expr),
-                                ty: right_data_type.kind(),
-                            });
-                            return_type_tmp = right_data_type.kind();
-                        }
-                        (Date, Interval) => {}
-                        (left_kind, right_kind) => todo!(
-                            "Support implicit conversion of {:?} and {:?}",
-                            left_kind,
-                            right_kind
-                        ),
-                    }
-                }
-                Some(return_type_tmp.nullable())
-            }
-            (None, None) => None,
-            (left, right) => {
-                return Err(BindError::BinaryOpTypeMismatch(
-                    format!("{:?}", left),
-                    format!("{:?}", right),
-                ))
-            }
-        };
+        let left_bound_expr = self.bind_expr(left)?;
+        let right_bound_expr = self.bind_expr(right)?;
+        let (left_bound_expr, right_bound_expr, left_data_type_kind) =
+            implicit_type_cast(left_bound_expr, right_bound_expr)?;
 
         let return_type = match op {
             Op::Plus | Op::Minus | Op::Multiply | Op::Divide | Op::Modulo => left_data_type_kind,
             Op::Gt | Op::GtEq | Op::Lt | Op::LtEq | Op::Eq | Op::NotEq | Op::And | Op::Or => {
                 Some(DataTypeKind::Boolean.nullable())
             }
+            Op::Like | Op::NotLike | Op::ILike | Op::NotILike => {
+                if let Some(ty) = &left_data_type_kind {
+                    if ty.physical_kind() != PhysicalDataTypeKind::String {
+                        return Err(BindError::InvalidExpression(format!(
+                            "{} can only be applied to strings: {}",
+                            op, left
+                        )));
+                    }
+                }
+                Some(DataTypeKind::Boolean.nullable())
+            }
             _ => todo!("Support more binary operators"),
         };
         Ok(BoundExpr::BinaryOp(BoundBinaryOp {
@@ -110,3 +71,111 @@ impl Binder {
         }))
     }
 }
+
+/// Insert implicit type casts so that both expressions have the same physical type.
+///
+/// Returns the casted expressions and their common type.
+pub(in crate::binder) fn implicit_type_cast(
+    mut left_bound_expr: BoundExpr,
+    mut right_bound_expr: BoundExpr,
+) -> Result<(BoundExpr, BoundExpr, Option<DataType>), BindError> {
+    use crate::types::PhysicalDataTypeKind::*;
+
+    let data_type = match (
+        left_bound_expr.return_type(),
+        right_bound_expr.return_type(),
+    ) {
+        (Some(left_data_type), Some(right_data_type)) => {
+            let left_physical_kind = left_data_type.physical_kind();
+            let right_physical_kind = right_data_type.physical_kind();
+            let mut return_type_tmp = left_data_type.kind();
+            // Check if implicit type conversion is needed
+            if left_physical_kind != right_physical_kind {
+                // Insert type cast expr
+                match (left_physical_kind, right_physical_kind) {
+                    (Float64 | Decimal, Int32 | Int64)
+                    | (Int64, Int32)
+                    | (Date, String)
+                    | (Decimal, Float64) => {
+                        right_bound_expr = BoundExpr::TypeCast(BoundTypeCast {
+                            expr: Box::new(right_bound_expr),
+                            ty: left_data_type.kind(),
+                        });
+                    }
+                    (Int32 | Int64, Float64 | Decimal)
+                    | (Int32, Int64)
+                    | (String, Date)
+                    | (Float64, Decimal) => {
+                        left_bound_expr = BoundExpr::TypeCast(BoundTypeCast {
+                            expr: Box::new(left_bound_expr),
+                            ty: right_data_type.kind(),
+                        });
+                        return_type_tmp = right_data_type.kind();
+                    }
+                    (Date, Interval) => {}
+                    (left_kind, right_kind) => {
+                        return Err(BindError::BinaryOpTypeMismatch(
+                            format!("{:?}", left_kind),
+                            format!("{:?}", right_kind),
+                        ))
+                    }
+                }
+            }
+            Some(return_type_tmp.nullable())
+        }
+        (None, None) => None,
+        (left, right) => {
+            return Err(BindError::BinaryOpTypeMismatch(
+                format!("{:?}", left),
+                format!("{:?}", right),
+            ))
+        }
+    };
+    Ok((left_bound_expr, right_bound_expr, data_type))
+}
+
+/// Insert implicit type casts so that all expressions have the same physical type.
+///
+/// Returns the casted expressions and their common type, which is `None` if all expressions are
+/// `NULL`.
+pub(in crate::binder) fn unify_types(
+    exprs: Vec<BoundExpr>,
+) -> Result<(Vec<BoundExpr>, Option<DataType>), BindError> {
+    // an expression of the common type
+    let mut common: Option<BoundExpr> = None;
+    for expr in exprs.iter().filter(|expr| expr.return_type().is_some()) {
+        common = Some(match common {
+            None => expr.clone(),
+            Some(common) => {
+                let (common, expr, _) = implicit_type_cast(common, expr.clone())?;
+                let common_type = common.return_type().unwrap();
+                let data_type = expr.return_type().unwrap();
+                if common_type.physical_kind() != data_type.physical_kind() {
+                    return Err(BindError::BinaryOpTypeMismatch(
+                        format!("{:?}", common_type),
+                        format!("{:?}", data_type),
+                    ));
+                }
+                common
+            }
+        });
+    }
+    let common_type = match common {
+        Some(common) => common.return_type().unwrap(),
+        None => return Ok((exprs, None)),
+    };
+    let nullable = exprs
+        .iter()
+        .any(|expr| expr.return_type().map_or(true, |ty| ty.is_nullable()));
+    let exprs = exprs
+        .into_iter()
+        .map(|expr| match expr.return_type() {
+            Some(ty) if ty.physical_kind() == common_type.physical_kind() => expr,
+            _ => BoundExpr::TypeCast(BoundTypeCast {
+                expr: Box::new(expr),
+                ty: common_type.kind(),
+            }),
+        })
+        .collect();
+    Ok((exprs, Some(DataType::new(common_type.kind(), nullable))))
+}
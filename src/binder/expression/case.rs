@@ -0,0 +1,144 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use super::*;
+
+/// A bound `CASE` expression, which returns the result of the first branch whose condition is
+/// true, or the `ELSE` result if there is no such branch.
+///
+/// `COALESCE` and `NULLIF` are also bound to it.
+#[derive(PartialEq, Clone, Serialize)]
+pub struct BoundCase {
+    /// The conditions and results of the `WHEN ... THEN ...` branches.
+    pub branches: Vec<(BoundExpr, BoundExpr)>,
+    pub else_result: Option<Box<BoundExpr>>,
+    pub return_type: Option<DataType>,
+}
+
+impl std::fmt::Debug for BoundCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "case")?;
+        for (condition, result) in &self.branches {
+            write!(f, " when {:?} then {:?}", condition, result)?;
+        }
+        if let Some(else_result) = &self.else_result {
+            write!(f, " else {:?}", else_result)?;
+        }
+        write!(f, " end")
+    }
+}
+
+impl std::fmt::Display for BoundCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "case")?;
+        for (condition, result) in &self.branches {
+            write!(f, " when {} then {}", condition, result)?;
+        }
+        if let Some(else_result) = &self.else_result {
+            write!(f, " else {}", else_result)?;
+        }
+        write!(f, " end")
+    }
+}
+
+impl Binder {
+    pub fn bind_case(
+        &mut self,
+        operand: Option<&Expr>,
+        conditions: &[Expr],
+        results: &[Expr],
+        else_result: Option<&Expr>,
+    ) -> Result<BoundExpr, BindError> {
+        let mut bound_conditions = vec![];
+        for condition in conditions {
+            // `CASE x WHEN v ...` is equivalent to `CASE WHEN x = v ...`
+            let condition = match operand {
+                Some(operand) => self.bind_binary_op(operand, &BinaryOperator::Eq, condition)?,
+                None => self.bind_expr(condition)?,
+            };
+            bound_conditions.push(condition);
+        }
+        let results: Vec<_> = results
+            .iter()
+            .map(|expr| self.bind_expr(expr))
+            .try_collect()?;
+        let else_result = else_result.map(|expr| self.bind_expr(expr)).transpose()?;
+        build_case(bound_conditions, results, else_result)
+    }
+}
+
+/// Bind `COALESCE(a, b, ...)` as
+/// `CASE WHEN a IS NOT NULL THEN a WHEN b IS NOT NULL THEN b ... END`.
+pub(crate) fn bind_coalesce(args: Vec<BoundExpr>) -> Result<BoundExpr, BindError> {
+    if args.is_empty() {
+        return Err(BindError::InvalidExpression(
+            "coalesce requires at least one argument".into(),
+        ));
+    }
+    let conditions = args
+        .iter()
+        .map(|arg| {
+            BoundExpr::UnaryOp(BoundUnaryOp {
+                op: UnaryOperator::Not,
+                expr: Box::new(BoundExpr::IsNull(BoundIsNull {
+                    expr: Box::new(arg.clone()),
+                })),
+                return_type: Some(DataTypeKind::Boolean.not_null()),
+            })
+        })
+        .collect();
+    build_case(conditions, args, None)
+}
+
+/// Bind `NULLIF(a, b)` as `CASE WHEN a = b THEN NULL ELSE a END`.
+pub(super) fn bind_nullif(args: Vec<BoundExpr>) -> Result<BoundExpr, BindError> {
+    let (left, right) = args.into_iter().collect_tuple().ok_or_else(|| {
+        BindError::InvalidExpression("nullif requires exactly two arguments".into())
+    })?;
+    let (left_expr, right_expr, _) = implicit_type_cast(left.clone(), right)?;
+    let condition = BoundExpr::BinaryOp(BoundBinaryOp {
+        op: BinaryOperator::Eq,
+        left_expr: Box::new(left_expr),
+        right_expr: Box::new(right_expr),
+        return_type: Some(DataTypeKind::Boolean.nullable()),
+    });
+    build_case(
+        vec![condition],
+        vec![BoundExpr::Constant(DataValue::Null)],
+        Some(left),
+    )
+}
+
+/// Build a `CASE` expression, whose results are casted to their common type.
+fn build_case(
+    conditions: Vec<BoundExpr>,
+    results: Vec<BoundExpr>,
+    else_result: Option<BoundExpr>,
+) -> Result<BoundExpr, BindError> {
+    for condition in &conditions {
+        if let Some(ty) = condition.return_type() {
+            if ty.kind() != DataTypeKind::Boolean {
+                return Err(BindError::InvalidExpression(format!(
+                    "the condition of CASE must be boolean: {}",
+                    condition
+                )));
+            }
+        }
+    }
+    let has_else = else_result.is_some();
+    let (mut results, return_type) = unify_types(results.into_iter().chain(else_result).collect())?;
+    let else_result = if has_else {
+        results.pop().map(Box::new)
+    } else {
+        None
+    };
+    // null is returned if no condition is true and there is no `ELSE`
+    let return_type = return_type.map(|ty| DataType::new(ty.kind(), ty.is_nullable() || !has_else));
+    Ok(BoundExpr::Case(BoundCase {
+        branches: conditions.into_iter().zip(results).collect(),
+        else_result,
+        return_type,
+    }))
+}
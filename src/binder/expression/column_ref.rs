@@ -22,17 +22,29 @@ impl std::fmt::Debug for BoundColumnRef {
 
 impl Binder {
     pub fn bind_all_column_refs(&mut self) -> Result<Vec<BoundExpr>, BindError> {
+        // the columns merged by joins come first, and they are not output again by each table
         let mut exprs = vec![];
-        for ref_id in self.context.regular_tables.values().cloned().collect_vec() {
-            let table = self.catalog.get_table(&ref_id).unwrap();
+        for (name, expr) in &self.context.using_columns {
+            exprs.push(match expr {
+                BoundExpr::ColumnRef(_) => expr.clone(),
+                _ => BoundExpr::ExprWithAlias(BoundExprWithAlias {
+                    expr: Box::new(expr.clone()),
+                    alias: name.clone(),
+                }),
+            });
+        }
+        for name in self.context.table_names.clone() {
+            let ref_id = self.context.regular_tables[&name];
+            let table = self.get_table(&ref_id);
+            let merged_columns = self.context.merged_columns.get(&name).cloned();
             for (col_id, col) in &table.all_columns() {
+                if let Some(merged_columns) = &merged_columns {
+                    if merged_columns.contains(col.name()) {
+                        continue;
+                    }
+                }
                 let column_ref_id = ColumnRefId::from_table(ref_id, *col_id);
-                self.record_regular_table_column(
-                    &table.name(),
-                    col.name(),
-                    *col_id,
-                    col.desc().clone(),
-                );
+                self.record_regular_table_column(&name, col.name(), *col_id, col.desc().clone());
                 let expr = BoundExpr::ColumnRef(BoundColumnRef {
                     column_ref_id,
                     is_primary_key: col.is_primary(),
@@ -58,10 +70,15 @@ impl Binder {
         };
         if let Some(name) = table_name {
             if !self.context.regular_tables.contains_key(name) {
+                if let Some(expr) =
+                    self.bind_correlated_column_ref(&idents, table_name, column_name)?
+                {
+                    return Ok(expr);
+                }
                 return Err(BindError::InvalidTable(name.clone()));
             }
             let table_ref_id = self.context.regular_tables[name];
-            let table = self.catalog.get_table(&table_ref_id).unwrap();
+            let table = self.get_table(&table_ref_id);
             let col = table
                 .get_column_by_name(column_name)
                 .ok_or_else(|| BindError::InvalidColumn(column_name.clone()))?;
@@ -73,16 +90,22 @@ impl Binder {
                 desc: col.desc().clone(),
             }))
         } else {
+            // a column merged by joins is not ambiguous
+            if let Some((_, expr)) =
+                (self.context.using_columns.iter()).find(|(name, _)| name == column_name)
+            {
+                return Ok(expr.clone());
+            }
             let mut info = None;
-            for ref_id in self.context.regular_tables.values() {
-                let table = self.catalog.get_table(ref_id).unwrap();
+            for (name, ref_id) in &self.context.regular_tables {
+                let table = self.get_table(ref_id);
                 if let Some(col) = table.get_column_by_name(column_name) {
                     if info.is_some() {
                         return Err(BindError::AmbiguousColumn);
                     }
                     let column_ref_id = ColumnRefId::from_table(*ref_id, col.id());
                     info = Some((
-                        table.name().clone(),
+                        name.clone(),
                         column_ref_id,
                         col.is_primary(),
                         col.desc().clone(),
@@ -100,6 +123,10 @@ impl Binder {
                         alias: column_name.clone(),
                         expr: Box::new(self.context.aliases_expressions[index].clone()),
                     }))
+                } else if let Some(expr) =
+                    self.bind_correlated_column_ref(&idents, table_name, column_name)?
+                {
+                    Ok(expr)
                 } else {
                     Err(BindError::InvalidColumn(column_name.clone()))
                 }
@@ -122,6 +149,45 @@ impl Binder {
         }
     }
 
+    /// Bind a column of the outer query in a correlated subquery.
+    ///
+    /// Returns `None` if the column can not be found in any outer query.
+    fn bind_correlated_column_ref(
+        &mut self,
+        idents: &[Ident],
+        table_name: Option<&String>,
+        column_name: &str,
+    ) -> Result<Option<BoundExpr>, BindError> {
+        let has_column = |context: &BinderContext| {
+            let has_column = |ref_id: &TableRefId| {
+                let table = self.get_table(ref_id);
+                table.get_column_by_name(column_name).is_some()
+            };
+            match table_name {
+                Some(name) => context.regular_tables.get(name).map_or(false, has_column),
+                None => context.regular_tables.values().any(has_column),
+            }
+        };
+        match self.upper_contexts.last() {
+            Some(context) if has_column(context) => {}
+            // only columns of the immediate outer query are supported
+            _ if self.upper_contexts.iter().any(has_column) => {
+                return Err(BindError::CorrelatedSubquery(
+                    idents.iter().map(|ident| &ident.value).join("."),
+                ));
+            }
+            _ => return Ok(None),
+        }
+        // Bind the column in the context of the outer query, so that it will be recorded as a
+        // required column of the outer table.
+        std::mem::swap(&mut self.context, self.upper_contexts.last_mut().unwrap());
+        let ret = self.bind_column_ref(idents);
+        std::mem::swap(&mut self.context, self.upper_contexts.last_mut().unwrap());
+        Ok(Some(BoundExpr::CorrelatedRef(BoundCorrelatedRef {
+            expr: Box::new(ret?),
+        })))
+    }
+
     pub fn record_regular_table_column(
         &mut self,
         table_name: &str,
@@ -0,0 +1,126 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::sync::Arc;
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use super::*;
+use crate::function::Function;
+use crate::types::PhysicalDataTypeKind;
+
+/// A bound call of a scalar function.
+#[derive(Clone, Serialize)]
+pub struct BoundFunctionCall {
+    pub name: String,
+    pub args: Vec<BoundExpr>,
+    pub return_type: DataType,
+    #[serde(skip)]
+    pub function: Arc<dyn Function>,
+}
+
+impl PartialEq for BoundFunctionCall {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.args == other.args && self.return_type == other.return_type
+    }
+}
+
+impl std::fmt::Debug for BoundFunctionCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({:?}) -> {:?}",
+            self.name,
+            self.args.iter().format(", "),
+            self.return_type
+        )
+    }
+}
+
+impl std::fmt::Display for BoundFunctionCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", self.name, self.args.iter().format(", "))
+    }
+}
+
+impl Binder {
+    /// Bind a call of the scalar function `name` in the function registry.
+    pub(super) fn bind_function_call(
+        &mut self,
+        name: &str,
+        args: Vec<BoundExpr>,
+    ) -> Result<BoundExpr, BindError> {
+        let arg_types: Vec<_> = args.iter().map(|arg| arg.return_type()).collect();
+        let invalid_function = || {
+            BindError::InvalidFunction(format!(
+                "{}({})",
+                name,
+                arg_types
+                    .iter()
+                    .map(|ty| match ty {
+                        Some(ty) => ty.kind().to_string(),
+                        None => "NULL".into(),
+                    })
+                    .join(", ")
+            ))
+        };
+        let physical_types: Vec<_> = arg_types
+            .iter()
+            .map(|ty| ty.as_ref().map(|ty| ty.physical_kind()))
+            .collect::<Option<_>>()
+            .ok_or_else(invalid_function)?;
+        let (function, physical_return_type) = self
+            .functions
+            .resolve(name, &physical_types)
+            .ok_or_else(invalid_function)?;
+
+        // prefer the logical type of an argument with the same physical type, e.g. `abs(float)`
+        // returns float instead of double
+        let kind = arg_types
+            .iter()
+            .flatten()
+            .map(|ty| ty.kind())
+            .find(|kind| PhysicalDataTypeKind::from(kind.clone()) == physical_return_type)
+            .unwrap_or_else(|| default_kind(&physical_return_type));
+        let nullable = arg_types.iter().flatten().any(|ty| ty.is_nullable());
+        Ok(BoundExpr::FunctionCall(BoundFunctionCall {
+            name: name.into(),
+            args,
+            return_type: DataType::new(kind, nullable),
+            function,
+        }))
+    }
+
+    /// Bind `SUBSTRING(expr [FROM start] [FOR count])` as a call of `substring`.
+    pub(super) fn bind_substring(
+        &mut self,
+        expr: &Expr,
+        start: Option<&Expr>,
+        count: Option<&Expr>,
+    ) -> Result<BoundExpr, BindError> {
+        let mut args = vec![self.bind_expr(expr)?];
+        args.push(match start {
+            Some(start) => self.bind_expr(start)?,
+            None => BoundExpr::Constant(DataValue::Int32(1)),
+        });
+        if let Some(count) = count {
+            args.push(self.bind_expr(count)?);
+        }
+        self.bind_function_call("substring", args)
+    }
+}
+
+/// The default logical type of a physical type.
+fn default_kind(physical_kind: &PhysicalDataTypeKind) -> DataTypeKind {
+    match physical_kind {
+        PhysicalDataTypeKind::Int32 => DataTypeKind::Int(None),
+        PhysicalDataTypeKind::Int64 => DataTypeKind::BigInt(None),
+        PhysicalDataTypeKind::Float64 => DataTypeKind::Double,
+        PhysicalDataTypeKind::String => DataTypeKind::String,
+        PhysicalDataTypeKind::Blob => DataTypeKind::Blob(0),
+        PhysicalDataTypeKind::Bool => DataTypeKind::Boolean,
+        PhysicalDataTypeKind::Decimal => DataTypeKind::Decimal(None, None),
+        PhysicalDataTypeKind::Date => DataTypeKind::Date,
+        PhysicalDataTypeKind::Interval => DataTypeKind::Interval,
+    }
+}
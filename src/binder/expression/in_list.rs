@@ -0,0 +1,58 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use super::*;
+
+/// A bound `IN` list expression, like `x IN (1, 2, 3)`.
+#[derive(PartialEq, Clone, Serialize)]
+pub struct BoundInList {
+    pub expr: Box<BoundExpr>,
+    pub list: Vec<BoundExpr>,
+    pub negated: bool,
+}
+
+impl std::fmt::Debug for BoundInList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} {}in ({:?})",
+            self.expr,
+            if self.negated { "not " } else { "" },
+            self.list.iter().format(", ")
+        )
+    }
+}
+
+impl std::fmt::Display for BoundInList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}in ({})",
+            self.expr,
+            if self.negated { "not " } else { "" },
+            self.list.iter().format(", ")
+        )
+    }
+}
+
+impl Binder {
+    pub fn bind_in_list(
+        &mut self,
+        expr: &Expr,
+        list: &[Expr],
+        negated: bool,
+    ) -> Result<BoundExpr, BindError> {
+        let expr = self.bind_expr(expr)?;
+        let list: Vec<_> = list.iter().map(|expr| self.bind_expr(expr)).try_collect()?;
+        // the expression and the list are casted to their common type
+        let (mut exprs, _) = unify_types(std::iter::once(expr).chain(list).collect())?;
+        let expr = exprs.remove(0);
+        Ok(BoundExpr::InList(BoundInList {
+            expr: Box::new(expr),
+            list: exprs,
+            negated,
+        }))
+    }
+}
@@ -11,21 +11,31 @@ use crate::types::{DataType, DataTypeExt, DataTypeKind, DataValue, Interval};
 
 mod agg_call;
 mod binary_op;
+mod case;
 mod column_ref;
 mod expr_with_alias;
+mod function_call;
+mod in_list;
 mod input_ref;
 mod isnull;
+mod subquery;
 mod type_cast;
 mod unary_op;
+mod window;
 
 pub use self::agg_call::*;
 pub use self::binary_op::*;
+pub use self::case::*;
 pub use self::column_ref::*;
 pub use self::expr_with_alias::*;
+pub use self::function_call::*;
+pub use self::in_list::*;
 pub use self::input_ref::*;
 pub use self::isnull::*;
+pub use self::subquery::*;
 pub use self::type_cast::*;
 pub use self::unary_op::*;
+pub use self::window::*;
 
 /// A bound expression.
 #[derive(PartialEq, Clone, Serialize)]
@@ -41,6 +51,12 @@ pub enum BoundExpr {
     IsNull(BoundIsNull),
     ExprWithAlias(BoundExprWithAlias),
     Alias(BoundAlias),
+    Subquery(BoundSubquery),
+    CorrelatedRef(BoundCorrelatedRef),
+    Case(BoundCase),
+    InList(BoundInList),
+    FunctionCall(BoundFunctionCall),
+    WindowCall(BoundWindowCall),
 }
 
 impl BoundExpr {
@@ -56,6 +72,12 @@ impl BoundExpr {
             Self::IsNull(_) => Some(DataTypeKind::Boolean.not_null()),
             Self::ExprWithAlias(expr) => expr.expr.return_type(),
             Self::Alias(expr) => expr.expr.return_type(),
+            Self::Subquery(expr) => Some(expr.return_type.clone()),
+            Self::CorrelatedRef(expr) => expr.expr.return_type(),
+            Self::Case(expr) => expr.return_type.clone(),
+            Self::InList(_) => Some(DataTypeKind::Boolean.nullable()),
+            Self::FunctionCall(expr) => Some(expr.return_type.clone()),
+            Self::WindowCall(expr) => Some(expr.return_type.clone()),
         }
     }
 
@@ -96,6 +118,10 @@ impl BoundExpr {
             fn visit_agg_call(&mut self, expr: &BoundAggCall) {
                 self.0 = expr.kind == AggKind::RowCount;
             }
+            // window functions like `row_number()` need rows even if no column is referred
+            fn visit_window_call(&mut self, _: &BoundWindowCall) {
+                self.0 = true;
+            }
         }
         let mut visitor = Visitor(false);
         visitor.visit_expr(self);
@@ -134,6 +160,18 @@ impl BoundExpr {
         visitor.0
     }
 
+    pub fn contains_window_call(&self) -> bool {
+        struct Visitor(bool);
+        impl ExprVisitor for Visitor {
+            fn visit_window_call(&mut self, _: &BoundWindowCall) {
+                self.0 = true;
+            }
+        }
+        let mut visitor = Visitor(false);
+        visitor.visit_expr(self);
+        visitor.0
+    }
+
     pub fn format_name(&self, child_schema: &Vec<ColumnDesc>) -> String {
         match self {
             Self::Constant(DataValue::Int64(num)) => format!("{}", num),
@@ -167,6 +205,12 @@ impl std::fmt::Debug for BoundExpr {
             Self::IsNull(expr) => write!(f, "{:?} (isnull)", expr)?,
             Self::ExprWithAlias(expr) => write!(f, "{:?}", expr)?,
             Self::Alias(expr) => write!(f, "{:?}", expr)?,
+            Self::Subquery(expr) => write!(f, "{:?}", expr)?,
+            Self::CorrelatedRef(expr) => write!(f, "{:?}", expr)?,
+            Self::Case(expr) => write!(f, "{:?}", expr)?,
+            Self::InList(expr) => write!(f, "{:?}", expr)?,
+            Self::FunctionCall(expr) => write!(f, "{:?}", expr)?,
+            Self::WindowCall(expr) => write!(f, "{:?} (window)", expr)?,
         }
         Ok(())
     }
@@ -185,6 +229,12 @@ impl std::fmt::Display for BoundExpr {
             Self::IsNull(expr) => write!(f, "{:?} (isnull)", expr)?,
             Self::ExprWithAlias(expr) => write!(f, "{}", expr)?,
             Self::Alias(expr) => write!(f, "{:?}", expr)?,
+            Self::Subquery(expr) => write!(f, "{}", expr)?,
+            Self::CorrelatedRef(expr) => write!(f, "{}", expr)?,
+            Self::Case(expr) => write!(f, "{}", expr)?,
+            Self::InList(expr) => write!(f, "{}", expr)?,
+            Self::FunctionCall(expr) => write!(f, "{}", expr)?,
+            Self::WindowCall(expr) => write!(f, "{}", expr)?,
         }
         Ok(())
     }
@@ -202,6 +252,11 @@ impl Binder {
             Expr::Nested(expr) => self.bind_expr(expr),
             Expr::Cast { expr, data_type } => self.bind_type_cast(expr, data_type.clone()),
             Expr::Function(func) => self.bind_function(func),
+            Expr::Substring {
+                expr,
+                substring_from,
+                substring_for,
+            } => self.bind_substring(expr, substring_from.as_deref(), substring_for.as_deref()),
             Expr::IsNull(expr) => self.bind_isnull(expr),
             Expr::IsNotNull(expr) => {
                 let expr = self.bind_isnull(expr)?;
@@ -218,6 +273,29 @@ impl Binder {
                 low,
                 high,
             } => self.bind_between(expr, negated, low, high),
+            Expr::Subquery(query) => self.bind_scalar_subquery(query),
+            Expr::Exists(subquery) => self.bind_exists(subquery, false),
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => self.bind_in_subquery(expr, subquery, *negated),
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => self.bind_case(
+                operand.as_deref(),
+                conditions,
+                results,
+                else_result.as_deref(),
+            ),
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => self.bind_in_list(expr, list, *negated),
             _ => todo!("bind expression: {:?}", expr),
         }
     }
@@ -0,0 +1,127 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use serde::Serialize;
+
+use super::*;
+use crate::parser::Query;
+
+/// The kind of a subquery expression.
+#[derive(PartialEq, Clone, Serialize)]
+pub enum BoundSubqueryKind {
+    /// `(SELECT ...)` which returns a single value.
+    Scalar,
+    /// `[NOT] EXISTS (SELECT ...)`
+    Exists,
+    /// `expr [NOT] IN (SELECT ...)`
+    In(Box<BoundExpr>),
+}
+
+/// A bound subquery expression.
+#[derive(PartialEq, Clone, Serialize)]
+pub struct BoundSubquery {
+    pub kind: BoundSubqueryKind,
+    /// Whether the result of `EXISTS` or `IN` is negated.
+    pub negated: bool,
+    #[serde(skip)]
+    pub query: Box<BoundSelect>,
+    pub return_type: DataType,
+}
+
+impl std::fmt::Debug for BoundSubquery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let not = if self.negated { "not " } else { "" };
+        match &self.kind {
+            BoundSubqueryKind::Scalar => write!(f, "(subquery)"),
+            BoundSubqueryKind::Exists => write!(f, "{}exists (subquery)", not),
+            BoundSubqueryKind::In(expr) => write!(f, "{:?} {}in (subquery)", expr, not),
+        }
+    }
+}
+
+impl std::fmt::Display for BoundSubquery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let not = if self.negated { "not " } else { "" };
+        match &self.kind {
+            BoundSubqueryKind::Scalar => write!(f, "(subquery)"),
+            BoundSubqueryKind::Exists => write!(f, "{}exists (subquery)", not),
+            BoundSubqueryKind::In(expr) => write!(f, "{} {}in (subquery)", expr, not),
+        }
+    }
+}
+
+/// A reference to a column of the outer query in a correlated subquery.
+#[derive(PartialEq, Clone, Serialize)]
+pub struct BoundCorrelatedRef {
+    /// The column in the outer query.
+    ///
+    /// It is a `ColumnRef` after binding, and will be resolved into an `InputRef` to the output
+    /// of the outer plan.
+    pub expr: Box<BoundExpr>,
+}
+
+impl std::fmt::Debug for BoundCorrelatedRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} (outer)", self.expr)
+    }
+}
+
+impl std::fmt::Display for BoundCorrelatedRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (outer)", self.expr)
+    }
+}
+
+impl Binder {
+    /// Bind a scalar subquery: `(SELECT ...)`.
+    pub fn bind_scalar_subquery(&mut self, query: &Query) -> Result<BoundExpr, BindError> {
+        let query = self.bind_select(query)?;
+        if query.select_list.len() != 1 {
+            return Err(BindError::SubqueryColumnCount(query.select_list.len()));
+        }
+        let return_type = query.select_list[0]
+            .return_type()
+            .map(|ty| ty.kind())
+            .unwrap_or(DataTypeKind::Int(None))
+            .nullable();
+        Ok(BoundExpr::Subquery(BoundSubquery {
+            kind: BoundSubqueryKind::Scalar,
+            negated: false,
+            query,
+            return_type,
+        }))
+    }
+
+    /// Bind `[NOT] EXISTS (SELECT ...)`.
+    pub fn bind_exists(&mut self, query: &Query, negated: bool) -> Result<BoundExpr, BindError> {
+        let query = self.bind_select(query)?;
+        Ok(BoundExpr::Subquery(BoundSubquery {
+            kind: BoundSubqueryKind::Exists,
+            negated,
+            query,
+            return_type: DataTypeKind::Boolean.not_null(),
+        }))
+    }
+
+    /// Bind `expr [NOT] IN (SELECT ...)`.
+    pub fn bind_in_subquery(
+        &mut self,
+        expr: &Expr,
+        query: &Query,
+        negated: bool,
+    ) -> Result<BoundExpr, BindError> {
+        let expr = self.bind_expr(expr)?;
+        let mut query = self.bind_select(query)?;
+        if query.select_list.len() != 1 {
+            return Err(BindError::SubqueryColumnCount(query.select_list.len()));
+        }
+        // Cast both sides to the same type, as if they are compared by `=`.
+        let (expr, item, _) = implicit_type_cast(expr, query.select_list[0].clone())?;
+        query.select_list[0] = item;
+        Ok(BoundExpr::Subquery(BoundSubquery {
+            kind: BoundSubqueryKind::In(Box::new(expr)),
+            negated,
+            query,
+            return_type: DataTypeKind::Boolean.nullable(),
+        }))
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use super::*;
+use crate::parser::{WindowFrameBound, WindowFrameUnits, WindowSpec};
+
+/// Window function kind
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub enum WindowFuncKind {
+    RowNumber,
+    Rank,
+    DenseRank,
+    /// The value of the row `offset` rows before the current row.
+    Lag(usize),
+    /// The value of the row `offset` rows after the current row.
+    Lead(usize),
+    /// An aggregate over the window frame.
+    Agg(AggKind),
+}
+
+impl std::fmt::Display for WindowFuncKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RowNumber => write!(f, "row_number"),
+            Self::Rank => write!(f, "rank"),
+            Self::DenseRank => write!(f, "dense_rank"),
+            Self::Lag(_) => write!(f, "lag"),
+            Self::Lead(_) => write!(f, "lead"),
+            Self::Agg(kind) => write!(f, "{}", kind),
+        }
+    }
+}
+
+/// The frame of a window, which is the set of rows in the partition that a framed aggregate is
+/// computed over.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct BoundWindowFrame {
+    pub units: WindowFrameUnits,
+    pub start: WindowFrameBound,
+    pub end: WindowFrameBound,
+}
+
+impl Default for BoundWindowFrame {
+    /// `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`
+    fn default() -> Self {
+        BoundWindowFrame {
+            units: WindowFrameUnits::Range,
+            start: WindowFrameBound::Preceding(None),
+            end: WindowFrameBound::CurrentRow,
+        }
+    }
+}
+
+impl std::fmt::Display for BoundWindowFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} between {} and {}", self.units, self.start, self.end)
+    }
+}
+
+/// Represents a call of a window function, like `rank() OVER (PARTITION BY a ORDER BY b)`.
+#[derive(PartialEq, Clone, Serialize)]
+pub struct BoundWindowCall {
+    pub kind: WindowFuncKind,
+    pub args: Vec<BoundExpr>,
+    pub partition_by: Vec<BoundExpr>,
+    pub order_by: Vec<BoundOrderBy>,
+    pub frame: BoundWindowFrame,
+    pub return_type: DataType,
+}
+
+impl std::fmt::Debug for BoundWindowCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?}({:?}) over (partition by {:?} order by {:?} {}) -> {:?}",
+            self.kind, self.args, self.partition_by, self.order_by, self.frame, self.return_type
+        )
+    }
+}
+
+impl std::fmt::Display for BoundWindowCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({}) over (", self.kind, self.args.iter().format(", "))?;
+        if !self.partition_by.is_empty() {
+            write!(f, "partition by {} ", self.partition_by.iter().format(", "))?;
+        }
+        if !self.order_by.is_empty() {
+            write!(f, "order by {:?} ", self.order_by.iter().format(", "))?;
+        }
+        write!(f, "{})", self.frame)
+    }
+}
+
+impl Binder {
+    /// Bind a call of the window function `name` with the bound arguments.
+    pub(super) fn bind_window_call(
+        &mut self,
+        name: &str,
+        mut args: Vec<BoundExpr>,
+        window: &WindowSpec,
+    ) -> Result<BoundExpr, BindError> {
+        let partition_by: Vec<_> = window
+            .partition_by
+            .iter()
+            .map(|expr| self.bind_expr(expr))
+            .try_collect()?;
+        let order_by = self.bind_order_by(&window.order_by)?;
+        let frame = match &window.window_frame {
+            Some(frame) => bind_window_frame(
+                frame.units.clone(),
+                frame.start_bound.clone(),
+                frame
+                    .end_bound
+                    .clone()
+                    .unwrap_or(WindowFrameBound::CurrentRow),
+            )?,
+            None => BoundWindowFrame::default(),
+        };
+        for expr in args
+            .iter()
+            .chain(&partition_by)
+            .chain(order_by.iter().map(|o| &o.expr))
+        {
+            if expr.contains_window_call() {
+                return Err(BindError::InvalidExpression(
+                    "window function calls cannot be nested".into(),
+                ));
+            }
+        }
+
+        let arg_type = |args: &[BoundExpr]| {
+            args.first()
+                .and_then(|arg| arg.return_type())
+                .map(|ty| DataType::new(ty.kind(), true))
+                .ok_or_else(|| {
+                    BindError::InvalidExpression(format!("invalid arguments of {}", name))
+                })
+        };
+        if matches!(name, "row_number" | "rank" | "dense_rank") && !args.is_empty() {
+            return Err(BindError::InvalidExpression(format!(
+                "{} takes no arguments",
+                name
+            )));
+        }
+        let int = DataTypeKind::Int(None).not_null();
+        let (kind, return_type) = match name {
+            "row_number" => (WindowFuncKind::RowNumber, int),
+            "rank" => (WindowFuncKind::Rank, int),
+            "dense_rank" => (WindowFuncKind::DenseRank, int),
+            "lag" | "lead" => {
+                let offset = bind_window_offset(&mut args)?;
+                let return_type = arg_type(&args)?;
+                let kind = match name {
+                    "lag" => WindowFuncKind::Lag(offset),
+                    _ => WindowFuncKind::Lead(offset),
+                };
+                (kind, return_type)
+            }
+            "count" if args.is_empty() => (WindowFuncKind::Agg(AggKind::RowCount), int),
+            "count" => (WindowFuncKind::Agg(AggKind::Count), int),
+            "max" => (WindowFuncKind::Agg(AggKind::Max), arg_type(&args)?),
+            "min" => (WindowFuncKind::Agg(AggKind::Min), arg_type(&args)?),
+            "sum" => (WindowFuncKind::Agg(AggKind::Sum), arg_type(&args)?),
+            "avg" => {
+                // `avg(x) over w` is rewritten into `sum(x) over w / count(x) over w`
+                let return_type = arg_type(&args)?;
+                let window_call = |kind, return_type| {
+                    BoundExpr::WindowCall(BoundWindowCall {
+                        kind,
+                        args: args.clone(),
+                        partition_by: partition_by.clone(),
+                        order_by: order_by.clone(),
+                        frame: frame.clone(),
+                        return_type,
+                    })
+                };
+                return Ok(BoundExpr::BinaryOp(BoundBinaryOp {
+                    op: BinaryOperator::Divide,
+                    left_expr: Box::new(window_call(
+                        WindowFuncKind::Agg(AggKind::Sum),
+                        return_type.clone(),
+                    )),
+                    right_expr: Box::new(BoundExpr::TypeCast(BoundTypeCast {
+                        expr: Box::new(window_call(WindowFuncKind::Agg(AggKind::Count), int)),
+                        ty: return_type.kind(),
+                    })),
+                    return_type: Some(return_type),
+                }));
+            }
+            _ => return Err(BindError::InvalidFunction(format!("{} over", name))),
+        };
+        Ok(BoundExpr::WindowCall(BoundWindowCall {
+            kind,
+            args,
+            partition_by,
+            order_by,
+            frame,
+            return_type,
+        }))
+    }
+}
+
+/// Check the bounds of a window frame.
+fn bind_window_frame(
+    units: WindowFrameUnits,
+    start: WindowFrameBound,
+    end: WindowFrameBound,
+) -> Result<BoundWindowFrame, BindError> {
+    let invalid_frame = |reason: &str| Err(BindError::InvalidWindowFrame(reason.into()));
+    if units == WindowFrameUnits::Groups {
+        return invalid_frame("GROUPS is not supported");
+    }
+    if start == WindowFrameBound::Following(None) {
+        return invalid_frame("frame start cannot be UNBOUNDED FOLLOWING");
+    }
+    if end == WindowFrameBound::Preceding(None) {
+        return invalid_frame("frame end cannot be UNBOUNDED PRECEDING");
+    }
+    let has_offset = |bound: &WindowFrameBound| {
+        matches!(
+            bound,
+            WindowFrameBound::Preceding(Some(_)) | WindowFrameBound::Following(Some(_))
+        )
+    };
+    if units == WindowFrameUnits::Range && (has_offset(&start) || has_offset(&end)) {
+        return invalid_frame("RANGE with offset PRECEDING or FOLLOWING is not supported");
+    }
+    Ok(BoundWindowFrame { units, start, end })
+}
+
+/// Take the offset and the default value from the arguments of `lag` or `lead`, leaving the
+/// expression and the default value casted to its type.
+fn bind_window_offset(args: &mut Vec<BoundExpr>) -> Result<usize, BindError> {
+    if args.is_empty() || args.len() > 3 {
+        return Err(BindError::InvalidExpression(
+            "lag and lead require 1 to 3 arguments".into(),
+        ));
+    }
+    let offset = match args.get(1) {
+        None => 1,
+        Some(BoundExpr::Constant(value)) => match value.as_usize() {
+            Ok(Some(offset)) => offset,
+            _ => {
+                return Err(BindError::InvalidExpression(format!(
+                    "invalid offset of lag or lead: {}",
+                    value
+                )))
+            }
+        },
+        Some(expr) => {
+            return Err(BindError::InvalidExpression(format!(
+                "the offset of lag or lead must be a constant: {}",
+                expr
+            )))
+        }
+    };
+    if args.len() == 3 {
+        let default = args.pop().unwrap();
+        args.truncate(1);
+        let expr = args.pop().unwrap();
+        let (exprs, _) = unify_types(vec![expr, default])?;
+        *args = exprs;
+    } else {
+        args.truncate(1);
+    }
+    Ok(offset)
+}
@@ -5,10 +5,11 @@ use std::sync::Arc;
 use std::vec::Vec;
 
 use crate::catalog::{
-    ColumnDesc, RootCatalog, TableRefId, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME,
+    ColumnDesc, RootCatalog, TableCatalog, TableRefId, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME,
 };
+use crate::function::FunctionRegistry;
 use crate::parser::{Ident, ObjectName, Statement};
-use crate::types::{ColumnId, DataTypeKind, DataValue};
+use crate::types::{ColumnId, DataTypeKind, DataValue, DatabaseId, TableId};
 
 mod expr_visitor;
 mod expression;
@@ -30,6 +31,7 @@ pub enum BoundStatement {
     Select(Box<BoundSelect>),
     Explain(Box<BoundStatement>),
     Delete(Box<BoundDelete>),
+    Update(Box<BoundUpdate>),
 }
 
 /// The error type of bind operations.
@@ -43,12 +45,16 @@ pub enum BindError {
     InvalidTable(String),
     #[error("invalid column {0}")]
     InvalidColumn(String),
+    #[error("invalid function {0}")]
+    InvalidFunction(String),
     #[error("duplicated table {0}")]
     DuplicatedTable(String),
     #[error("duplicated column {0}")]
     DuplicatedColumn(String),
     #[error("invalid expression: {0}")]
     InvalidExpression(String),
+    #[error("invalid window frame: {0}")]
+    InvalidWindowFrame(String),
     #[error("not nullable column: {0}")]
     NotNullableColumn(String),
     #[error("binary operator types mismatch: {0} != {1}")]
@@ -63,12 +69,28 @@ pub enum BindError {
     InvalidSQL,
     #[error("cannot cast {0:?} to {1:?}")]
     CastError(DataValue, DataTypeKind),
+    #[error("subquery must return only one column, but returns {0}")]
+    SubqueryColumnCount(usize),
+    #[error("referring to columns of a query more than one level up is not supported: {0}")]
+    CorrelatedSubquery(String),
+    #[error("table {0} has {1} columns available but {2} columns specified")]
+    ColumnCountMismatch(String, usize, usize),
+    #[error("invalid recursive query {0}: {1}")]
+    InvalidRecursiveQuery(String, String),
+    #[error("each {0} query must have the same number of columns: {1} != {2}")]
+    SetOperationColumnCount(String, usize, usize),
+    #[error("{0} types {1} and {2} cannot be matched")]
+    SetOperationTypeMismatch(String, String, String),
+    #[error("{0} is not supported as an operand of set operations")]
+    UnsupportedSetOperand(String),
 }
 
 /// The context of binder execution.
 #[derive(Debug, Default)]
 struct BinderContext {
     regular_tables: HashMap<String, TableRefId>,
+    // The names of tables in the order they are added
+    table_names: Vec<String>,
     // Mapping the table name to column names
     column_names: HashMap<String, HashSet<String>>,
     // Mapping table name to its column ids
@@ -79,8 +101,18 @@ struct BinderContext {
     aliases: Vec<String>,
 
     aliases_expressions: Vec<BoundExpr>,
+    // Mapping CTE name to its definition
+    ctes: HashMap<String, CteBinding>,
+    // Columns merged by `USING` or `NATURAL` joins, with the expressions they refer to
+    using_columns: Vec<(String, BoundExpr)>,
+    // Mapping table name to its columns which are merged into `using_columns`
+    merged_columns: HashMap<String, HashSet<String>>,
 }
 
+/// The database id of virtual tables, which are the references to CTEs and the results of set
+/// operations.
+const VIRTUAL_DATABASE_ID: DatabaseId = DatabaseId::MAX;
+
 /// The binder resolves all expressions referring to schema objects such as
 /// tables or views with their column names and types.
 pub struct Binder {
@@ -88,6 +120,12 @@ pub struct Binder {
     context: BinderContext,
     upper_contexts: Vec<BinderContext>,
     base_table_refs: Vec<String>,
+    // Mapping the table id of virtual tables to their catalogs
+    virtual_tables: HashMap<TableRefId, Arc<TableCatalog>>,
+    next_virtual_table_id: TableId,
+    next_cte_id: usize,
+    // Scalar functions which can be called in expressions
+    functions: FunctionRegistry,
 }
 
 impl Binder {
@@ -98,6 +136,10 @@ impl Binder {
             upper_contexts: Vec::new(),
             context: BinderContext::default(),
             base_table_refs: Vec::new(),
+            virtual_tables: HashMap::new(),
+            next_virtual_table_id: 0,
+            next_cte_id: 0,
+            functions: FunctionRegistry::default(),
         }
     }
 
@@ -111,6 +153,14 @@ impl Binder {
         self.context = old_context.unwrap();
     }
 
+    /// Get the catalog of a base table or a virtual table.
+    fn get_table(&self, ref_id: &TableRefId) -> Arc<TableCatalog> {
+        match self.virtual_tables.get(ref_id) {
+            Some(table) => table.clone(),
+            None => self.catalog.get_table(ref_id).unwrap(),
+        }
+    }
+
     /// Bind a statement.
     pub fn bind(&mut self, stmt: &Statement) -> Result<BoundStatement, BindError> {
         match stmt {
@@ -120,6 +170,7 @@ impl Binder {
             Statement::Drop { .. } => Ok(BoundStatement::Drop(self.bind_drop(stmt)?)),
             Statement::Insert { .. } => Ok(BoundStatement::Insert(self.bind_insert(stmt)?)),
             Statement::Delete { .. } => Ok(BoundStatement::Delete(self.bind_delete(stmt)?)),
+            Statement::Update { .. } => Ok(BoundStatement::Update(self.bind_update(stmt)?)),
             Statement::Copy { .. } => Ok(BoundStatement::Copy(self.bind_copy(stmt)?)),
             Statement::Query(query) => Ok(BoundStatement::Select(self.bind_select(&*query)?)),
             Statement::Explain { statement, .. } => {
@@ -8,6 +8,8 @@ mod delete;
 pub(crate) mod drop;
 mod insert;
 mod select;
+mod set_operation;
+mod update;
 
 pub use copy::*;
 pub use create_table::*;
@@ -15,3 +17,5 @@ pub use delete::*;
 pub use drop::*;
 pub use insert::*;
 pub use select::*;
+pub use set_operation::*;
+pub use update::*;
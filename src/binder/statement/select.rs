@@ -3,7 +3,9 @@ use serde::Serialize;
 
 use super::BoundExpr::*;
 use super::{BoundExpr, BoundTableRef, *};
-use crate::parser::{Query, SelectItem, SetExpr};
+use crate::parser::{
+    distinct_on, Expr, Offset, OrderByExpr, Query, Select, SelectItem, SetExpr, Value,
+};
 use crate::types::DataValue::Bool;
 
 /// A bound `select` statement.
@@ -13,6 +15,8 @@ pub struct BoundSelect {
     pub from_table: Option<BoundTableRef>,
     pub where_clause: Option<BoundExpr>,
     pub select_distinct: bool,
+    /// The expressions of `DISTINCT ON`, which is empty if not specified.
+    pub distinct_on: Vec<BoundExpr>,
     pub group_by: Vec<BoundExpr>,
     pub orderby: Vec<BoundOrderBy>,
     pub limit: Option<BoundExpr>,
@@ -30,11 +34,35 @@ impl Binder {
     }
 
     fn bind_select_internal(&mut self, query: &Query) -> Result<Box<BoundSelect>, BindError> {
-        let select = match &query.body {
-            SetExpr::Select(select) => &**select,
+        if let Some(with) = &query.with {
+            self.bind_with(with)?;
+        }
+        let order_by = &query.order_by;
+        let limit = query.limit.as_ref();
+        let offset = query.offset.as_ref();
+        match &query.body {
+            SetExpr::Select(select) => self.bind_select_clause(select, order_by, limit, offset),
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => {
+                let set_operation = self.bind_set_operation(op, *all, left, right)?;
+                self.bind_set_operation_result(set_operation, order_by, limit, offset)
+            }
             _ => todo!("not select"),
-        };
+        }
+    }
 
+    /// Bind a `select` clause with its `order by`, `limit` and `offset` clauses.
+    pub(in crate::binder) fn bind_select_clause(
+        &mut self,
+        select: &Select,
+        order_by: &[OrderByExpr],
+        limit: Option<&Expr>,
+        offset: Option<&Offset>,
+    ) -> Result<Box<BoundSelect>, BindError> {
         // Bind table ref
         let mut from_table = if select.from.is_empty() {
             None
@@ -66,17 +94,17 @@ impl Binder {
             .as_ref()
             .map(|expr| self.bind_expr(expr))
             .transpose()?;
-        let limit = query
-            .limit
-            .as_ref()
-            .map(|expr| self.bind_expr(expr))
-            .transpose()?;
-        let offset = query
-            .offset
-            .as_ref()
+        let limit = limit.map(|expr| self.bind_expr(expr)).transpose()?;
+        let offset = offset
             .map(|offset| self.bind_expr(&offset.value))
             .transpose()?;
 
+        let distinct_on = distinct_on(select)
+            .unwrap_or_default()
+            .iter()
+            .map(|expr| self.bind_expr(expr))
+            .collect::<Result<Vec<_>, _>>()?;
+
         // Bind the select list.
         let mut select_list = vec![];
         // let mut return_names = vec![];
@@ -109,13 +137,7 @@ impl Binder {
             having = Some(self.bind_expr(expr)?);
         }
 
-        let mut orderby = vec![];
-        for e in &query.order_by {
-            orderby.push(BoundOrderBy {
-                expr: self.bind_expr(&e.expr)?,
-                descending: e.asc == Some(false),
-            });
-        }
+        let orderby = self.bind_output_order_by(order_by, &select_list)?;
 
         // Add referred columns for base table reference
         if let Some(table_ref) = &mut from_table {
@@ -126,7 +148,8 @@ impl Binder {
             select_list,
             from_table,
             where_clause,
-            select_distinct: select.distinct,
+            select_distinct: select.distinct && distinct_on.is_empty(),
+            distinct_on,
             group_by,
             orderby,
             limit,
@@ -135,6 +158,74 @@ impl Binder {
         }))
     }
 
+    pub(in crate::binder) fn bind_order_by(
+        &mut self,
+        order_by: &[OrderByExpr],
+    ) -> Result<Vec<BoundOrderBy>, BindError> {
+        let mut orderby = vec![];
+        for e in order_by {
+            let descending = e.asc == Some(false);
+            orderby.push(BoundOrderBy {
+                expr: self.bind_expr(&e.expr)?,
+                descending,
+                nulls_first: e.nulls_first.unwrap_or(descending),
+            });
+        }
+        Ok(orderby)
+    }
+
+    /// Bind the `order by` clause of a query, which can also refer to the output columns.
+    ///
+    /// An integer constant refers to the output column at the position, starting from 1. A single
+    /// name refers to the output column with the alias, even if an input column has the same name.
+    pub(in crate::binder) fn bind_output_order_by(
+        &mut self,
+        order_by: &[OrderByExpr],
+        select_list: &[BoundExpr],
+    ) -> Result<Vec<BoundOrderBy>, BindError> {
+        let mut orderby = vec![];
+        for e in order_by {
+            let expr = match &e.expr {
+                Expr::Value(Value::Number(n, _)) => {
+                    let item = n
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|position| select_list.get(position.wrapping_sub(1)))
+                        .ok_or_else(|| {
+                            BindError::InvalidExpression(format!(
+                                "ORDER BY position {} is not in select list",
+                                n
+                            ))
+                        })?;
+                    match item {
+                        ExprWithAlias(alias) => (*alias.expr).clone(),
+                        _ => item.clone(),
+                    }
+                }
+                Expr::Identifier(ident) => {
+                    let output = select_list.iter().find_map(|item| match item {
+                        ExprWithAlias(alias) if alias.alias.eq_ignore_ascii_case(&ident.value) => {
+                            Some((*alias.expr).clone())
+                        }
+                        _ => None,
+                    });
+                    match output {
+                        Some(expr) => expr,
+                        None => self.bind_expr(&e.expr)?,
+                    }
+                }
+                expr => self.bind_expr(expr)?,
+            };
+            let descending = e.asc == Some(false);
+            orderby.push(BoundOrderBy {
+                expr,
+                descending,
+                nulls_first: e.nulls_first.unwrap_or(descending),
+            });
+        }
+        Ok(orderby)
+    }
+
     pub fn bind_column_ids(&self, table_ref: &mut BoundTableRef) {
         match table_ref {
             BoundTableRef::BaseTableRef {
@@ -155,24 +246,46 @@ impl Binder {
                     self.bind_column_ids(&mut table.table_ref);
                 }
             }
+            // all columns of a virtual table are output
+            BoundTableRef::CteRef { .. }
+            | BoundTableRef::WorkTableRef { .. }
+            | BoundTableRef::SetOperationRef { .. } => {}
         }
     }
 }
 
+/// Get the name of an output column of a query.
+pub(in crate::binder) fn output_column_name(expr: &BoundExpr) -> String {
+    match expr {
+        ExprWithAlias(alias) => alias.alias.to_lowercase(),
+        ColumnRef(column_ref) => column_ref.desc.name().to_string(),
+        _ => "?column?".into(),
+    }
+}
+
 /// A bound `order by` statement.
 #[derive(PartialEq, Clone, Serialize)]
 pub struct BoundOrderBy {
     pub expr: BoundExpr,
     pub descending: bool,
+    /// Whether NULLs are ordered before non-NULL values. By default, NULLs are larger than any
+    /// non-NULL value, i.e. they are last in ascending order and first in descending order.
+    pub nulls_first: bool,
 }
 
 impl std::fmt::Debug for BoundOrderBy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{:?} ({})",
+            "{:?} ({}",
             self.expr,
             if self.descending { "desc" } else { "asc" }
-        )
+        )?;
+        // only print the nulls ordering if it is not the default
+        if self.nulls_first != self.descending {
+            let nulls = if self.nulls_first { "first" } else { "last" };
+            write!(f, " nulls {}", nulls)?;
+        }
+        write!(f, ")")
     }
 }
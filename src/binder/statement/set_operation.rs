@@ -0,0 +1,193 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use itertools::Itertools;
+
+use super::*;
+use crate::parser::{Expr, Offset, OrderByExpr, SetExpr, SetOperator};
+use crate::types::DataType;
+
+/// A bound set operation, which combines the results of two queries.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundSetOperation {
+    pub op: SetOperator,
+    /// Whether duplicate rows are kept.
+    pub all: bool,
+    pub left: Box<BoundSelect>,
+    pub right: Box<BoundSelect>,
+    /// The output columns, whose names come from the left query.
+    pub column_descs: Vec<ColumnDesc>,
+}
+
+impl Binder {
+    /// Select all columns from the result of a set operation, with the `order by`, `limit` and
+    /// `offset` clauses of the query.
+    pub(super) fn bind_set_operation_result(
+        &mut self,
+        set_operation: BoundSetOperation,
+        order_by: &[OrderByExpr],
+        limit: Option<&Expr>,
+        offset: Option<&Offset>,
+    ) -> Result<Box<BoundSelect>, BindError> {
+        // the result is registered as a virtual table, so that `order by` can refer to its columns
+        let table_name = set_operation.op.to_string().to_lowercase();
+        let ref_id = self.add_virtual_table(&table_name, &set_operation.column_descs)?;
+        let select_list = self.bind_all_column_refs()?;
+        let orderby = self.bind_output_order_by(order_by, &select_list)?;
+        let limit = limit.map(|expr| self.bind_expr(expr)).transpose()?;
+        let offset = offset
+            .map(|offset| self.bind_expr(&offset.value))
+            .transpose()?;
+
+        Ok(Box::new(BoundSelect {
+            select_list,
+            from_table: Some(BoundTableRef::SetOperationRef {
+                ref_id,
+                table_name,
+                set_operation: Box::new(set_operation),
+            }),
+            where_clause: None,
+            select_distinct: false,
+            distinct_on: vec![],
+            group_by: vec![],
+            orderby,
+            limit,
+            offset,
+            having: None,
+        }))
+    }
+
+    pub(super) fn bind_set_operation(
+        &mut self,
+        op: &SetOperator,
+        all: bool,
+        left: &SetExpr,
+        right: &SetExpr,
+    ) -> Result<BoundSetOperation, BindError> {
+        let mut left = self.bind_set_operand(left)?;
+        let mut right = self.bind_set_operand(right)?;
+        if left.select_list.len() != right.select_list.len() {
+            return Err(BindError::SetOperationColumnCount(
+                op.to_string(),
+                left.select_list.len(),
+                right.select_list.len(),
+            ));
+        }
+
+        let mut column_names = HashSet::new();
+        let mut column_descs = vec![];
+        let left_list = std::mem::take(&mut left.select_list);
+        let right_list = std::mem::take(&mut right.select_list);
+        for (left_expr, right_expr) in left_list.into_iter().zip(right_list) {
+            let mut column_name = output_column_name(&left_expr);
+            // columns of the virtual table must have distinct names
+            if column_names.contains(&column_name) {
+                column_name = (1..)
+                    .map(|i| format!("{}_{}", column_name, i))
+                    .find(|name| !column_names.contains(name))
+                    .unwrap();
+            }
+            column_names.insert(column_name.clone());
+
+            let (left_expr, right_expr, ty) = coerce_set_operands(op, left_expr, right_expr)?;
+            column_descs.push(ty.to_column(column_name));
+            left.select_list.push(left_expr);
+            right.select_list.push(right_expr);
+        }
+
+        Ok(BoundSetOperation {
+            op: op.clone(),
+            all,
+            left,
+            right,
+            column_descs,
+        })
+    }
+
+    /// Bind an operand of a set operation in a new context.
+    pub(in crate::binder) fn bind_set_operand(
+        &mut self,
+        operand: &SetExpr,
+    ) -> Result<Box<BoundSelect>, BindError> {
+        match operand {
+            SetExpr::Select(select) => {
+                self.push_context();
+                let ret = self.bind_select_clause(select, &[], None, None);
+                self.pop_context();
+                ret
+            }
+            SetExpr::Query(query) => self.bind_select(query),
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => {
+                self.push_context();
+                let ret =
+                    self.bind_set_operation(op, *all, left, right)
+                        .and_then(|set_operation| {
+                            self.bind_set_operation_result(set_operation, &[], None, None)
+                        });
+                self.pop_context();
+                ret
+            }
+            // TODO: support VALUES in set operations
+            SetExpr::Values(_) => Err(BindError::UnsupportedSetOperand("VALUES".into())),
+            _ => Err(BindError::NotSupportedTSQL),
+        }
+    }
+}
+
+/// Cast a pair of columns of the two sides of a set operation to their common type.
+fn coerce_set_operands(
+    op: &SetOperator,
+    left: BoundExpr,
+    right: BoundExpr,
+) -> Result<(BoundExpr, BoundExpr, DataType), BindError> {
+    let mismatch = BindError::SetOperationTypeMismatch(
+        op.to_string(),
+        format!("{:?}", left.return_type()),
+        format!("{:?}", right.return_type()),
+    );
+    match unify_types(vec![left, right]) {
+        Ok((exprs, Some(ty))) => {
+            let (left, right) = exprs.into_iter().collect_tuple().unwrap();
+            Ok((left, right, ty))
+        }
+        _ => Err(mismatch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::catalog::{ColumnCatalog, RootCatalog, TableRefId};
+    use crate::parser::parse;
+    use crate::types::{DataTypeExt, DataTypeKind};
+
+    #[test]
+    fn bind_values_operand() {
+        let catalog = Arc::new(RootCatalog::new());
+        let mut binder = Binder::new(catalog.clone());
+        catalog
+            .add_table(
+                TableRefId::new(0, 0, 0),
+                "t".into(),
+                vec![ColumnCatalog::new(
+                    0,
+                    DataTypeKind::Int(None).not_null().to_column("a".into()),
+                )],
+                false,
+                vec![],
+            )
+            .unwrap();
+
+        let stmts = parse("select a from t union values (1)").unwrap();
+        assert!(matches!(
+            binder.bind(&stmts[0]),
+            Err(BindError::UnsupportedSetOperand(operand)) if operand == "VALUES"
+        ));
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use itertools::Itertools;
+
+use super::*;
+use crate::parser::{Assignment, Statement};
+use crate::types::DataType;
+
+/// A bound `update` statement.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundUpdate {
+    pub table_ref: BoundTableRef,
+    /// The new value of every column of the table, in the order of its column ids.
+    ///
+    /// Columns which are not assigned keep their original value.
+    pub values: Vec<BoundExpr>,
+    pub where_clause: Option<BoundExpr>,
+}
+
+impl Binder {
+    pub fn bind_update(&mut self, stmt: &Statement) -> Result<Box<BoundUpdate>, BindError> {
+        self.push_context();
+        let ret = self.bind_update_internal(stmt);
+        self.pop_context();
+        ret
+    }
+
+    pub fn bind_update_internal(
+        &mut self,
+        stmt: &Statement,
+    ) -> Result<Box<BoundUpdate>, BindError> {
+        if let Statement::Update {
+            table,
+            assignments,
+            selection,
+            ..
+        } = stmt
+        {
+            if !table.joins.is_empty() {
+                return Err(BindError::InvalidSQL);
+            }
+            let mut table_ref = self.bind_table_ref(&table.relation)?;
+            // An update deletes the old rows and appends the new versions, so every column must
+            // be scanned. Bind all columns first, so that they are recorded in column id order.
+            let columns = self.bind_all_column_refs()?;
+            let mut values = columns.clone();
+            let mut assigned = vec![false; columns.len()];
+            for assignment in assignments {
+                let (index, data_type) = self.bind_assignment_target(assignment, &columns)?;
+                if std::mem::replace(&mut assigned[index], true) {
+                    return Err(BindError::DuplicatedColumn(
+                        assignment.id.iter().map(|ident| &ident.value).join("."),
+                    ));
+                }
+                let mut expr = self.bind_expr(&assignment.value)?;
+                match expr.return_type() {
+                    // If the data value is null, the column must be nullable.
+                    None if !data_type.is_nullable() => {
+                        return Err(BindError::NotNullableColumn(
+                            assignment.id.iter().map(|ident| &ident.value).join("."),
+                        ));
+                    }
+                    Some(ty) if ty.physical_kind() == data_type.physical_kind() => {}
+                    _ => {
+                        expr = BoundExpr::TypeCast(BoundTypeCast {
+                            expr: Box::new(expr),
+                            ty: data_type.kind(),
+                        });
+                    }
+                }
+                values[index] = expr;
+            }
+            let where_clause = selection
+                .as_ref()
+                .map(|expr| self.bind_expr(expr))
+                .transpose()?;
+            self.bind_column_ids(&mut table_ref);
+            Ok(Box::new(BoundUpdate {
+                table_ref,
+                values,
+                where_clause,
+            }))
+        } else {
+            panic!("unmatched statement type")
+        }
+    }
+
+    /// Resolve the column assigned by `assignment`, returning its position in `columns` and its
+    /// data type.
+    fn bind_assignment_target(
+        &mut self,
+        assignment: &Assignment,
+        columns: &[BoundExpr],
+    ) -> Result<(usize, DataType), BindError> {
+        let name = assignment.id.iter().map(|ident| &ident.value).join(".");
+        let column_ref_id = match self.bind_column_ref(&assignment.id)? {
+            BoundExpr::ColumnRef(column) => column.column_ref_id,
+            _ => return Err(BindError::InvalidColumn(name)),
+        };
+        columns
+            .iter()
+            .find_position(|expr| {
+                matches!(expr, BoundExpr::ColumnRef(column) if column.column_ref_id == column_ref_id)
+            })
+            .map(|(index, expr)| (index, expr.return_type().unwrap()))
+            .ok_or(BindError::InvalidColumn(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::catalog::{ColumnCatalog, RootCatalog, TableRefId};
+    use crate::parser::parse;
+    use crate::types::{DataTypeExt, DataTypeKind};
+
+    #[test]
+    fn bind_update() {
+        let catalog = Arc::new(RootCatalog::new());
+        let mut binder = Binder::new(catalog.clone());
+
+        let ref_id = TableRefId::new(0, 0, 0);
+        catalog
+            .add_table(
+                ref_id,
+                "t".into(),
+                vec![
+                    ColumnCatalog::new(0, DataTypeKind::Int(None).not_null().to_column("a".into())),
+                    ColumnCatalog::new(1, DataTypeKind::Int(None).nullable().to_column("b".into())),
+                ],
+                false,
+                vec![],
+            )
+            .unwrap();
+
+        let sql = "
+            update t set b = a + 1 where a > 1;
+            update t set b = null;
+            update t set a = null;
+            update t set c = 1;
+            update t set b = 1, b = 2;";
+        let stmts = parse(sql).unwrap();
+
+        let stmt = binder.bind_update(&stmts[0]).unwrap();
+        assert_eq!(stmt.values.len(), 2);
+        assert!(matches!(stmt.values[0], BoundExpr::ColumnRef(_)));
+        assert!(matches!(stmt.values[1], BoundExpr::BinaryOp(_)));
+        assert!(stmt.where_clause.is_some());
+
+        binder.bind_update(&stmts[1]).unwrap();
+        assert!(matches!(
+            binder.bind_update(&stmts[2]),
+            Err(BindError::NotNullableColumn(_))
+        ));
+        assert!(matches!(
+            binder.bind_update(&stmts[3]),
+            Err(BindError::InvalidColumn(_))
+        ));
+        assert!(matches!(
+            binder.bind_update(&stmts[4]),
+            Err(BindError::DuplicatedColumn(_))
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use super::*;
+use crate::parser::{Cte, SetExpr, SetOperator, With};
+use crate::types::DataType;
+
+/// A bound common table expression (CTE).
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundCte {
+    pub id: usize,
+    pub name: String,
+    pub column_descs: Vec<ColumnDesc>,
+    pub query: Box<BoundSelect>,
+    /// The recursive term of a recursive CTE.
+    ///
+    /// It is evaluated repeatedly on the rows produced by the last iteration, starting from the
+    /// result of `query`, until no more rows are produced.
+    pub recursive_query: Option<Box<BoundSelect>>,
+    /// Whether the terms of a recursive CTE are combined by `UNION ALL`.
+    pub union_all: bool,
+}
+
+/// A CTE visible to the query being bound.
+#[derive(Debug, Clone)]
+pub(in crate::binder) enum CteBinding {
+    Bound(Arc<BoundCte>),
+    /// A recursive CTE whose recursive term is being bound. References to it in the recursive
+    /// term are bound to the working table.
+    Working {
+        id: usize,
+        column_descs: Vec<ColumnDesc>,
+        referenced: bool,
+    },
+}
+
+impl Binder {
+    /// Bind the CTEs in a `WITH` clause into the current context.
+    pub(in crate::binder) fn bind_with(&mut self, with: &With) -> Result<(), BindError> {
+        for cte in &with.cte_tables {
+            let name = cte.alias.name.value.to_lowercase();
+            if self.context.ctes.contains_key(&name) {
+                return Err(BindError::DuplicatedTable(name));
+            }
+            let bound_cte = match &cte.query.body {
+                SetExpr::SetOperation {
+                    op: SetOperator::Union,
+                    all,
+                    left,
+                    right,
+                } if with.recursive => self.bind_recursive_cte(cte, &name, left, right, *all)?,
+                _ => self.bind_non_recursive_cte(cte, &name)?,
+            };
+            self.context
+                .ctes
+                .insert(name, CteBinding::Bound(Arc::new(bound_cte)));
+        }
+        Ok(())
+    }
+
+    fn bind_non_recursive_cte(&mut self, cte: &Cte, name: &str) -> Result<BoundCte, BindError> {
+        let query = self.bind_select(&cte.query)?;
+        let column_descs = self.bind_cte_columns(cte, name, &query, false)?;
+        Ok(BoundCte {
+            id: self.next_cte_id(),
+            name: name.into(),
+            column_descs,
+            query,
+            recursive_query: None,
+            union_all: false,
+        })
+    }
+
+    /// Bind a recursive CTE in the form of `anchor UNION [ALL] recursive`.
+    fn bind_recursive_cte(
+        &mut self,
+        cte: &Cte,
+        name: &str,
+        anchor: &SetExpr,
+        recursive: &SetExpr,
+        union_all: bool,
+    ) -> Result<BoundCte, BindError> {
+        let query = self.bind_set_operand(anchor)?;
+        // the recursive term may produce nulls even if the non-recursive term does not
+        let column_descs = self.bind_cte_columns(cte, name, &query, true)?;
+        let id = self.next_cte_id();
+
+        self.context.ctes.insert(
+            name.into(),
+            CteBinding::Working {
+                id,
+                column_descs: column_descs.clone(),
+                referenced: false,
+            },
+        );
+        let recursive_query = self.bind_set_operand(recursive);
+        let binding = self.context.ctes.remove(name).unwrap();
+        let recursive_query = recursive_query?;
+
+        if !matches!(
+            binding,
+            CteBinding::Working {
+                referenced: true,
+                ..
+            }
+        ) {
+            // a union whose second term does not refer to the query itself is not recursive
+            return self.bind_non_recursive_cte(cte, name);
+        }
+        let physical_kind = |expr: &BoundExpr| expr.return_type().map(|ty| ty.physical_kind());
+        if recursive_query.select_list.len() != column_descs.len()
+            || !recursive_query
+                .select_list
+                .iter()
+                .zip(&query.select_list)
+                .all(|(a, b)| physical_kind(a) == physical_kind(b))
+        {
+            return Err(BindError::InvalidRecursiveQuery(
+                name.into(),
+                "the recursive term must return the same column types as the non-recursive term"
+                    .into(),
+            ));
+        }
+
+        Ok(BoundCte {
+            id,
+            name: name.into(),
+            column_descs,
+            query,
+            recursive_query: Some(recursive_query),
+            union_all,
+        })
+    }
+
+    /// Get the output columns of a CTE from its column aliases and its select list.
+    fn bind_cte_columns(
+        &self,
+        cte: &Cte,
+        name: &str,
+        query: &BoundSelect,
+        nullable: bool,
+    ) -> Result<Vec<ColumnDesc>, BindError> {
+        let aliases = &cte.alias.columns;
+        if aliases.len() > query.select_list.len() {
+            return Err(BindError::ColumnCountMismatch(
+                name.into(),
+                query.select_list.len(),
+                aliases.len(),
+            ));
+        }
+        let mut column_names = HashSet::new();
+        let mut column_descs = vec![];
+        for (i, expr) in query.select_list.iter().enumerate() {
+            let column_name = match aliases.get(i) {
+                Some(alias) => alias.value.to_lowercase(),
+                None => output_column_name(expr),
+            };
+            if !column_names.insert(column_name.clone()) {
+                return Err(BindError::DuplicatedColumn(column_name));
+            }
+            let ty = expr.return_type().ok_or_else(|| {
+                BindError::InvalidExpression(format!("can not infer the type of {}", expr))
+            })?;
+            let ty = if nullable {
+                DataType::new(ty.kind(), true)
+            } else {
+                ty
+            };
+            column_descs.push(ty.to_column(column_name));
+        }
+        Ok(column_descs)
+    }
+
+    /// Find the CTE named `name` which is visible to the current query.
+    ///
+    /// References to the working table of a recursive CTE are recorded.
+    pub(super) fn find_cte(&mut self, name: &str) -> Option<CteBinding> {
+        let binding = std::iter::once(&mut self.context)
+            .chain(self.upper_contexts.iter_mut().rev())
+            .find_map(|context| context.ctes.get_mut(name))?;
+        if let CteBinding::Working { referenced, .. } = binding {
+            *referenced = true;
+        }
+        Some(binding.clone())
+    }
+
+    /// Bind a reference to a CTE, which is referred to as `table_name` in the query.
+    ///
+    /// Each reference is registered as a new virtual table, so that columns of different
+    /// references to the same CTE can be distinguished.
+    pub(super) fn bind_cte_ref(
+        &mut self,
+        cte: CteBinding,
+        table_name: &str,
+    ) -> Result<BoundTableRef, BindError> {
+        let column_descs = match &cte {
+            CteBinding::Bound(cte) => cte.column_descs.clone(),
+            CteBinding::Working { column_descs, .. } => column_descs.clone(),
+        };
+        let ref_id = self.add_virtual_table(table_name, &column_descs)?;
+
+        Ok(match cte {
+            CteBinding::Bound(cte) => BoundTableRef::CteRef {
+                ref_id,
+                table_name: table_name.into(),
+                cte,
+            },
+            CteBinding::Working { id, .. } => BoundTableRef::WorkTableRef {
+                ref_id,
+                table_name: table_name.into(),
+                cte_id: id,
+                column_descs,
+            },
+        })
+    }
+
+    /// Bind a table which has been referred to in the current query, which is referred to as
+    /// `alias` again.
+    ///
+    /// The reference is bound to a CTE selecting all columns of the table, so that its columns
+    /// are distinguished from those of the other references.
+    pub(super) fn bind_repeated_table_ref(
+        &mut self,
+        database_name: &str,
+        schema_name: &str,
+        table_name: &str,
+        alias: &str,
+    ) -> Result<BoundTableRef, BindError> {
+        self.push_context();
+        let query = self.bind_all_columns_of_table(database_name, schema_name, table_name);
+        self.pop_context();
+        let query = query?;
+        let column_descs = query
+            .select_list
+            .iter()
+            .map(|expr| match expr {
+                BoundExpr::ColumnRef(column_ref) => column_ref.desc.clone(),
+                _ => unreachable!("only columns are selected"),
+            })
+            .collect();
+        let cte = BoundCte {
+            id: self.next_cte_id(),
+            name: alias.into(),
+            column_descs,
+            query,
+            recursive_query: None,
+            union_all: false,
+        };
+        self.bind_cte_ref(CteBinding::Bound(Arc::new(cte)), alias)
+    }
+
+    /// Bind `SELECT * FROM table_name` in the current context.
+    fn bind_all_columns_of_table(
+        &mut self,
+        database_name: &str,
+        schema_name: &str,
+        table_name: &str,
+    ) -> Result<Box<BoundSelect>, BindError> {
+        let mut from_table =
+            self.bind_table_ref_with_name(database_name, schema_name, table_name)?;
+        let select_list = self.bind_all_column_refs()?;
+        self.bind_column_ids(&mut from_table);
+        Ok(Box::new(BoundSelect {
+            select_list,
+            from_table: Some(from_table),
+            where_clause: None,
+            select_distinct: false,
+            distinct_on: vec![],
+            group_by: vec![],
+            orderby: vec![],
+            limit: None,
+            offset: None,
+            having: None,
+        }))
+    }
+
+    fn next_cte_id(&mut self) -> usize {
+        let id = self.next_cte_id;
+        self.next_cte_id += 1;
+        id
+    }
+}
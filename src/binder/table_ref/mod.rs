@@ -2,13 +2,21 @@
 
 use std::vec::Vec;
 
+use itertools::Itertools;
 use serde::Serialize;
 
 use super::BoundExpr::*;
 use super::*;
-use crate::catalog::INTERNAL_SCHEMA_NAME;
-use crate::parser::{JoinConstraint, JoinOperator, TableFactor, TableWithJoins};
+use crate::catalog::{ColumnCatalog, INTERNAL_SCHEMA_NAME};
+use crate::parser::{
+    BinaryOperator, Ident, JoinConstraint, JoinOperator, TableFactor, TableWithJoins,
+};
 use crate::types::DataValue::Bool;
+use crate::types::{DataTypeExt, DataTypeKind};
+
+mod cte;
+
+pub use self::cte::*;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct BoundedSingleJoinTableRef {
@@ -31,6 +39,26 @@ pub enum BoundTableRef {
         relation: Box<BoundTableRef>,
         join_tables: Vec<BoundedSingleJoinTableRef>,
     },
+    /// A reference to a CTE, whose columns are referred to through the virtual table `ref_id`.
+    CteRef {
+        ref_id: TableRefId,
+        table_name: String,
+        cte: Arc<BoundCte>,
+    },
+    /// A reference to the working table in the recursive term of a recursive CTE.
+    WorkTableRef {
+        ref_id: TableRefId,
+        table_name: String,
+        cte_id: usize,
+        column_descs: Vec<ColumnDesc>,
+    },
+    /// The result of a set operation, whose columns are referred to through the virtual table
+    /// `ref_id`.
+    SetOperationRef {
+        ref_id: TableRefId,
+        table_name: String,
+        set_operation: Box<BoundSetOperation>,
+    },
 }
 
 #[derive(PartialEq, Clone, Copy, Serialize)]
@@ -39,6 +67,13 @@ pub enum BoundJoinOperator {
     LeftOuter,
     RightOuter,
     FullOuter,
+    /// Outputs left rows which have at least one match. Used by `EXISTS` and `IN` subqueries.
+    LeftSemi,
+    /// Outputs left rows which have no match. Used by `NOT EXISTS` and `NOT IN` subqueries.
+    LeftAnti,
+    /// Like left outer join, but each left row must match at most one right row.
+    /// Used by scalar subqueries.
+    LeftSingle,
 }
 
 impl std::fmt::Debug for BoundJoinOperator {
@@ -48,6 +83,9 @@ impl std::fmt::Debug for BoundJoinOperator {
             Self::LeftOuter => write!(f, "Left Outer"),
             Self::RightOuter => write!(f, "Right Outer"),
             Self::FullOuter => write!(f, "Full Outer"),
+            Self::LeftSemi => write!(f, "Left Semi"),
+            Self::LeftAnti => write!(f, "Left Anti"),
+            Self::LeftSingle => write!(f, "Left Single"),
         }
     }
 }
@@ -57,11 +95,15 @@ impl Binder {
         &mut self,
         table_with_joins: &TableWithJoins,
     ) -> Result<BoundTableRef, BindError> {
+        let tables_start = self.context.table_names.len();
         let relation = self.bind_table_ref(&table_with_joins.relation)?;
         let mut join_tables = vec![];
         for join in &table_with_joins.joins {
+            let left_tables = self.context.table_names[tables_start..].to_vec();
             let join_table = self.bind_table_ref(&join.relation)?;
-            let (join_op, join_cond) = self.bind_join_op(&join.join_operator)?;
+            let right_table = self.context.table_names.last().unwrap().clone();
+            let (join_op, join_cond) =
+                self.bind_join_op(&join.join_operator, &left_tables, &right_table)?;
             let join_ref = BoundedSingleJoinTableRef {
                 table_ref: (join_table.into()),
                 join_op,
@@ -74,62 +116,217 @@ impl Binder {
             join_tables,
         })
     }
+
+    /// Bind the operator of a join between the tables `left_tables` and the table `right_table`.
     pub fn bind_join_op(
         &mut self,
         join_op: &JoinOperator,
+        left_tables: &[String],
+        right_table: &str,
     ) -> Result<(BoundJoinOperator, BoundExpr), BindError> {
-        match join_op {
-            JoinOperator::Inner(constraint) => {
-                let condition = self.bind_join_constraint(constraint)?;
-                Ok((BoundJoinOperator::Inner, condition))
-            }
-            JoinOperator::LeftOuter(constraint) => {
-                let condition = self.bind_join_constraint(constraint)?;
-                Ok((BoundJoinOperator::LeftOuter, condition))
-            }
-            JoinOperator::RightOuter(constraint) => {
-                let condition = self.bind_join_constraint(constraint)?;
-                Ok((BoundJoinOperator::RightOuter, condition))
-            }
-            JoinOperator::FullOuter(constraint) => {
-                let condition = self.bind_join_constraint(constraint)?;
-                Ok((BoundJoinOperator::FullOuter, condition))
-            }
-            JoinOperator::CrossJoin => Ok((BoundJoinOperator::Inner, Constant(Bool(true)))),
-            _ => todo!("Support more join types"),
-        }
+        let (join_op, constraint) = match join_op {
+            JoinOperator::Inner(constraint) => (BoundJoinOperator::Inner, constraint),
+            JoinOperator::LeftOuter(constraint) => (BoundJoinOperator::LeftOuter, constraint),
+            JoinOperator::RightOuter(constraint) => (BoundJoinOperator::RightOuter, constraint),
+            JoinOperator::FullOuter(constraint) => (BoundJoinOperator::FullOuter, constraint),
+            JoinOperator::CrossJoin => return Ok((BoundJoinOperator::Inner, Constant(Bool(true)))),
+            // `CROSS APPLY` and `OUTER APPLY`
+            _ => return Err(BindError::NotSupportedTSQL),
+        };
+        let condition = self.bind_join_constraint(constraint, join_op, left_tables, right_table)?;
+        Ok((join_op, condition))
     }
 
     pub fn bind_join_constraint(
         &mut self,
         join_constraint: &JoinConstraint,
+        join_op: BoundJoinOperator,
+        left_tables: &[String],
+        right_table: &str,
     ) -> Result<BoundExpr, BindError> {
         match join_constraint {
             JoinConstraint::On(expr) => {
                 let expr = self.bind_expr(expr)?;
                 Ok(expr)
             }
-            _ => todo!("Support more join constraints"),
+            JoinConstraint::Using(columns) => {
+                let names = columns
+                    .iter()
+                    .map(|column| column.value.to_lowercase())
+                    .collect_vec();
+                self.bind_using_columns(&names, join_op, left_tables, right_table)
+            }
+            JoinConstraint::Natural => {
+                // join on all columns with the same names on both sides
+                let right_names = self.column_names_of(right_table);
+                let names = (self.context.using_columns.iter())
+                    .map(|(name, _)| name.clone())
+                    .chain(
+                        left_tables
+                            .iter()
+                            .flat_map(|table| self.column_names_of(table)),
+                    )
+                    .unique()
+                    .filter(|name| right_names.contains(name))
+                    .collect_vec();
+                self.bind_using_columns(&names, join_op, left_tables, right_table)
+            }
+            JoinConstraint::None => Err(BindError::InvalidExpression(
+                "JOIN requires a condition".into(),
+            )),
         }
     }
 
+    /// Bind the condition of a join on the columns `names` of both sides, which are merged into
+    /// one column in the output.
+    ///
+    /// The merged column refers to the left column, or the right column for right outer joins,
+    /// or the first non-NULL one of them for full outer joins.
+    fn bind_using_columns(
+        &mut self,
+        names: &[String],
+        join_op: BoundJoinOperator,
+        left_tables: &[String],
+        right_table: &str,
+    ) -> Result<BoundExpr, BindError> {
+        let mut condition = None;
+        let mut using_columns = vec![];
+        for name in names {
+            if using_columns.iter().any(|(merged, _)| merged == name) {
+                return Err(BindError::DuplicatedColumn(name.clone()));
+            }
+            let left = self.bind_left_using_column(left_tables, name)?;
+            let right = self.bind_column_ref(&[Ident::new(right_table), Ident::new(name)])?;
+            let (left, right, _) = implicit_type_cast(left, right)?;
+            let eq = BinaryOp(BoundBinaryOp {
+                op: BinaryOperator::Eq,
+                left_expr: Box::new(left.clone()),
+                right_expr: Box::new(right.clone()),
+                return_type: Some(DataTypeKind::Boolean.nullable()),
+            });
+            condition = Some(match condition {
+                Some(condition) => BinaryOp(BoundBinaryOp {
+                    op: BinaryOperator::And,
+                    left_expr: Box::new(condition),
+                    right_expr: Box::new(eq),
+                    return_type: Some(DataTypeKind::Boolean.nullable()),
+                }),
+                None => eq,
+            });
+            let merged = match join_op {
+                BoundJoinOperator::RightOuter => right,
+                BoundJoinOperator::FullOuter => bind_coalesce(vec![left, right])?,
+                _ => left,
+            };
+            using_columns.push((name.clone(), merged));
+        }
+        self.context
+            .merged_columns
+            .entry(right_table.into())
+            .or_default()
+            .extend(names.iter().cloned());
+        // the latest merged columns are output first
+        self.context
+            .using_columns
+            .retain(|(merged, _)| !names.contains(merged));
+        self.context.using_columns.splice(0..0, using_columns);
+        Ok(condition.unwrap_or(Constant(Bool(true))))
+    }
+
+    /// Bind the column `name` on the left side of a join, which is either merged by a previous
+    /// join, or a column of exactly one of `left_tables`.
+    fn bind_left_using_column(
+        &mut self,
+        left_tables: &[String],
+        name: &str,
+    ) -> Result<BoundExpr, BindError> {
+        if let Some((_, expr)) =
+            (self.context.using_columns.iter()).find(|(merged, _)| merged == name)
+        {
+            return Ok(expr.clone());
+        }
+        let mut tables = left_tables.iter().filter(|table| {
+            let table_ref_id = self.context.regular_tables[*table];
+            self.get_table(&table_ref_id)
+                .get_column_by_name(name)
+                .is_some()
+        });
+        let table = tables
+            .next()
+            .ok_or_else(|| BindError::InvalidColumn(name.into()))?
+            .clone();
+        if tables.next().is_some() {
+            return Err(BindError::AmbiguousColumn);
+        }
+        self.context
+            .merged_columns
+            .entry(table.clone())
+            .or_default()
+            .insert(name.into());
+        self.bind_column_ref(&[Ident::new(table), Ident::new(name)])
+    }
+
+    /// Returns the names of all columns of the table `table_name`.
+    fn column_names_of(&self, table_name: &str) -> Vec<String> {
+        let table_ref_id = self.context.regular_tables[table_name];
+        (self.get_table(&table_ref_id).all_columns().values())
+            .map(|column| column.name().to_string())
+            .collect()
+    }
+
     pub fn bind_table_ref_with_name(
         &mut self,
         database_name: &str,
         schema_name: &str,
         table_name: &str,
     ) -> Result<BoundTableRef, BindError> {
-        if self.context.regular_tables.contains_key(table_name) {
-            return Err(BindError::DuplicatedTable(table_name.into()));
-        }
+        self.bind_table_ref_with_alias(database_name, schema_name, table_name, table_name)
+    }
 
+    /// Bind a table which is referred to as `alias` in the query.
+    fn bind_table_ref_with_alias(
+        &mut self,
+        database_name: &str,
+        schema_name: &str,
+        table_name: &str,
+        alias: &str,
+    ) -> Result<BoundTableRef, BindError> {
         let ref_id = self
             .catalog
             .get_table_id_by_name(database_name, schema_name, table_name)
             .ok_or_else(|| BindError::InvalidTable(table_name.into()))?;
+        // the columns are identified by the id of their table, so the table can only be scanned
+        // directly by its first reference in a query
+        if self.context.regular_tables.values().any(|id| *id == ref_id) {
+            return self.bind_repeated_table_ref(database_name, schema_name, table_name, alias);
+        }
+        let table_name = alias;
+        self.add_table_to_context(table_name, ref_id)?;
+        let base_table_ref = BoundTableRef::BaseTableRef {
+            ref_id,
+            table_name: table_name.into(),
+            column_ids: vec![],
+            column_descs: vec![],
+            is_internal: schema_name == INTERNAL_SCHEMA_NAME,
+        };
+        self.base_table_refs.push(table_name.into());
+        Ok(base_table_ref)
+    }
+
+    /// Add a table which is referred to as `table_name` to the current context.
+    fn add_table_to_context(
+        &mut self,
+        table_name: &str,
+        ref_id: TableRefId,
+    ) -> Result<(), BindError> {
+        if self.context.regular_tables.contains_key(table_name) {
+            return Err(BindError::DuplicatedTable(table_name.into()));
+        }
+
         self.context
             .regular_tables
             .insert(table_name.into(), ref_id);
+        self.context.table_names.push(table_name.into());
         self.context
             .column_names
             .insert(table_name.into(), HashSet::new());
@@ -139,26 +336,52 @@ impl Binder {
         self.context
             .column_descs
             .insert(table_name.into(), Vec::new());
-        let base_table_ref = BoundTableRef::BaseTableRef {
-            ref_id,
-            table_name: table_name.into(),
-            column_ids: vec![],
-            column_descs: vec![],
-            is_internal: schema_name == INTERNAL_SCHEMA_NAME,
-        };
-        self.base_table_refs.push(table_name.into());
-        Ok(base_table_ref)
+        Ok(())
+    }
+
+    /// Register a virtual table with columns `column_descs`, which is referred to as `table_name`
+    /// in the current context.
+    pub(in crate::binder) fn add_virtual_table(
+        &mut self,
+        table_name: &str,
+        column_descs: &[ColumnDesc],
+    ) -> Result<TableRefId, BindError> {
+        let ref_id = TableRefId::new(VIRTUAL_DATABASE_ID, 0, self.next_virtual_table_id);
+        self.next_virtual_table_id += 1;
+        let columns = column_descs
+            .iter()
+            .enumerate()
+            .map(|(id, desc)| ColumnCatalog::new(id as ColumnId, desc.clone()))
+            .collect();
+        let table = TableCatalog::new(ref_id.table_id, table_name.into(), columns, false, vec![]);
+        self.virtual_tables.insert(ref_id, Arc::new(table));
+        self.add_table_to_context(table_name, ref_id)?;
+        Ok(ref_id)
     }
 
     pub fn bind_table_ref(&mut self, table: &TableFactor) -> Result<BoundTableRef, BindError> {
         match table {
             TableFactor::Table { name, alias, .. } => {
                 let name = &lower_case_name(name);
-                let (database_name, schema_name, mut table_name) = split_name(name)?;
-                if let Some(alias) = alias {
-                    table_name = &alias.name.value;
+                if let [cte_name] = name.0.as_slice() {
+                    if let Some(cte) = self.find_cte(&cte_name.value) {
+                        let alias = match alias {
+                            Some(alias) => alias.name.value.to_lowercase(),
+                            None => cte_name.value.clone(),
+                        };
+                        return self.bind_cte_ref(cte, &alias);
+                    }
+                }
+                let (database_name, schema_name, table_name) = split_name(name)?;
+                match alias {
+                    Some(alias) => self.bind_table_ref_with_alias(
+                        database_name,
+                        schema_name,
+                        table_name,
+                        &alias.name.value.to_lowercase(),
+                    ),
+                    None => self.bind_table_ref_with_name(database_name, schema_name, table_name),
                 }
-                self.bind_table_ref_with_name(database_name, schema_name, table_name)
             }
             _ => panic!("bind table ref"),
         }
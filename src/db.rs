@@ -1,5 +1,6 @@
 // Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use futures::TryStreamExt;
@@ -10,23 +11,26 @@ use crate::array::{
     ArrayBuilder, ArrayBuilderImpl, Chunk, DataChunk, I32ArrayBuilder, Utf8ArrayBuilder,
 };
 use crate::binder::{BindError, Binder};
-use crate::catalog::RootCatalogRef;
+use crate::catalog::{RootCatalogRef, TableRefId};
 use crate::executor::context::Context;
 use crate::executor::{ExecutorBuilder, ExecutorError};
 use crate::logical_planner::{LogicalPlanError, LogicalPlaner};
 use crate::optimizer::logical_plan_rewriter::{InputRefResolver, PlanRewriter};
-use crate::optimizer::plan_nodes::PlanRef;
-use crate::optimizer::Optimizer;
-use crate::parser::{parse, ParserError};
+use crate::optimizer::plan_nodes::{LogicalTableScan, PlanRef};
+use crate::optimizer::{Optimizer, PlanVisitor, Statistics, TableStatistics};
+use crate::parser::{parse, ParserError, Statement};
 use crate::storage::{
     InMemoryStorage, SecondaryStorage, SecondaryStorageOptions, Storage, StorageColumnRef,
     StorageImpl, Table,
 };
+use crate::types::{ColumnId, DataValue};
 
 /// The database instance.
 pub struct Database {
     catalog: RootCatalogRef,
     storage: StorageImpl,
+    /// Optimize the plans with the memo-based optimizer instead of the heuristic one.
+    enable_cascades: bool,
 }
 
 impl Database {
@@ -35,7 +39,11 @@ impl Database {
         let storage = InMemoryStorage::new();
         let catalog = storage.catalog().clone();
         let storage = StorageImpl::InMemoryStorage(Arc::new(storage));
-        Database { catalog, storage }
+        Database {
+            catalog,
+            storage,
+            enable_cascades: false,
+        }
     }
 
     /// Create a new database instance with merge-tree engine.
@@ -44,7 +52,17 @@ impl Database {
         storage.spawn_compactor().await;
         let catalog = storage.catalog().clone();
         let storage = StorageImpl::SecondaryStorage(storage);
-        Database { catalog, storage }
+        Database {
+            catalog,
+            storage,
+            enable_cascades: false,
+        }
+    }
+
+    /// Set whether to optimize the plans with the memo-based optimizer, which is disabled by
+    /// default.
+    pub fn set_enable_cascades(&mut self, enable: bool) {
+        self.enable_cascades = enable;
     }
 
     pub async fn shutdown(&self) -> Result<(), Error> {
@@ -155,8 +173,89 @@ impl Database {
         }
     }
 
-    /// Run SQL queries and return the outputs.
+    /// Collect the statistics of the tables scanned by the plan from the block statistics.
+    ///
+    /// Only the merge-tree engine has statistics. The number of rows of a table is aggregated
+    /// from its first column.
+    async fn collect_statistics(&self, plan: &PlanRef) -> Result<Statistics, Error> {
+        #[derive(Default)]
+        struct TableScanCollector(HashMap<TableRefId, Vec<ColumnId>>);
+        impl PlanVisitor<()> for TableScanCollector {
+            fn visit_logical_table_scan(&mut self, plan: &LogicalTableScan) -> Option<()> {
+                let column_ids = self.0.entry(plan.table_ref_id()).or_default();
+                column_ids.extend_from_slice(plan.column_ids());
+                None
+            }
+        }
 
+        let mut statistics = Statistics::default();
+        let storage = match &self.storage {
+            StorageImpl::SecondaryStorage(storage) => storage,
+            StorageImpl::InMemoryStorage(_) => return Ok(statistics),
+        };
+        let mut collector = TableScanCollector::default();
+        collector.visit(plan.clone());
+        for (table_ref_id, mut column_ids) in collector.0 {
+            column_ids.sort_unstable();
+            column_ids.dedup();
+            let table = storage.get_table(table_ref_id)?;
+            // the rows of a table without columns can not be counted
+            if table.columns.is_empty() {
+                continue;
+            }
+            let txn = table.read().await?;
+            // Note that the column ids are the column catalog ids instead of storage column ids,
+            // as in the `stat` command.
+            let mut stat_types = vec![(BlockStatisticsType::RowCount, StorageColumnRef::Idx(0))];
+            stat_types.extend((column_ids.iter()).map(|id| {
+                (
+                    BlockStatisticsType::DistinctValue,
+                    StorageColumnRef::Idx(*id),
+                )
+            }));
+            let values = txn.aggreagate_block_stat(&stat_types);
+            let as_usize = |value: &DataValue| value.as_usize().ok().flatten().unwrap_or(0);
+            statistics.add_table(
+                table_ref_id,
+                TableStatistics {
+                    row_count: as_usize(&values[0]),
+                    distinct_values: column_ids
+                        .into_iter()
+                        .zip(values[1..].iter().map(as_usize))
+                        .collect(),
+                },
+            );
+        }
+        Ok(statistics)
+    }
+
+    /// Bind and plan the statement, and optimize the plan with the statistics of the tables it
+    /// scans. Returns the optimized plan and the names of its output columns.
+    async fn plan(
+        &self,
+        binder: &mut Binder,
+        stmt: &Statement,
+    ) -> Result<(PlanRef, Vec<String>), Error> {
+        let stmt = binder.bind(stmt)?;
+        debug!("{:#?}", stmt);
+        let logical_plan = LogicalPlaner::default().plan(stmt)?;
+        debug!("{:#?}", logical_plan);
+        // Resolve input reference
+        let logical_plan = InputRefResolver::default().rewrite(logical_plan);
+        let column_names = logical_plan.out_names();
+        debug!("{:#?}", logical_plan);
+        let mut optimizer = Optimizer {
+            enable_filter_scan: self.storage.enable_filter_scan(),
+            enable_metadata_scan: self.storage.enable_metadata_scan(),
+            enable_cascades: self.enable_cascades,
+            statistics: self.collect_statistics(&logical_plan).await?,
+        };
+        let optimized_plan = optimizer.optimize(logical_plan)?;
+        debug!("{:#?}", optimized_plan);
+        Ok((optimized_plan, column_names))
+    }
+
+    /// Run SQL queries and return the outputs.
     pub async fn run(&self, sql: &str) -> Result<Vec<Chunk>, Error> {
         self.run_with_context(Default::default(), sql).await
     }
@@ -174,25 +273,11 @@ impl Database {
         let stmts = parse(sql)?;
 
         let mut binder = Binder::new(self.catalog.clone());
-        let logical_planner = LogicalPlaner::default();
-        let mut optimizer = Optimizer {
-            enable_filter_scan: self.storage.enable_filter_scan(),
-        };
         // TODO: parallelize
         let mut outputs: Vec<Chunk> = vec![];
         for stmt in stmts {
             debug!("{:#?}", stmt);
-            let stmt = binder.bind(&stmt)?;
-            debug!("{:#?}", stmt);
-            let logical_plan = logical_planner.plan(stmt)?;
-            debug!("{:#?}", logical_plan);
-            // Resolve input reference
-            let mut input_ref_resolver = InputRefResolver::default();
-            let logical_plan = input_ref_resolver.rewrite(logical_plan);
-            let column_names = logical_plan.out_names();
-            debug!("{:#?}", logical_plan);
-            let optimized_plan = optimizer.optimize(logical_plan);
-            debug!("{:#?}", optimized_plan);
+            let (optimized_plan, column_names) = self.plan(&mut binder, &stmt).await?;
 
             let mut executor_builder = ExecutorBuilder::new(context.clone(), self.storage.clone());
             let executor = executor_builder.build(optimized_plan);
@@ -209,26 +294,13 @@ impl Database {
     }
 
     // Generate the execution plans for SQL queries.
-    pub fn generate_execution_plan(&self, sql: &str) -> Result<Vec<PlanRef>, Error> {
+    pub async fn generate_execution_plan(&self, sql: &str) -> Result<Vec<PlanRef>, Error> {
         let stmts = parse(sql)?;
 
         let mut binder = Binder::new(self.catalog.clone());
-        let logical_planner = LogicalPlaner::default();
-        let mut optimizer = Optimizer {
-            enable_filter_scan: self.storage.enable_filter_scan(),
-        };
         let mut plans = vec![];
         for stmt in stmts {
-            let stmt = binder.bind(&stmt)?;
-            debug!("{:#?}", stmt);
-            let logical_plan = logical_planner.plan(stmt)?;
-            debug!("{:#?}", logical_plan);
-            // Resolve input reference
-            let mut input_ref_resolver = InputRefResolver::default();
-            let logical_plan = input_ref_resolver.rewrite(logical_plan);
-            debug!("{:#?}", logical_plan);
-            let optimized_plan = optimizer.optimize(logical_plan);
-            debug!("{:#?}", optimized_plan);
+            let (optimized_plan, _) = self.plan(&mut binder, &stmt).await?;
             plans.push(optimized_plan);
         }
         Ok(plans)
@@ -27,7 +27,10 @@ impl AggregationState for CountAggregationState {
         Ok(())
     }
 
-    fn update_single(&mut self, _: &DataValue) -> Result<(), ExecutorError> {
+    fn update_single(&mut self, value: &DataValue) -> Result<(), ExecutorError> {
+        if *value == DataValue::Null {
+            return Ok(());
+        }
         self.result = match &self.result {
             DataValue::Null => DataValue::Int32(1),
             DataValue::Int32(res) => DataValue::Int32(res + 1),
@@ -0,0 +1,66 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::collections::HashSet;
+
+use super::*;
+
+/// State for aggregations with `DISTINCT`, which only passes each distinct non-NULL value to the
+/// inner state once.
+pub struct DistinctAggregationState {
+    inner: Box<dyn AggregationState>,
+    seen: HashSet<DataValue>,
+}
+
+impl DistinctAggregationState {
+    pub fn new(inner: Box<dyn AggregationState>) -> Self {
+        Self {
+            inner,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl AggregationState for DistinctAggregationState {
+    fn update(&mut self, array: &ArrayImpl) -> Result<(), ExecutorError> {
+        for i in 0..array.len() {
+            self.update_single(&array.get(i))?;
+        }
+        Ok(())
+    }
+
+    fn update_single(&mut self, value: &DataValue) -> Result<(), ExecutorError> {
+        if *value == DataValue::Null || self.seen.contains(value) {
+            return Ok(());
+        }
+        self.seen.insert(value.clone());
+        self.inner.update_single(value)
+    }
+
+    fn output(&self) -> DataValue {
+        self.inner.output()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DataTypeKind;
+
+    #[test]
+    fn test_distinct() {
+        let array = ArrayImpl::new_int32([Some(1), Some(2), None, Some(1)].into_iter().collect());
+
+        let state = SumAggregationState::new(DataTypeKind::Int(None));
+        let mut state = DistinctAggregationState::new(Box::new(state));
+        state.update(&array).unwrap();
+        state.update_single(&DataValue::Int32(2)).unwrap();
+        state.update_single(&DataValue::Int32(3)).unwrap();
+        assert_eq!(state.output(), DataValue::Int32(6));
+
+        let state = CountAggregationState::new(DataValue::Int32(0));
+        let mut state = DistinctAggregationState::new(Box::new(state));
+        state.update(&array).unwrap();
+        state.update_single(&DataValue::Null).unwrap();
+        assert_eq!(state.output(), DataValue::Int32(2));
+    }
+}
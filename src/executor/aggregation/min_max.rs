@@ -37,7 +37,6 @@ min_max_func_gen!(min_i32, i32, i32, min);
 min_max_func_gen!(max_i32, i32, i32, max);
 min_max_func_gen!(min_i64, i64, i64, min);
 min_max_func_gen!(max_i64, i64, i64, max);
-// TODO: To support min and max on `f64`, we should implement std::cmp::Ord for `f64`
 
 impl AggregationState for MinMaxAggregationState {
     fn update(&mut self, array: &ArrayImpl) -> Result<(), ExecutorError> {
@@ -68,13 +67,19 @@ impl AggregationState for MinMaxAggregationState {
                     };
                 }
             }
-            _ => panic!("Mismatched type"),
+            // the values of other types are compared one by one
+            _ => {
+                for i in 0..array.len() {
+                    self.update_single(&array.get(i))?;
+                }
+            }
         }
         Ok(())
     }
 
     fn update_single(&mut self, value: &DataValue) -> Result<(), ExecutorError> {
         match (value, &self.input_datatype) {
+            (DataValue::Null, _) => {}
             (DataValue::Int32(val), DataTypeKind::Int(_)) => {
                 self.result = match self.result {
                     DataValue::Null => DataValue::Int32(*val),
@@ -91,7 +96,17 @@ impl AggregationState for MinMaxAggregationState {
                     _ => panic!("Mismatched type"),
                 };
             }
-            _ => panic!("Mismatched type"),
+            (DataValue::Int32(_) | DataValue::Int64(_), _) => panic!("Mismatched type"),
+            (value, _) => {
+                let replace = match &self.result {
+                    DataValue::Null => true,
+                    result if self.is_min => value < result,
+                    result => value > result,
+                };
+                if replace {
+                    self.result = value.clone();
+                }
+            }
         }
         Ok(())
     }
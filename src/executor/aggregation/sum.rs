@@ -126,6 +126,7 @@ impl AggregationState for SumAggregationState {
 
     fn update_single(&mut self, value: &DataValue) -> Result<(), ExecutorError> {
         match (value, &self.input_datatype) {
+            (DataValue::Null, _) => {}
             (DataValue::Int32(val), DataTypeKind::Int(_)) => {
                 self.result = match self.result {
                     DataValue::Null => DataValue::Int32(*val),
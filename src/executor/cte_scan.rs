@@ -0,0 +1,30 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use futures::TryStreamExt;
+use tokio::sync::OnceCell;
+
+use super::*;
+use crate::array::DataChunk;
+
+/// The executor of a reference to a materialized CTE.
+///
+/// The CTE is evaluated by the first reference being executed, and the result is shared by all
+/// references to the CTE.
+pub struct CteScanExecutor {
+    pub result: Arc<OnceCell<Vec<DataChunk>>>,
+    pub child: BoxedExecutor,
+}
+
+impl CteScanExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecutorError)]
+    pub async fn execute(self) {
+        let child = self.child;
+        let chunks = self
+            .result
+            .get_or_try_init(|| child.try_collect::<Vec<DataChunk>>())
+            .await?;
+        for chunk in chunks.iter() {
+            yield chunk.clone();
+        }
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use super::*;
+use crate::array::{ArrayImpl, DataChunk};
+use crate::binder::BoundExpr;
+use crate::types::DataValue;
+
+/// The executor of `DISTINCT ON`.
+///
+/// The input is sorted by the `DISTINCT ON` expressions, so the rows with the same values of the
+/// expressions are adjacent. Only the first row of them is output.
+pub struct DistinctOnExecutor {
+    pub distinct_on: Vec<BoundExpr>,
+    pub child: BoxedExecutor,
+}
+
+impl DistinctOnExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecutorError)]
+    pub async fn execute(self) {
+        // the values of the expressions of the last row
+        let mut last_key: Option<Vec<DataValue>> = None;
+        #[for_await]
+        for batch in self.child {
+            let batch = batch?;
+            let arrays: Vec<ArrayImpl> = self
+                .distinct_on
+                .iter()
+                .map(|expr| expr.eval(&batch))
+                .try_collect()?;
+            let mut visibility = Vec::with_capacity(batch.cardinality());
+            for row in 0..batch.cardinality() {
+                let key: Vec<DataValue> = arrays.iter().map(|array| array.get(row)).collect();
+                visibility.push(last_key.as_ref() != Some(&key));
+                last_key = Some(key);
+            }
+            yield batch.filter(visibility.into_iter());
+        }
+    }
+}
@@ -3,9 +3,10 @@
 //! Apply expressions on data chunks.
 
 use std::borrow::Borrow;
+use std::collections::HashSet;
 
 use crate::array::*;
-use crate::binder::BoundExpr;
+use crate::binder::{BoundCase, BoundExpr, BoundFunctionCall, BoundInList};
 use crate::parser::{BinaryOperator, UnaryOperator};
 use crate::types::{Blob, ConvertError, DataTypeExt, DataTypeKind, DataValue, Date};
 
@@ -52,6 +53,11 @@ impl BoundExpr {
                 ))
             }
             BoundExpr::ExprWithAlias(expr_with_alias) => expr_with_alias.expr.eval(chunk),
+            BoundExpr::Case(case) => case.eval(chunk),
+            BoundExpr::InList(in_list) => in_list.eval_with(|expr| expr.eval(chunk)),
+            BoundExpr::FunctionCall(function_call) => {
+                function_call.eval_with(|expr| expr.eval(chunk))
+            }
             _ => panic!("{:?} should not be evaluated in `eval_array`", self),
         }
     }
@@ -78,8 +84,12 @@ impl BoundExpr {
                 Ok(array.unary_op(&op.op))
             }
             BoundExpr::Constant(v) => {
-                let mut builder =
-                    ArrayBuilderImpl::with_capacity(cardinality, &self.return_type().unwrap());
+                let mut builder = ArrayBuilderImpl::with_capacity(
+                    cardinality,
+                    &self
+                        .return_type()
+                        .unwrap_or_else(|| DataTypeKind::Int(None).nullable()),
+                );
                 // TODO: optimize this
                 for _ in 0..cardinality {
                     builder.push(v);
@@ -101,11 +111,170 @@ impl BoundExpr {
                         .collect(),
                 ))
             }
+            BoundExpr::Case(case) => case.eval_in_storage(chunk, cardinality),
+            BoundExpr::InList(in_list) => {
+                in_list.eval_with(|expr| expr.eval_array_in_storage(chunk, cardinality))
+            }
+            BoundExpr::FunctionCall(function_call) => {
+                function_call.eval_with(|expr| expr.eval_array_in_storage(chunk, cardinality))
+            }
             _ => panic!("{:?} should not be evaluated in `eval_array`", self),
         }
     }
 }
 
+impl BoundCase {
+    /// Evaluate the `CASE` expression as an array.
+    fn eval(&self, chunk: &DataChunk) -> Result<ArrayImpl, ConvertError> {
+        self.eval_with(
+            chunk.cardinality(),
+            |visibility| chunk.filter(visibility.iter().copied()),
+            |expr, chunk| expr.eval(chunk),
+        )
+    }
+
+    /// Evaluate the `CASE` expression as an array in storage.
+    fn eval_in_storage(
+        &self,
+        chunk: &PackedVec<Option<ArrayImpl>>,
+        cardinality: usize,
+    ) -> Result<ArrayImpl, ConvertError> {
+        self.eval_with(
+            cardinality,
+            |visibility| {
+                let arrays: PackedVec<_> = chunk
+                    .iter()
+                    .map(|array| array.as_ref().map(|a| a.filter(visibility.iter().copied())))
+                    .collect();
+                (arrays, visibility.iter().filter(|v| **v).count())
+            },
+            |expr, (chunk, cardinality)| expr.eval_array_in_storage(chunk, *cardinality),
+        )
+    }
+
+    /// Evaluate the `CASE` expression on a chunk of `cardinality` rows.
+    ///
+    /// The condition of each branch is evaluated on the rows which do not match any previous
+    /// branch, and the result of the branch is only evaluated on the rows matching the condition.
+    /// `filter` selects the visible rows of the chunk, and `eval` evaluates an expression on them.
+    fn eval_with<C>(
+        &self,
+        cardinality: usize,
+        filter: impl Fn(&[bool]) -> C,
+        eval: impl Fn(&BoundExpr, &C) -> Result<ArrayImpl, ConvertError>,
+    ) -> Result<ArrayImpl, ConvertError> {
+        // whether each row does not match any branch yet
+        let mut unmatched = vec![true; cardinality];
+        // the branch matched by each row, and the index of the row in the result of the branch
+        let mut selected = vec![None; cardinality];
+        let mut results = vec![];
+        for (condition, result) in &self.branches {
+            let rows: Vec<usize> = (0..cardinality).filter(|&i| unmatched[i]).collect();
+            if rows.is_empty() {
+                break;
+            }
+            let condition = eval(condition, &filter(&unmatched))?;
+            let mut matched = vec![false; cardinality];
+            let mut num_matched = 0;
+            for (i, row) in rows.into_iter().enumerate() {
+                if condition.get(i) == DataValue::Bool(true) {
+                    matched[row] = true;
+                    unmatched[row] = false;
+                    selected[row] = Some((results.len(), num_matched));
+                    num_matched += 1;
+                }
+            }
+            results.push(eval(result, &filter(&matched))?);
+        }
+        if let Some(else_result) = &self.else_result {
+            for (i, row) in (0..cardinality).filter(|&i| unmatched[i]).enumerate() {
+                selected[row] = Some((results.len(), i));
+            }
+            results.push(eval(else_result, &filter(&unmatched))?);
+        }
+
+        let return_type = self
+            .return_type
+            .clone()
+            .unwrap_or_else(|| DataTypeKind::Int(None).nullable());
+        let mut builder = ArrayBuilderImpl::with_capacity(cardinality, &return_type);
+        for selected in selected {
+            match selected {
+                Some((branch, i)) => builder.push(&results[branch].get(i)),
+                None => builder.push(&DataValue::Null),
+            }
+        }
+        Ok(builder.finish())
+    }
+}
+
+impl BoundInList {
+    /// Evaluate the `IN` list as an array, where `eval` evaluates the expression and the list.
+    ///
+    /// Constants in the list are collected into a hash set once per chunk. Other items are
+    /// evaluated as arrays and compared row by row.
+    fn eval_with(
+        &self,
+        eval: impl Fn(&BoundExpr) -> Result<ArrayImpl, ConvertError>,
+    ) -> Result<ArrayImpl, ConvertError> {
+        let array = eval(&self.expr)?;
+        let mut set = HashSet::new();
+        let mut has_null = false;
+        let mut others = vec![];
+        for item in &self.list {
+            match item {
+                BoundExpr::Constant(DataValue::Null) => has_null = true,
+                BoundExpr::Constant(value) => {
+                    set.insert(value.clone());
+                }
+                _ => others.push(eval(item)?),
+            }
+        }
+        let result = (0..array.len())
+            .map(|i| {
+                let value = array.get(i);
+                if value == DataValue::Null {
+                    return None;
+                }
+                if set.contains(&value) {
+                    return Some(!self.negated);
+                }
+                // the result is null if the value is not found and the list contains null
+                let mut has_null = has_null;
+                for other in &others {
+                    match other.get(i) {
+                        DataValue::Null => has_null = true,
+                        v if v == value => return Some(!self.negated),
+                        _ => {}
+                    }
+                }
+                if has_null {
+                    None
+                } else {
+                    Some(self.negated)
+                }
+            })
+            .collect();
+        Ok(ArrayImpl::new_bool(result))
+    }
+}
+
+impl BoundFunctionCall {
+    /// Evaluate the function on the chunk of its arguments, where `eval` evaluates an argument.
+    fn eval_with(
+        &self,
+        eval: impl Fn(&BoundExpr) -> Result<ArrayImpl, ConvertError>,
+    ) -> Result<ArrayImpl, ConvertError> {
+        let args = self
+            .args
+            .iter()
+            .map(eval)
+            .collect::<Result<DataChunk, _>>()?;
+        let output = self.function.execute(&args)?;
+        Ok(output.array_at(0).clone())
+    }
+}
+
 impl ArrayImpl {
     /// Perform unary operation.
     pub fn unary_op(&self, op: &UnaryOperator) -> ArrayImpl {
@@ -210,6 +379,23 @@ impl ArrayImpl {
                 }
                 _ => panic!("Or can only be applied to BOOL arrays"),
             },
+            BinaryOperator::Like
+            | BinaryOperator::NotLike
+            | BinaryOperator::ILike
+            | BinaryOperator::NotILike => match (self, right) {
+                (A::Utf8(a), A::Utf8(b)) => {
+                    let case_insensitive =
+                        matches!(op, BinaryOperator::ILike | BinaryOperator::NotILike);
+                    let result = a.like(b, case_insensitive);
+                    match op {
+                        BinaryOperator::NotLike | BinaryOperator::NotILike => {
+                            A::new_bool(unary_op(&result, |b| !b))
+                        }
+                        _ => A::new_bool(result),
+                    }
+                }
+                _ => panic!("{} can only be applied to STRING arrays", op),
+            },
             _ => todo!("evaluate operator: {:?}", op),
         }
     }
@@ -1,21 +1,28 @@
 // Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::vec::Vec;
 
 use futures::TryStreamExt;
+use itertools::Itertools;
 
 use super::*;
-use crate::array::{DataChunk, DataChunkBuilder, RowRef};
+use crate::array::{Array, ArrayImpl, DataChunk, DataChunkBuilder, RowRef};
 use crate::binder::{BoundExpr, BoundJoinOperator};
 use crate::types::{DataType, DataValue};
 
 /// The executor for hash join
+///
+/// The hash table is built on the left side, and the chunks of the right side are streamed
+/// through it. Rows with NULL keys match nothing. The matched rows of both sides are tracked, so
+/// that the unmatched rows can be padded with NULLs for outer joins, and the left rows can be
+/// selected for semi and anti joins. A single join is a left outer join where each left row
+/// matches at most one right row.
 pub struct HashJoinExecutor {
     pub left_child: BoxedExecutor,
     pub right_child: BoxedExecutor,
     pub join_op: BoundJoinOperator,
-    // TODO: filter by condition
+    /// The conditions other than the equal keys, which are evaluated on the joined rows.
     pub condition: BoundExpr,
     pub left_column_indexes: Vec<usize>,
     pub right_column_indexes: Vec<usize>,
@@ -26,86 +33,203 @@ pub struct HashJoinExecutor {
 impl HashJoinExecutor {
     #[try_stream(boxed, ok = DataChunk, error = ExecutorError)]
     pub async fn execute(self) {
-        // collect all chunks from children
-        let (left_chunks, right_chunks) = async {
-            tokio::try_join!(
-                self.left_child.try_collect::<Vec<DataChunk>>(),
-                self.right_child.try_collect::<Vec<DataChunk>>(),
-            )
-        }
-        .await?;
-
-        // helper functions
-        let left_rows = || left_chunks.iter().flat_map(|chunk| chunk.rows());
-        let right_rows = || right_chunks.iter().flat_map(|chunk| chunk.rows());
+        // collect all chunks from the build side
+        let left_chunks = self.left_child.try_collect::<Vec<DataChunk>>().await?;
+        let left_rows: Vec<RowRef<'_>> = left_chunks.iter().flat_map(|c| c.rows()).collect();
 
         // build
-        let mut hash_map: HashMap<Vec<DataValue>, Vec<RowRef<'_>>> = HashMap::new();
-        for left_row in left_rows() {
+        let mut hash_map: HashMap<Vec<DataValue>, Vec<usize>> = HashMap::new();
+        for (i, left_row) in left_rows.iter().enumerate() {
             let hash_value = left_row.get_by_indexes(&self.left_column_indexes);
-            hash_map
-                .entry(hash_value)
-                .or_insert_with(Vec::new)
-                .push(left_row);
+            if hash_value.contains(&DataValue::Null) {
+                continue;
+            }
+            hash_map.entry(hash_value).or_default().push(i);
         }
 
-        let data_types = self.left_types.iter().chain(self.right_types.iter());
-        let mut builder = DataChunkBuilder::new(data_types, PROCESSING_WINDOW_SIZE);
+        // semi and anti joins only output the left rows after probing
+        let outputs_joined_rows = !matches!(
+            self.join_op,
+            BoundJoinOperator::LeftSemi | BoundJoinOperator::LeftAnti
+        );
+        let single = self.join_op == BoundJoinOperator::LeftSingle;
+        // the matches of single joins are checked when the joined rows are filtered, where no row
+        // is left in the builders
+        let has_condition = self.condition != BoundExpr::Constant(DataValue::Bool(true)) || single;
+        let mut left_matched = vec![false; left_rows.len()];
+
+        let join_types = self.left_types.iter().chain(self.right_types.iter());
+        // the joined rows before evaluating the condition
+        let mut builder = DataChunkBuilder::new(join_types.clone(), PROCESSING_WINDOW_SIZE);
+        // the rows padded with NULLs
+        let mut padded_builder = DataChunkBuilder::new(join_types, PROCESSING_WINDOW_SIZE);
+        // the indexes of the left row and the right row of each row in the builder
+        let mut pairs = vec![];
 
         // probe
-        for right_row in right_rows() {
-            let hash_value = right_row.get_by_indexes(&self.right_column_indexes);
-            for left_row in hash_map.get(&hash_value).unwrap_or(&vec![]) {
-                let values = left_row.values().chain(right_row.values());
+        #[for_await]
+        for right_chunk in self.right_child {
+            let right_chunk = right_chunk?;
+            let right_rows = right_chunk.rows().collect_vec();
+            let mut right_matched = vec![false; right_rows.len()];
+            for (i, j) in lookup(&hash_map, &self.right_column_indexes, &right_chunk) {
+                if !has_condition {
+                    left_matched[i] = true;
+                    right_matched[j] = true;
+                    if !outputs_joined_rows {
+                        continue;
+                    }
+                }
+                pairs.push((i, j));
+                let values = left_rows[i].values().chain(right_rows[j].values());
                 if let Some(chunk) = builder.push_row(values) {
-                    yield chunk;
+                    let chunk = if has_condition {
+                        filter_joined_rows(
+                            &self.condition,
+                            chunk,
+                            &pairs,
+                            single,
+                            &mut left_matched,
+                            &mut right_matched,
+                        )?
+                    } else {
+                        chunk
+                    };
+                    pairs.clear();
+                    if outputs_joined_rows {
+                        yield chunk;
+                    }
+                }
+            }
+            // the rest rows refer to this chunk, so the condition is evaluated on them now
+            if has_condition {
+                if let Some(chunk) = builder.take() {
+                    let chunk = filter_joined_rows(
+                        &self.condition,
+                        chunk,
+                        &pairs,
+                        single,
+                        &mut left_matched,
+                        &mut right_matched,
+                    )?;
+                    pairs.clear();
+                    if outputs_joined_rows {
+                        yield chunk;
+                    }
                 }
             }
+
+            // append rows for right outer join
+            if matches!(
+                self.join_op,
+                BoundJoinOperator::RightOuter | BoundJoinOperator::FullOuter
+            ) {
+                for (right_row, matched) in right_rows.iter().zip_eq(&right_matched) {
+                    if *matched {
+                        continue;
+                    }
+                    // append row: (NULL, right)
+                    let values =
+                        (self.left_types.iter().map(|_| DataValue::Null)).chain(right_row.values());
+                    if let Some(chunk) = padded_builder.push_row(values) {
+                        yield chunk;
+                    }
+                }
+            }
+        }
+        if let Some(chunk) = builder.take() {
+            yield chunk;
         }
 
-        // append rows for left outer join
-        if matches!(
-            self.join_op,
-            BoundJoinOperator::LeftOuter | BoundJoinOperator::FullOuter
-        ) {
-            let right_keys = right_rows()
-                .map(|row| row.get_by_indexes(&self.right_column_indexes))
-                .collect::<HashSet<Vec<DataValue>>>();
-            for left_row in left_rows() {
-                let hash_value = left_row.get_by_indexes(&self.left_column_indexes);
-                if right_keys.contains(&hash_value) {
+        // append the left rows of semi and anti joins
+        if !outputs_joined_rows {
+            let is_semi = self.join_op == BoundJoinOperator::LeftSemi;
+            let mut builder = DataChunkBuilder::new(&self.left_types, PROCESSING_WINDOW_SIZE);
+            for (left_row, matched) in left_rows.iter().zip_eq(&left_matched) {
+                if *matched != is_semi {
                     continue;
                 }
-                // append row: (left, NULL)
-                let values =
-                    (left_row.values()).chain(self.right_types.iter().map(|_| DataValue::Null));
-                if let Some(chunk) = builder.push_row(values) {
+                if let Some(chunk) = builder.push_row(left_row.values()) {
                     yield chunk;
                 }
             }
+            if let Some(chunk) = { builder }.take() {
+                yield chunk;
+            }
         }
 
-        // append rows for right outer join
+        // append rows for left outer and single join
         if matches!(
             self.join_op,
-            BoundJoinOperator::RightOuter | BoundJoinOperator::FullOuter
+            BoundJoinOperator::LeftOuter
+                | BoundJoinOperator::FullOuter
+                | BoundJoinOperator::LeftSingle
         ) {
-            for right_row in right_rows() {
-                let hash_value = right_row.get_by_indexes(&self.right_column_indexes);
-                if hash_map.contains_key(&hash_value) {
+            for (left_row, matched) in left_rows.iter().zip_eq(&left_matched) {
+                if *matched {
                     continue;
                 }
-                // append row: (NULL, right)
+                // append row: (left, NULL)
                 let values =
-                    (self.left_types.iter().map(|_| DataValue::Null)).chain(right_row.values());
-                if let Some(chunk) = builder.push_row(values) {
+                    (left_row.values()).chain(self.right_types.iter().map(|_| DataValue::Null));
+                if let Some(chunk) = padded_builder.push_row(values) {
                     yield chunk;
                 }
             }
         }
 
-        if let Some(chunk) = { builder }.take() {
+        if let Some(chunk) = { padded_builder }.take() {
             yield chunk;
         }
     }
 }
+
+/// Look up the keys of all rows of the right chunk in the hash table.
+///
+/// Returns the indexes of the left row and the right row of each matched pair.
+fn lookup(
+    hash_map: &HashMap<Vec<DataValue>, Vec<usize>>,
+    right_column_indexes: &[usize],
+    right_chunk: &DataChunk,
+) -> Vec<(usize, usize)> {
+    let key_arrays = right_column_indexes
+        .iter()
+        .map(|&index| right_chunk.array_at(index))
+        .collect_vec();
+    let mut pairs = vec![];
+    for j in 0..right_chunk.cardinality() {
+        let hash_value = key_arrays.iter().map(|array| array.get(j)).collect_vec();
+        if let Some(indexes) = hash_map.get(&hash_value) {
+            pairs.extend(indexes.iter().map(|&i| (i, j)));
+        }
+    }
+    pairs
+}
+
+/// Evaluate `condition` on the joined rows, whose left and right rows are at `pairs`.
+///
+/// Marks the rows of both sides which are matched, and returns the joined rows satisfying
+/// the condition. If `single`, a left row can not be matched more than once.
+fn filter_joined_rows(
+    condition: &BoundExpr,
+    chunk: DataChunk,
+    pairs: &[(usize, usize)],
+    single: bool,
+    left_matched: &mut [bool],
+    right_matched: &mut [bool],
+) -> Result<DataChunk, ExecutorError> {
+    let visibility = match condition.eval(&chunk)? {
+        ArrayImpl::Bool(a) => a.iter().map(|b| matches!(b, Some(true))).collect_vec(),
+        _ => panic!("unsupported value from join condition"),
+    };
+    for (&(i, j), &visible) in pairs.iter().zip_eq(&visibility) {
+        if visible {
+            if single && left_matched[i] {
+                return Err(ExecutorError::SubqueryMultipleRows);
+            }
+            left_matched[i] = true;
+            right_matched[j] = true;
+        }
+    }
+    Ok(chunk.filter(visibility.into_iter()))
+}
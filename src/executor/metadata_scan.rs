@@ -0,0 +1,104 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::sync::Arc;
+
+use risinglight_proto::rowset::block_statistics::BlockStatisticsType;
+
+use super::*;
+use crate::array::{ArrayBuilderImpl, DataChunk};
+use crate::binder::{AggKind, BoundExpr};
+use crate::optimizer::plan_nodes::PhysicalMetadataScan;
+use crate::storage::{
+    SecondaryStorage, Storage, StorageColumnRef, Table, Transaction, TxnIterator,
+};
+use crate::types::{ConvertError, DataTypeKind};
+
+/// The executor of answering aggregations from the metadata in the secondary storage.
+///
+/// The block statistics of a rowset also cover the rows deleted by its delete vectors, and the
+/// rowsets written before the minimum and maximum values are recorded lack them. So the
+/// statistics are only aggregated over the other rowsets, and these rowsets are scanned and
+/// aggregated instead.
+pub struct MetadataScanExecutor {
+    pub context: Arc<Context>,
+    pub plan: PhysicalMetadataScan,
+    pub storage: Arc<SecondaryStorage>,
+}
+
+impl MetadataScanExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecutorError)]
+    pub async fn execute(self) {
+        let logical = self.plan.logical();
+        let table = self.storage.get_table(logical.scan().table_ref_id())?;
+        let txn = table.read().await?;
+
+        let agg_calls = logical.agg_calls();
+        let stat_types = (agg_calls.iter())
+            .map(|agg| {
+                let column_id = match (&agg.kind, &agg.args[..]) {
+                    (AggKind::Min | AggKind::Max, [BoundExpr::InputRef(input_ref)]) => {
+                        logical.scan().column_ids()[input_ref.index]
+                    }
+                    // the rows are counted from the first column
+                    _ => 0,
+                };
+                let ty = match agg.kind {
+                    AggKind::RowCount => BlockStatisticsType::RowCount,
+                    AggKind::Min => BlockStatisticsType::MinValue,
+                    AggKind::Max => BlockStatisticsType::MaxValue,
+                    _ => unreachable!("unsupported aggregation from metadata: {}", agg.kind),
+                };
+                (ty, StorageColumnRef::Idx(column_id))
+            })
+            .collect_vec();
+        let mut values = txn.aggregate_exact_block_stat(&stat_types);
+        let mut rows = match values
+            .iter()
+            .zip(agg_calls)
+            .find(|(_, agg)| agg.kind == AggKind::RowCount)
+        {
+            Some((value, _)) => value.as_usize()?.unwrap_or(0),
+            None => 0,
+        };
+
+        // the i-th column of the scanned chunks is the column of the i-th aggregation
+        let mut iter = txn
+            .scan_rowsets_without_exact_block_stat(&stat_types)
+            .await?;
+        while let Some(chunk) = iter.next_batch(None).await? {
+            rows += chunk.cardinality();
+            for ((agg, value), array) in agg_calls.iter().zip(&mut values).zip(chunk.arrays()) {
+                if agg.kind == AggKind::RowCount {
+                    continue;
+                }
+                for i in 0..array.len() {
+                    let item = array.get(i);
+                    let replace = match (&item, &*value) {
+                        (DataValue::Null, _) => false,
+                        (_, DataValue::Null) => true,
+                        (item, value) if agg.kind == AggKind::Min => item < value,
+                        (item, value) => item > value,
+                    };
+                    if replace {
+                        *value = item;
+                    }
+                }
+            }
+        }
+        txn.abort().await?;
+
+        let mut arrays = vec![];
+        for (agg, value) in agg_calls.iter().zip_eq(values) {
+            let value = match agg.kind {
+                AggKind::RowCount => DataValue::Int32(i32::try_from(rows).map_err(|_| {
+                    ConvertError::Overflow(DataValue::Int64(rows as i64), DataTypeKind::Int(None))
+                })?),
+                _ => value,
+            };
+            let mut builder = ArrayBuilderImpl::with_capacity(1, &agg.return_type);
+            builder.push(&value);
+            arrays.push(builder.finish());
+        }
+        yield arrays.into_iter().collect::<DataChunk>();
+    }
+}
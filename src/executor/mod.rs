@@ -12,6 +12,7 @@
 //!
 //! [`try_stream`]: async_stream::try_stream
 
+use std::collections::HashMap;
 use std::future::Future;
 use std::sync::Arc;
 
@@ -19,6 +20,8 @@ use futures::stream::{BoxStream, StreamExt};
 use futures_async_stream::{for_await, try_stream};
 use itertools::Itertools;
 use minitrace::prelude::*;
+use parking_lot::Mutex;
+use tokio::sync::OnceCell;
 use tokio_util::sync::CancellationToken;
 
 pub use self::aggregation::*;
@@ -26,7 +29,9 @@ use self::context::*;
 use self::copy_from_file::*;
 use self::copy_to_file::*;
 use self::create::*;
+use self::cte_scan::*;
 use self::delete::*;
+use self::distinct_on::*;
 use self::drop::*;
 use self::dummy_scan::*;
 use self::explain::*;
@@ -36,21 +41,27 @@ use self::hash_join::*;
 use self::insert::*;
 use self::internal::*;
 use self::limit::*;
+use self::metadata_scan::*;
 use self::nested_loop_join::*;
 use self::order::*;
 use self::projection::*;
+use self::recursive_union::*;
+use self::set_operation::*;
 use self::simple_agg::*;
-#[allow(unused_imports)]
 use self::sort_agg::*;
-#[allow(unused_imports)]
 use self::sort_merge_join::*;
 use self::table_scan::*;
 use self::top_n::TopNExecutor;
+use self::union_all::*;
+use self::update::*;
 use self::values::*;
+use self::window::*;
+use self::work_table_scan::*;
 use crate::array::DataChunk;
 use crate::binder::BoundExpr;
 use crate::optimizer::plan_nodes::*;
 use crate::optimizer::PlanVisitor;
+use crate::parser::SetOperator;
 use crate::storage::{StorageImpl, TracedStorageError};
 use crate::types::{ConvertError, DataValue};
 
@@ -59,7 +70,9 @@ pub mod context;
 mod copy_from_file;
 mod copy_to_file;
 mod create;
+mod cte_scan;
 mod delete;
+mod distinct_on;
 mod drop;
 mod dummy_scan;
 pub mod evaluator;
@@ -70,15 +83,22 @@ mod hash_join;
 mod insert;
 mod internal;
 mod limit;
+mod metadata_scan;
 mod nested_loop_join;
 mod order;
 mod projection;
+mod recursive_union;
+mod set_operation;
 mod simple_agg;
 mod sort_agg;
 mod sort_merge_join;
 mod table_scan;
 mod top_n;
+mod union_all;
+mod update;
 mod values;
+mod window;
+mod work_table_scan;
 
 /// The error type of execution.
 #[derive(thiserror::Error, Debug)]
@@ -114,6 +134,8 @@ pub enum ExecutorError {
     ExceedLengthLimit { length: u64, width: u64 },
     #[error("abort")]
     Abort,
+    #[error("more than one row returned by a subquery used as an expression")]
+    SubqueryMultipleRows,
 }
 
 /// The maximum chunk length produced by executor at a time.
@@ -127,17 +149,39 @@ const PROCESSING_WINDOW_SIZE: usize = 1024;
 /// and produces a stream to its parent.
 pub type BoxedExecutor = BoxStream<'static, Result<DataChunk, ExecutorError>>;
 
+/// The result of a CTE, which is materialized by the first reference to it.
+type CteResult = Arc<OnceCell<Vec<DataChunk>>>;
+
 /// The builder of executor.
 #[derive(Clone)]
 pub struct ExecutorBuilder {
     context: Arc<Context>,
     storage: StorageImpl,
+    /// The results of materialized CTEs, which are shared by all references to the CTE.
+    ctes: Arc<Mutex<HashMap<usize, CteResult>>>,
+    /// The working tables of recursive CTEs being evaluated.
+    work_tables: HashMap<usize, Arc<Vec<DataChunk>>>,
 }
 
 impl ExecutorBuilder {
     /// Create a new executor builder.
     pub fn new(context: Arc<Context>, storage: StorageImpl) -> ExecutorBuilder {
-        ExecutorBuilder { context, storage }
+        ExecutorBuilder {
+            context,
+            storage,
+            ctes: Default::default(),
+            work_tables: HashMap::new(),
+        }
+    }
+
+    /// Create a builder for an iteration of a recursive CTE, whose working table holds `chunks`.
+    fn with_work_table(&self, cte_id: usize, chunks: Vec<DataChunk>) -> ExecutorBuilder {
+        let mut builder = self.clone();
+        // CTEs in the recursive term may refer to the working table, so their results can not be
+        // shared across iterations
+        builder.ctes = Default::default();
+        builder.work_tables.insert(cte_id, Arc::new(chunks));
+        builder
     }
 
     pub fn build(&mut self, plan: PlanRef) -> BoxedExecutor {
@@ -429,6 +473,41 @@ impl PlanVisitor<BoxedExecutor> for ExecutorBuilder {
         ))
     }
 
+    fn visit_physical_sort_agg(&mut self, plan: &PhysicalSortAgg) -> Option<BoxedExecutor> {
+        Some(ExecutorBuilder::trace_execute(
+            SortAggExecutor {
+                agg_calls: plan.logical().agg_calls().to_vec(),
+                group_keys: plan.logical().group_keys().to_vec(),
+                child: self.visit(plan.child()).unwrap(),
+            }
+            .execute(),
+            "SortAggExecutor",
+        ))
+    }
+
+    fn visit_physical_window(&mut self, plan: &PhysicalWindow) -> Option<BoxedExecutor> {
+        Some(ExecutorBuilder::trace_execute(
+            WindowExecutor {
+                window_calls: plan.logical().window_calls().to_vec(),
+                child_column_count: plan.child().out_types().len(),
+                child: self.visit(plan.child()).unwrap(),
+            }
+            .execute(),
+            "WindowExecutor",
+        ))
+    }
+
+    fn visit_physical_distinct_on(&mut self, plan: &PhysicalDistinctOn) -> Option<BoxedExecutor> {
+        Some(ExecutorBuilder::trace_execute(
+            DistinctOnExecutor {
+                distinct_on: plan.logical().distinct_on().to_vec(),
+                child: self.visit(plan.child()).unwrap(),
+            }
+            .execute(),
+            "DistinctOnExecutor",
+        ))
+    }
+
     fn visit_physical_hash_join(&mut self, plan: &PhysicalHashJoin) -> Option<BoxedExecutor> {
         let left_child = self.visit(plan.left()).unwrap();
         let right_child = self.visit(plan.right()).unwrap();
@@ -446,7 +525,7 @@ impl PlanVisitor<BoxedExecutor> for ExecutorBuilder {
                 left_child,
                 right_child,
                 join_op: plan.logical().join_op(),
-                condition: BoundExpr::Constant(DataValue::Bool(true)),
+                condition: plan.logical().predicate().non_eq_cond(),
                 left_column_indexes,
                 right_column_indexes,
                 left_types: plan.left().out_types(),
@@ -457,6 +536,29 @@ impl PlanVisitor<BoxedExecutor> for ExecutorBuilder {
         ))
     }
 
+    fn visit_physical_sort_merge_join(
+        &mut self,
+        plan: &PhysicalSortMergeJoin,
+    ) -> Option<BoxedExecutor> {
+        let left_child = self.visit(plan.left()).unwrap();
+        let right_child = self.visit(plan.right()).unwrap();
+
+        let left_col_num = plan.left().out_types().len();
+        let (left, right) = &plan.logical().predicate().eq_keys()[0];
+        Some(ExecutorBuilder::trace_execute(
+            SortMergeJoinExecutor {
+                left_child,
+                right_child,
+                left_column_index: left.index,
+                right_column_index: right.index - left_col_num,
+                left_types: plan.left().out_types(),
+                right_types: plan.right().out_types(),
+            }
+            .execute(),
+            "SortMergeJoinExecutor",
+        ))
+    }
+
     fn visit_physical_simple_agg(&mut self, plan: &PhysicalSimpleAgg) -> Option<BoxedExecutor> {
         Some(ExecutorBuilder::trace_execute(
             SimpleAggExecutor {
@@ -493,6 +595,33 @@ impl PlanVisitor<BoxedExecutor> for ExecutorBuilder {
         ))
     }
 
+    fn visit_physical_update(&mut self, plan: &PhysicalUpdate) -> Option<BoxedExecutor> {
+        let child = self.visit(plan.child()).unwrap();
+        Some(ExecutorBuilder::trace_execute(
+            match &self.storage {
+                StorageImpl::InMemoryStorage(storage) => UpdateExecutor {
+                    context: self.context.clone(),
+                    child,
+                    table_ref_id: plan.logical().table_ref_id(),
+                    value_exprs: plan.logical().value_exprs().to_vec(),
+                    storage: storage.clone(),
+                }
+                .execute()
+                .cancellable(self.context.token().child_token()),
+                StorageImpl::SecondaryStorage(storage) => UpdateExecutor {
+                    context: self.context.clone(),
+                    child,
+                    table_ref_id: plan.logical().table_ref_id(),
+                    value_exprs: plan.logical().value_exprs().to_vec(),
+                    storage: storage.clone(),
+                }
+                .execute()
+                .cancellable(self.context.token().child_token()),
+            },
+            "UpdateExecutor",
+        ))
+    }
+
     fn visit_physical_values(&mut self, plan: &PhysicalValues) -> Option<BoxedExecutor> {
         Some(ExecutorBuilder::trace_execute(
             ValuesExecutor {
@@ -504,6 +633,114 @@ impl PlanVisitor<BoxedExecutor> for ExecutorBuilder {
         ))
     }
 
+    fn visit_physical_cte_scan(&mut self, plan: &PhysicalCteScan) -> Option<BoxedExecutor> {
+        let result = self
+            .ctes
+            .lock()
+            .entry(plan.logical().cte_id())
+            .or_default()
+            .clone();
+        Some(ExecutorBuilder::trace_execute(
+            CteScanExecutor {
+                result,
+                child: self.visit(plan.child()).unwrap(),
+            }
+            .execute(),
+            "CteScanExecutor",
+        ))
+    }
+
+    fn visit_physical_recursive_union(
+        &mut self,
+        plan: &PhysicalRecursiveUnion,
+    ) -> Option<BoxedExecutor> {
+        Some(ExecutorBuilder::trace_execute(
+            RecursiveUnionExecutor {
+                cte_id: plan.logical().cte_id(),
+                union_all: plan.logical().union_all(),
+                left_child: self.visit(plan.left()).unwrap(),
+                right_plan: plan.right(),
+                builder: self.clone(),
+            }
+            .execute()
+            .cancellable(self.context.token().child_token()),
+            "RecursiveUnionExecutor",
+        ))
+    }
+
+    fn visit_physical_work_table_scan(
+        &mut self,
+        plan: &PhysicalWorkTableScan,
+    ) -> Option<BoxedExecutor> {
+        Some(ExecutorBuilder::trace_execute(
+            WorkTableScanExecutor {
+                chunks: self.work_tables[&plan.logical().cte_id()].clone(),
+            }
+            .execute(),
+            "WorkTableScanExecutor",
+        ))
+    }
+
+    fn visit_physical_metadata_scan(
+        &mut self,
+        plan: &PhysicalMetadataScan,
+    ) -> Option<BoxedExecutor> {
+        Some(ExecutorBuilder::trace_execute(
+            match &self.storage {
+                // the in-memory storage has no metadata, so the table is scanned and aggregated
+                StorageImpl::InMemoryStorage(storage) => SimpleAggExecutor {
+                    agg_calls: plan.logical().agg_calls().to_vec(),
+                    child: TableScanExecutor {
+                        context: self.context.clone(),
+                        plan: PhysicalTableScan::new(plan.logical().scan().clone()),
+                        expr: None,
+                        storage: storage.clone(),
+                    }
+                    .execute()
+                    .cancellable(self.context.token().child_token()),
+                }
+                .execute(),
+                StorageImpl::SecondaryStorage(storage) => MetadataScanExecutor {
+                    context: self.context.clone(),
+                    plan: plan.clone(),
+                    storage: storage.clone(),
+                }
+                .execute()
+                .cancellable(self.context.token().child_token()),
+            },
+            "MetadataScanExecutor",
+        ))
+    }
+
+    fn visit_physical_set_operation(
+        &mut self,
+        plan: &PhysicalSetOperation,
+    ) -> Option<BoxedExecutor> {
+        let left_child = self.visit(plan.left()).unwrap();
+        let right_child = self.visit(plan.right()).unwrap();
+        let logical = plan.logical();
+        if *logical.op() == SetOperator::Union && logical.all() {
+            return Some(ExecutorBuilder::trace_execute(
+                UnionAllExecutor {
+                    left_child,
+                    right_child,
+                }
+                .execute(),
+                "UnionAllExecutor",
+            ));
+        }
+        Some(ExecutorBuilder::trace_execute(
+            SetOperationExecutor {
+                op: logical.op().clone(),
+                all: logical.all(),
+                left_child,
+                right_child,
+            }
+            .execute(),
+            "SetOperationExecutor",
+        ))
+    }
+
     fn visit_physical_copy_from_file(
         &mut self,
         plan: &PhysicalCopyFromFile,
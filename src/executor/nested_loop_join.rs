@@ -22,12 +22,17 @@ pub struct NestedLoopJoinExecutor {
 impl NestedLoopJoinExecutor {
     #[try_stream(boxed, ok = DataChunk, error = ExecutorError)]
     pub async fn execute(self) {
-        // only support inner and left outer join
         if matches!(
             self.join_op,
-            BoundJoinOperator::RightOuter | BoundJoinOperator::FullOuter
+            BoundJoinOperator::LeftSemi
+                | BoundJoinOperator::LeftAnti
+                | BoundJoinOperator::LeftSingle
         ) {
-            panic!("unsupported join type");
+            #[for_await]
+            for chunk in self.execute_per_left_row() {
+                yield chunk?;
+            }
+            return Ok(());
         }
         let left_chunks = self.left_child.try_collect::<Vec<DataChunk>>().await?;
 
@@ -41,6 +46,13 @@ impl NestedLoopJoinExecutor {
         );
 
         let mut right_row_num = 0;
+        // the right chunks are kept to append the unmatched right rows of right and full outer
+        // joins
+        let keep_right = matches!(
+            self.join_op,
+            BoundJoinOperator::RightOuter | BoundJoinOperator::FullOuter
+        );
+        let mut right_chunks = vec![];
         // cross join: left x right
         #[for_await]
         for right_chunk in self.right_child {
@@ -62,6 +74,9 @@ impl NestedLoopJoinExecutor {
                 }
             }
             right_row_num += right_chunk.cardinality();
+            if keep_right {
+                right_chunks.push(right_chunk);
+            }
         }
 
         // take rest of data
@@ -80,8 +95,11 @@ impl NestedLoopJoinExecutor {
             _ => panic!("unsupported value from join condition"),
         };
 
-        // append rows for left outer join
-        if matches!(self.join_op, BoundJoinOperator::LeftOuter) {
+        // append rows for left and full outer join
+        if matches!(
+            self.join_op,
+            BoundJoinOperator::LeftOuter | BoundJoinOperator::FullOuter
+        ) {
             // we need to pick row of left_row which unmatched rows
             let left_row_num = left_rows().count();
             for (mut i, left_row) in left_rows().enumerate() {
@@ -105,6 +123,105 @@ impl NestedLoopJoinExecutor {
             }
         }
 
+        // append rows for right and full outer join
+        if keep_right {
+            let left_row_num = left_rows().count();
+            let right_rows = right_chunks.iter().flat_map(|chunk| chunk.rows());
+            for (j, right_row) in right_rows.enumerate() {
+                // the matching results of the j-th right row are consecutive in the `filter`
+                let matched = (j * left_row_num..(j + 1) * left_row_num)
+                    .any(|i| matches!(filter.get(i), Some(true)));
+                if matched {
+                    continue;
+                }
+                // if all false, we append row: (NULL, right)
+                let values =
+                    (self.left_types.iter().map(|_| DataValue::Null)).chain(right_row.values());
+                if let Some(chunk) = builder.push_row(values) {
+                    yield chunk;
+                }
+            }
+        }
+
+        if let Some(chunk) = { builder }.take() {
+            yield chunk;
+        }
+    }
+
+    /// Execute semi, anti and single joins, whose output depends on the number of matched right
+    /// rows of each left row.
+    #[try_stream(boxed, ok = DataChunk, error = ExecutorError)]
+    async fn execute_per_left_row(self) {
+        let left_chunks = self.left_child.try_collect::<Vec<DataChunk>>().await?;
+        let right_chunks = self.right_child.try_collect::<Vec<DataChunk>>().await?;
+
+        let join_types = self.left_types.iter().chain(self.right_types.iter());
+        let mut builder = match self.join_op {
+            BoundJoinOperator::LeftSingle => {
+                DataChunkBuilder::new(join_types.clone(), PROCESSING_WINDOW_SIZE)
+            }
+            _ => DataChunkBuilder::new(self.left_types.iter(), PROCESSING_WINDOW_SIZE),
+        };
+
+        for left_row in left_chunks.iter().flat_map(|chunk| chunk.rows()) {
+            // join the left row with all right rows
+            let mut join_builder =
+                DataChunkBuilder::new(join_types.clone(), PROCESSING_WINDOW_SIZE);
+            let mut joined_chunks = vec![];
+            for right_row in right_chunks.iter().flat_map(|chunk| chunk.rows()) {
+                let values = left_row.values().chain(right_row.values());
+                if let Some(chunk) = join_builder.push_row(values) {
+                    joined_chunks.push(chunk);
+                }
+            }
+            joined_chunks.extend(join_builder.take());
+
+            // find the matched rows
+            let mut matched_count = 0;
+            let mut matched_row = None;
+            for chunk in &joined_chunks {
+                let filter = match self.condition.eval(chunk)? {
+                    ArrayImpl::Bool(a) => a,
+                    _ => panic!("unsupported value from join condition"),
+                };
+                for (i, matched) in filter.iter().enumerate() {
+                    if matches!(matched, Some(true)) {
+                        matched_count += 1;
+                        if matched_row.is_none() {
+                            matched_row = Some(chunk.row(i));
+                        }
+                    }
+                }
+            }
+
+            let output = match self.join_op {
+                BoundJoinOperator::LeftSemi if matched_count > 0 => {
+                    builder.push_row(left_row.values())
+                }
+                BoundJoinOperator::LeftAnti if matched_count == 0 => {
+                    builder.push_row(left_row.values())
+                }
+                BoundJoinOperator::LeftSingle => match matched_row {
+                    _ if matched_count > 1 => {
+                        // discard the output rows, as the builder can not be dropped with them
+                        let _ = builder.take();
+                        return Err(ExecutorError::SubqueryMultipleRows);
+                    }
+                    Some(row) => builder.push_row(row.values()),
+                    // if no row matched, we append row: (left, NULL)
+                    None => builder.push_row(
+                        left_row
+                            .values()
+                            .chain(self.right_types.iter().map(|_| DataValue::Null)),
+                    ),
+                },
+                _ => None,
+            };
+            if let Some(chunk) = output {
+                yield chunk;
+            }
+        }
+
         if let Some(chunk) = { builder }.take() {
             yield chunk;
         }
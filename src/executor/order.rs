@@ -45,20 +45,31 @@ pub(super) fn cmp(row1: &RowRef, row2: &RowRef, comparators: &[BoundOrderBy]) ->
     for cmp in comparators {
         let column_index = match &cmp.expr {
             BoundExpr::InputRef(input_ref) => input_ref.index,
-            _ => todo!("only support order by columns now"),
+            _ => unreachable!("sort keys are evaluated into columns by the projection below"),
         };
         let v1 = row1.get(column_index);
         let v2 = row2.get(column_index);
-        match v1.partial_cmp(&v2).unwrap() {
-            Ordering::Equal => continue,
-            o if cmp.descending => return o.reverse(),
-            o => return o,
+        let nulls = if cmp.nulls_first {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        };
+        // the nulls ordering does not depend on the direction
+        let ordering = match (&v1, &v2) {
+            (DataValue::Null, DataValue::Null) => Ordering::Equal,
+            (DataValue::Null, _) => nulls,
+            (_, DataValue::Null) => nulls.reverse(),
+            _ if cmp.descending => v1.total_cmp(&v2).reverse(),
+            _ => v1.total_cmp(&v2),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
         }
     }
     Ordering::Equal
 }
 
 /// Generate an array of indexes for each element of the chunks.
-fn gen_index_array(chunks: &[DataChunk]) -> Vec<RowRef<'_>> {
+pub(super) fn gen_index_array(chunks: &[DataChunk]) -> Vec<RowRef<'_>> {
     chunks.iter().flat_map(|chunk| chunk.rows()).collect()
 }
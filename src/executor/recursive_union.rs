@@ -0,0 +1,71 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::collections::HashSet;
+
+use super::*;
+use crate::array::DataChunk;
+use crate::types::DataValue;
+
+/// The executor of a recursive CTE.
+///
+/// The non-recursive term is executed first. Then the recursive term is executed repeatedly on
+/// the working table, which holds the rows produced by the last iteration, until no more rows are
+/// produced.
+pub struct RecursiveUnionExecutor {
+    pub cte_id: usize,
+    pub union_all: bool,
+    pub left_child: BoxedExecutor,
+    /// The plan of the recursive term, which is built into a new executor in each iteration.
+    pub right_plan: PlanRef,
+    pub builder: ExecutorBuilder,
+}
+
+impl RecursiveUnionExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecutorError)]
+    pub async fn execute(self) {
+        let mut visited = HashSet::new();
+        let mut working_table = vec![];
+        #[for_await]
+        for chunk in self.left_child {
+            if let Some(chunk) = Self::deduplicate(chunk?, self.union_all, &mut visited) {
+                working_table.push(chunk.clone());
+                yield chunk;
+            }
+        }
+        while !working_table.is_empty() {
+            let mut builder = self
+                .builder
+                .with_work_table(self.cte_id, std::mem::take(&mut working_table));
+            #[for_await]
+            for chunk in builder.build(self.right_plan.clone()) {
+                if let Some(chunk) = Self::deduplicate(chunk?, self.union_all, &mut visited) {
+                    working_table.push(chunk.clone());
+                    yield chunk;
+                }
+            }
+        }
+    }
+
+    /// Removes rows which have been produced before if the terms are combined by `UNION`.
+    ///
+    /// Returns `None` if no rows are left.
+    fn deduplicate(
+        chunk: DataChunk,
+        union_all: bool,
+        visited: &mut HashSet<Vec<DataValue>>,
+    ) -> Option<DataChunk> {
+        let chunk = if union_all {
+            chunk
+        } else {
+            let visibility = chunk
+                .rows()
+                .map(|row| visited.insert(row.values().collect()))
+                .collect_vec();
+            chunk.filter(visibility.into_iter())
+        };
+        if chunk.cardinality() == 0 {
+            return None;
+        }
+        Some(chunk)
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::collections::HashSet;
+
+use super::*;
+use crate::array::DataChunk;
+use crate::parser::SetOperator;
+
+/// The executor of `UNION`, `INTERSECT [ALL]` and `EXCEPT [ALL]`.
+///
+/// Rows are compared by their [`HashKey`]s. `UNION` outputs rows of both children which have not
+/// been output before. Otherwise, the rows of the right child are counted in a hash table first,
+/// and then the rows of the left child are filtered by the table.
+pub struct SetOperationExecutor {
+    pub op: SetOperator,
+    pub all: bool,
+    pub left_child: BoxedExecutor,
+    pub right_child: BoxedExecutor,
+}
+
+impl SetOperationExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecutorError)]
+    pub async fn execute(self) {
+        // rows which have been output, used to remove duplicates
+        let mut visited = HashSet::new();
+        if self.op == SetOperator::Union {
+            assert!(
+                !self.all,
+                "UNION ALL should be executed by UnionAllExecutor"
+            );
+            #[for_await]
+            for chunk in self.left_child.chain(self.right_child) {
+                if let Some(chunk) = filter_rows(chunk?, |key| visited.insert(key)) {
+                    yield chunk;
+                }
+            }
+            return Ok(());
+        }
+
+        // the number of rows of the right child which are not matched yet
+        let mut right_rows: HashMap<HashKey, usize> = HashMap::new();
+        #[for_await]
+        for chunk in self.right_child {
+            for row in chunk?.rows() {
+                *right_rows.entry(row.values().collect()).or_default() += 1;
+            }
+        }
+
+        let (intersect, all) = (self.op == SetOperator::Intersect, self.all);
+        #[for_await]
+        for chunk in self.left_child {
+            let chunk = filter_rows(chunk?, |key| {
+                let matched = match right_rows.get_mut(&key) {
+                    // each row of the right child matches at most one row with `ALL`
+                    Some(count) if *count > 0 => {
+                        if all {
+                            *count -= 1;
+                        }
+                        true
+                    }
+                    _ => false,
+                };
+                if all {
+                    matched == intersect
+                } else {
+                    matched == intersect && visited.insert(key)
+                }
+            });
+            if let Some(chunk) = chunk {
+                yield chunk;
+            }
+        }
+    }
+}
+
+/// Keep the rows of `chunk` for which `predicate` on their keys returns true.
+///
+/// Returns `None` if no rows are left.
+fn filter_rows(chunk: DataChunk, mut predicate: impl FnMut(HashKey) -> bool) -> Option<DataChunk> {
+    let visibility = chunk
+        .rows()
+        .map(|row| predicate(row.values().collect()))
+        .collect_vec();
+    let chunk = chunk.filter(visibility.into_iter());
+    if chunk.cardinality() == 0 {
+        return None;
+    }
+    Some(chunk)
+}
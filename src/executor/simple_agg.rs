@@ -71,8 +71,8 @@ pub(super) fn create_agg_states(
     agg_calls.iter().map(create_agg_state).collect()
 }
 
-fn create_agg_state(agg_call: &BoundAggCall) -> Box<dyn AggregationState> {
-    match agg_call.kind {
+pub(super) fn create_agg_state(agg_call: &BoundAggCall) -> Box<dyn AggregationState> {
+    let state: Box<dyn AggregationState> = match agg_call.kind {
         AggKind::RowCount => Box::new(RowCountAggregationState::new(DataValue::Int32(0))),
         AggKind::Count => Box::new(CountAggregationState::new(DataValue::Int32(0))),
         AggKind::Max => Box::new(MinMaxAggregationState::new(
@@ -85,5 +85,10 @@ fn create_agg_state(agg_call: &BoundAggCall) -> Box<dyn AggregationState> {
         )),
         AggKind::Sum => Box::new(SumAggregationState::new(agg_call.return_type.kind())),
         _ => panic!("Unsupported aggregate kind"),
+    };
+    if agg_call.distinct {
+        Box::new(DistinctAggregationState::new(state))
+    } else {
+        state
     }
 }
@@ -1,12 +1,15 @@
 // Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
-#![allow(dead_code)]
+
 use smallvec::SmallVec;
 
 use super::*;
 use crate::array::{ArrayBuilderImpl, ArrayImpl};
 use crate::binder::BoundAggCall;
-use crate::types::{DataTypeExt, DataTypeKind};
 
+/// The executor of sort aggregation.
+///
+/// The child must be sorted by the group keys, so that the rows of each group are adjacent. The
+/// output is the group keys followed by the results of the aggregations, in the order of groups.
 pub struct SortAggExecutor {
     pub agg_calls: Vec<BoundAggCall>,
     pub group_keys: Vec<BoundExpr>,
@@ -18,6 +21,8 @@ impl SortAggExecutor {
     pub async fn execute(self) {
         let mut last_key = None::<HashKey>;
         let mut states = create_agg_states(&self.agg_calls);
+        let mut builders = create_builders(&self.agg_calls, &self.group_keys);
+        let mut num_groups = 0;
 
         #[for_await]
         for chunk in self.child {
@@ -41,15 +46,23 @@ impl SortAggExecutor {
                 for col in group_cols.iter() {
                     group_key.push(col.get(row_idx));
                 }
-                // Check group key & last key
-                match last_key {
-                    Some(last_key) => {
-                        if last_key != group_key {
-                            yield Self::finish_agg(&states);
-                            states = create_agg_states(&self.agg_calls);
+                // Output the last group when a new group begins
+                match &last_key {
+                    Some(key) if *key != group_key => {
+                        finish_group(key, &states, &mut builders);
+                        states = create_agg_states(&self.agg_calls);
+                        num_groups += 1;
+                        if num_groups == PROCESSING_WINDOW_SIZE {
+                            yield std::mem::replace(
+                                &mut builders,
+                                create_builders(&self.agg_calls, &self.group_keys),
+                            )
+                            .into_iter()
+                            .collect();
+                            num_groups = 0;
                         }
                     }
-                    None => (),
+                    _ => {}
                 }
                 for (state, expr) in states.iter_mut().zip_eq(&exprs) {
                     state.update_single(&expr.get(row_idx))?;
@@ -57,26 +70,35 @@ impl SortAggExecutor {
                 last_key = Some(group_key);
             }
         }
-        if last_key.is_some() {
-            yield Self::finish_agg(&states);
+        if let Some(key) = &last_key {
+            finish_group(key, &states, &mut builders);
+            yield builders.into_iter().collect();
         }
     }
+}
 
-    fn finish_agg(states: &SmallVec<[Box<dyn AggregationState>; 16]>) -> DataChunk {
-        return states
-            .iter()
-            .map(|s| {
-                let result = &s.output();
-                match &result.data_type() {
-                    Some(r) => {
-                        let mut builder = ArrayBuilderImpl::with_capacity(1, r);
-                        builder.push(result);
-                        builder.finish()
-                    }
-                    None => ArrayBuilderImpl::new(&DataTypeKind::Int(None).nullable()).finish(),
-                }
-            })
-            .collect::<DataChunk>();
+/// Create the builders of the group keys and the results of the aggregations.
+fn create_builders(agg_calls: &[BoundAggCall], group_keys: &[BoundExpr]) -> Vec<ArrayBuilderImpl> {
+    group_keys
+        .iter()
+        .map(|e| ArrayBuilderImpl::new(&e.return_type().unwrap()))
+        .chain(
+            agg_calls
+                .iter()
+                .map(|agg| ArrayBuilderImpl::new(&agg.return_type)),
+        )
+        .collect()
+}
+
+/// Push the group key and the results of the aggregations of a group into the builders.
+fn finish_group(
+    key: &HashKey,
+    states: &SmallVec<[Box<dyn AggregationState>; 16]>,
+    builders: &mut [ArrayBuilderImpl],
+) {
+    let values = key.iter().cloned().chain(states.iter().map(|s| s.output()));
+    for (value, builder) in values.zip_eq(builders) {
+        builder.push(&value);
     }
 }
 
@@ -104,10 +126,10 @@ mod tests {
                 vec![1.2, 1.2, 2.3, 2.4, 2.5],
             ],
             vec![
-                vec![1.3, 2.2],
-                vec![0.3, 1.3],
-                vec![0.4, 1.4],
-                vec![0.5, 1.5],
+                vec![1.1, 1.2, 1.3, 2.2],
+                vec![1.3, 2.3, 0.3, 1.3],
+                vec![1.4, 2.4, 0.4, 1.4],
+                vec![1.5, 2.5, 0.5, 1.5],
             ],
         )
         .await;
@@ -120,11 +142,11 @@ mod tests {
                 vec![1.1, 1.2, 2.3, 2.4, 2.5],
             ],
             vec![
-                vec![0.1, 1.1],
-                vec![0.2, 1.1],
-                vec![0.3, 1.3],
-                vec![0.4, 1.4],
-                vec![0.5, 1.5],
+                vec![1.1, 1.1, 0.1, 1.1],
+                vec![1.1, 1.2, 0.2, 1.1],
+                vec![1.3, 2.3, 0.3, 1.3],
+                vec![1.4, 2.4, 0.4, 1.4],
+                vec![1.5, 2.5, 0.5, 1.5],
             ],
         )
         .await
@@ -136,7 +158,7 @@ mod tests {
             vec![0, 1],
             vec![0],
             vec![vec![1.0, 1.0], vec![1.0, 2.0]],
-            vec![vec![2.0, 3.0]],
+            vec![vec![1.0, 2.0, 3.0]],
         )
         .await;
         test_group_agg(
@@ -148,10 +170,10 @@ mod tests {
                 vec![2.1, 2.2, 2.3, 2.4, 2.5],
             ],
             vec![
-                vec![1.3, 2.2],
-                vec![0.3, 1.3],
-                vec![0.4, 1.4],
-                vec![0.5, 1.5],
+                vec![1.1, 1.3, 2.2],
+                vec![1.3, 0.3, 1.3],
+                vec![1.4, 0.4, 1.4],
+                vec![1.5, 0.5, 1.5],
             ],
         )
         .await;
@@ -164,11 +186,11 @@ mod tests {
                 vec![2.1, 2.2, 2.3, 2.4, 2.5],
             ],
             vec![
-                vec![0.1, 1.1],
-                vec![0.2, 1.2],
-                vec![0.3, 1.3],
-                vec![0.4, 1.4],
-                vec![0.5, 1.5],
+                vec![1.1, 0.1, 1.1],
+                vec![1.2, 0.2, 1.2],
+                vec![1.3, 0.3, 1.3],
+                vec![1.4, 0.4, 1.4],
+                vec![1.5, 0.5, 1.5],
             ],
         )
         .await
@@ -178,7 +200,7 @@ mod tests {
         agg_call_index: Vec<usize>,
         group_key_index: Vec<usize>,
         cols: Vec<Vec<f64>>,
-        expected_cols: Vec<Vec<f64>>,
+        expected_rows: Vec<Vec<f64>>,
     ) {
         let mut agg_calls = vec![];
         for index in agg_call_index {
@@ -206,18 +228,16 @@ mod tests {
         };
         let mut executor = executor.execute();
 
-        let mut expected_cols_size = 0;
-        while let Some(chunk) = executor.next().await {
-            let chunk = chunk.unwrap();
-            let expected_col = expected_cols.get(expected_cols_size).unwrap();
-            let mut expected_array = vec![];
-            for data in expected_col {
-                expected_array.push(ArrayImpl::new_float64(vec![*data].into_iter().collect()));
-            }
-            assert_eq!(chunk.arrays(), expected_array);
-            expected_cols_size += 1;
+        let chunk = executor.next().await.map(|chunk| chunk.unwrap());
+        if expected_rows.is_empty() {
+            assert!(chunk.is_none());
+            return;
         }
-        assert_eq!(expected_cols_size, expected_cols.len());
+        let expected_arrays: Vec<ArrayImpl> = (0..expected_rows[0].len())
+            .map(|i| ArrayImpl::new_float64(expected_rows.iter().map(|row| row[i]).collect()))
+            .collect();
+        assert_eq!(chunk.unwrap().arrays(), expected_arrays);
+        assert!(executor.next().await.is_none());
     }
 
     fn create_sum_agg_call(value: usize) -> BoundAggCall {
@@ -231,21 +251,14 @@ mod tests {
                 ),
             })],
             return_type: DataType::new(DataTypeKind::Double, false),
+            distinct: false,
         }
     }
 
     fn create_input_ref(value: usize) -> BoundExpr {
         BoundExpr::InputRef(BoundInputRef {
             index: value,
-            return_type: DataType::new(DataTypeKind::Int(Option::None), false),
+            return_type: DataType::new(DataTypeKind::Double, false),
         })
     }
-
-    fn create_expected_col(cols: Vec<f64>) -> Vec<ArrayImpl> {
-        let mut expected = Vec::new();
-        for col in cols {
-            expected.push(ArrayImpl::new_float64([col].into_iter().collect()));
-        }
-        expected
-    }
 }
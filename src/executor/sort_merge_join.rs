@@ -1,12 +1,15 @@
 // Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
-#![allow(dead_code)]
+
 use std::cmp::Ordering;
 use std::vec::Vec;
 
 use super::*;
 use crate::array::{DataChunk, DataChunkBuilder};
 use crate::types::{DataType, Row};
-/// The executor for sort merge join
+/// The executor for sort merge join.
+///
+/// Both children must be sorted by the join key in ascending order. Rows with NULL keys never
+/// match, so they are skipped.
 pub struct SortMergeJoinExecutor {
     pub left_child: BoxedExecutor,
     pub right_child: BoxedExecutor,
@@ -86,7 +89,7 @@ pub fn compare_row(
 ) -> Ordering {
     let left_data_value = &left_row[left_column_index];
     let right_data_value = &right_row[right_column_index];
-    left_data_value.partial_cmp(right_data_value).unwrap()
+    left_data_value.total_cmp(right_data_value)
 }
 
 // convert chunk stream to row stream
@@ -100,32 +103,29 @@ async fn row_stream(stream: BoxedExecutor) {
     }
 }
 
-// chunk same join key rows together
+// chunk same join key rows together, skipping the rows with NULL keys
 #[try_stream(boxed, ok = Vec<Row>, error = ExecutorError)]
 async fn same_key_chunks(
     row_stream: BoxStream<'static, Result<Row, ExecutorError>>,
     column_index: usize,
 ) {
-    let mut current_row = None;
-    let mut chunk = Vec::new();
+    let mut chunk: Vec<Row> = Vec::new();
     #[for_await]
     for row in row_stream {
         let row = row?;
-        if current_row.is_none() {
-            current_row = Some(row);
-            chunk.push(current_row.clone().unwrap());
+        if row[column_index] == DataValue::Null {
             continue;
         }
-        if current_row.as_ref().unwrap().get(column_index) == row.get(column_index) {
-            chunk.push(row);
-        } else {
-            yield chunk;
-            chunk = Vec::new();
-            current_row = Some(row);
-            chunk.push(current_row.clone().unwrap());
+        if let Some(first) = chunk.first() {
+            if first[column_index] != row[column_index] {
+                yield std::mem::take(&mut chunk);
+            }
         }
+        chunk.push(row);
+    }
+    if !chunk.is_empty() {
+        yield chunk;
     }
-    yield chunk;
 }
 
 // for example:
@@ -220,5 +220,7 @@ mod tests {
     #[tokio::test]
     async fn test_no_intersection() {
         sort_merge_test(vec![1, 2, 3], vec![4, 5, 6], vec![]).await;
+        sort_merge_test(vec![], vec![1, 2], vec![]).await;
+        sort_merge_test(vec![1, 2], vec![], vec![]).await;
     }
 }
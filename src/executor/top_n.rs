@@ -165,6 +165,7 @@ mod tests {
                     return_type: catalog[*idx].datatype(),
                 }),
                 descending: *desc,
+                nulls_first: *desc,
             })
             .collect()
     }
@@ -0,0 +1,20 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use super::*;
+use crate::array::DataChunk;
+
+/// The executor of `UNION ALL`, which outputs all rows of the left child and then the right child.
+pub struct UnionAllExecutor {
+    pub left_child: BoxedExecutor,
+    pub right_child: BoxedExecutor,
+}
+
+impl UnionAllExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecutorError)]
+    pub async fn execute(self) {
+        #[for_await]
+        for chunk in self.left_child.chain(self.right_child) {
+            yield chunk?;
+        }
+    }
+}
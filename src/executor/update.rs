@@ -0,0 +1,98 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::sync::Arc;
+
+use super::*;
+use crate::array::{ArrayImplValidExt, DataChunk};
+use crate::binder::BoundExpr;
+use crate::catalog::{ColumnCatalog, TableRefId};
+use crate::storage::{RowHandler, Storage, Table, Transaction};
+
+/// The executor of `update` statement.
+///
+/// The old rows are deleted by their row handlers and the new rows are appended within the same
+/// transaction, so the update is committed atomically.
+pub struct UpdateExecutor<S: Storage> {
+    pub context: Arc<Context>,
+    pub table_ref_id: TableRefId,
+    pub value_exprs: Vec<BoundExpr>,
+    pub storage: Arc<S>,
+    pub child: BoxedExecutor,
+}
+
+impl<S: Storage> UpdateExecutor<S> {
+    async fn execute_inner(self, token: CancellationToken) -> Result<i32, ExecutorError> {
+        let table = self.storage.get_table(self.table_ref_id)?;
+        let columns = table.columns()?;
+        let mut txn = table.update().await?;
+        let mut cnt = 0;
+        #[for_await]
+        for chunk in self.child {
+            let chunk = chunk?;
+            if chunk.cardinality() == 0 {
+                continue;
+            }
+            let row_handlers = chunk.array_at(chunk.column_count() - 1);
+            for row_handler_idx in 0..row_handlers.len() {
+                let row_handler = <S::TransactionType as Transaction>::RowHandlerType::from_column(
+                    row_handlers,
+                    row_handler_idx,
+                );
+                if let Err(err) = unified_select_with_token(&token, txn.delete(&row_handler)).await
+                {
+                    txn.abort().await?;
+                    return Err(err);
+                }
+            }
+
+            let new_chunk: Result<DataChunk, _> = self
+                .value_exprs
+                .iter()
+                .map(|expr| expr.eval(&chunk))
+                .try_collect();
+            let new_chunk = match new_chunk {
+                Ok(new_chunk) => new_chunk,
+                Err(err) => {
+                    txn.abort().await?;
+                    return Err(err.into());
+                }
+            };
+            // the expressions assigned to NOT NULL columns may still evaluate to nulls
+            if let Err(err) = check_not_null(&columns, &new_chunk) {
+                txn.abort().await?;
+                return Err(err);
+            }
+            if let Err(err) = unified_select_with_token(&token, txn.append(new_chunk)).await {
+                txn.abort().await?;
+                return Err(err);
+            }
+            cnt += chunk.cardinality();
+        }
+        txn.commit().await?;
+
+        Ok(cnt as i32)
+    }
+
+    #[try_stream(boxed, ok = DataChunk, error = ExecutorError)]
+    pub async fn execute(self) {
+        let context = self.context.clone();
+        match context.spawn(|token| async move { self.execute_inner(token).await }) {
+            Some(handler) => {
+                let cnt = handler.await.expect("failed to join update thread")?;
+                let chunk = DataChunk::single(cnt as i32);
+                yield chunk;
+            }
+            None => return Err(ExecutorError::Abort),
+        }
+    }
+}
+
+/// Returns an error if a column of `chunk` which is not nullable in `columns` has nulls.
+fn check_not_null(columns: &[ColumnCatalog], chunk: &DataChunk) -> Result<(), ExecutorError> {
+    for (column, array) in columns.iter().zip_eq(chunk.arrays()) {
+        if !column.is_nullable() && !array.get_valid_bitmap().all() {
+            return Err(ExecutorError::NotNullable);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,270 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use super::*;
+use crate::array::{ArrayBuilderImpl, ArrayImpl, DataChunk, RowRef};
+use crate::binder::{
+    BoundAggCall, BoundInputRef, BoundOrderBy, BoundWindowCall, BoundWindowFrame, WindowFuncKind,
+};
+use crate::parser::{WindowFrameBound, WindowFrameUnits};
+use crate::types::{DataTypeExt, DataTypeKind};
+
+/// The executor of window functions.
+///
+/// All rows of the child are collected. For each window call, the rows are sorted by the
+/// partition keys and the order keys, and the result of the call is computed partition by
+/// partition. The results are appended to the input chunks, and the rows keep their input order.
+pub struct WindowExecutor {
+    pub child: BoxedExecutor,
+    pub window_calls: Vec<BoundWindowCall>,
+    /// The number of output columns of the child plan. The columns of the child which are not in
+    /// the plan, such as the row handlers of a table scan, are not passed through.
+    pub child_column_count: usize,
+}
+
+impl WindowExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecutorError)]
+    pub async fn execute(self) {
+        // collect all chunks
+        let mut chunks = vec![];
+        #[for_await]
+        for batch in self.child {
+            chunks.push(batch?);
+        }
+        if chunks.is_empty() {
+            return Ok(());
+        }
+        let results: Vec<ArrayImpl> = self
+            .window_calls
+            .iter()
+            .map(|call| eval_window_call(call, &chunks))
+            .try_collect()?;
+
+        let mut offset = 0;
+        for chunk in chunks {
+            let range = offset..offset + chunk.cardinality();
+            offset = range.end;
+            yield chunk
+                .arrays()
+                .iter()
+                .take(self.child_column_count)
+                .cloned()
+                .chain(results.iter().map(|array| array.slice(range.clone())))
+                .collect::<DataChunk>();
+        }
+    }
+}
+
+/// Evaluate the window call on all rows, and return the results in input order.
+fn eval_window_call(
+    call: &BoundWindowCall,
+    chunks: &[DataChunk],
+) -> Result<ArrayImpl, ExecutorError> {
+    // Evaluate the partition keys, the order keys and the arguments into a single chunk, with
+    // the position of each row at the last column.
+    let exprs = call
+        .partition_by
+        .iter()
+        .chain(call.order_by.iter().map(|order| &order.expr))
+        .chain(&call.args);
+    let mut columns = vec![];
+    for expr in exprs {
+        let arrays: Vec<_> = chunks.iter().map(|chunk| expr.eval(chunk)).try_collect()?;
+        let mut builder = ArrayBuilderImpl::from_type_of_array(&arrays[0]);
+        for array in &arrays {
+            builder.append(array);
+        }
+        columns.push(builder.finish());
+    }
+    let cardinality: usize = chunks.iter().map(|chunk| chunk.cardinality()).sum();
+    columns.push(ArrayImpl::new_int32((0..cardinality as i32).collect()));
+    let keys: DataChunk = columns.into_iter().collect();
+
+    let partition_len = call.partition_by.len();
+    let arg_start = partition_len + call.order_by.len();
+    let position_index = keys.column_count() - 1;
+    // the comparators of the keys, which are columns of the chunk
+    let comparator =
+        |index: usize, expr: &BoundExpr, descending: bool, nulls_first: bool| BoundOrderBy {
+            expr: BoundExpr::InputRef(BoundInputRef {
+                index,
+                return_type: (expr.return_type())
+                    .unwrap_or_else(|| DataTypeKind::Int(None).nullable()),
+            }),
+            descending,
+            nulls_first,
+        };
+    let partition_comparators: Vec<_> = (call.partition_by.iter().enumerate())
+        .map(|(i, expr)| comparator(i, expr, false, false))
+        .collect();
+    let order_comparators: Vec<_> = (call.order_by.iter().enumerate())
+        .map(|(i, order)| {
+            comparator(
+                partition_len + i,
+                &order.expr,
+                order.descending,
+                order.nulls_first,
+            )
+        })
+        .collect();
+
+    // sort the rows by partition keys and order keys
+    let mut rows = gen_index_array(std::slice::from_ref(&keys));
+    rows.sort_by(|row1, row2| {
+        cmp(row1, row2, &partition_comparators).then_with(|| cmp(row1, row2, &order_comparators))
+    });
+
+    let mut outputs = vec![DataValue::Null; cardinality];
+    let mut start = 0;
+    while start < rows.len() {
+        let len = rows[start..]
+            .iter()
+            .take_while(|row| cmp(&rows[start], row, &partition_comparators) == Ordering::Equal)
+            .count();
+        let partition = &rows[start..start + len];
+        let values = eval_partition(call, &keys, partition, &order_comparators, arg_start)?;
+        for (row, value) in partition.iter().zip(values) {
+            let position = row.get(position_index).as_usize()?.unwrap();
+            outputs[position] = value;
+        }
+        start += len;
+    }
+
+    let mut builder = ArrayBuilderImpl::with_capacity(cardinality, &call.return_type);
+    for value in &outputs {
+        builder.push(value);
+    }
+    Ok(builder.finish())
+}
+
+/// Evaluate the window call on the sorted rows of a partition, where the arguments start from
+/// the column `arg_start` of `keys`.
+fn eval_partition(
+    call: &BoundWindowCall,
+    keys: &DataChunk,
+    rows: &[RowRef<'_>],
+    order_comparators: &[BoundOrderBy],
+    arg_start: usize,
+) -> Result<Vec<DataValue>, ExecutorError> {
+    // the range of peers, which are the rows equal in order keys, of each row
+    let mut peers = Vec::with_capacity(rows.len());
+    let mut start = 0;
+    while start < rows.len() {
+        let len = rows[start..]
+            .iter()
+            .take_while(|row| cmp(&rows[start], row, order_comparators) == Ordering::Equal)
+            .count();
+        peers.extend(std::iter::repeat(start..start + len).take(len));
+        start += len;
+    }
+
+    let arg = |row: &RowRef<'_>, i: usize| row.get(arg_start + i);
+    let values = match &call.kind {
+        WindowFuncKind::RowNumber => (1..=rows.len() as i32).map(DataValue::Int32).collect(),
+        WindowFuncKind::Rank => peers
+            .iter()
+            .map(|peer| DataValue::Int32(peer.start as i32 + 1))
+            .collect(),
+        WindowFuncKind::DenseRank => {
+            let mut rank = 0;
+            (peers.iter().enumerate())
+                .map(|(i, peer)| {
+                    if peer.start == i {
+                        rank += 1;
+                    }
+                    DataValue::Int32(rank)
+                })
+                .collect()
+        }
+        WindowFuncKind::Lag(offset) | WindowFuncKind::Lead(offset) => {
+            let is_lag = matches!(call.kind, WindowFuncKind::Lag(_));
+            (rows.iter().enumerate())
+                .map(|(i, row)| {
+                    let target = if is_lag {
+                        i.checked_sub(*offset)
+                    } else {
+                        Some(i + offset).filter(|&j| j < rows.len())
+                    };
+                    match target {
+                        Some(j) => arg(&rows[j], 0),
+                        None if call.args.len() > 1 => arg(row, 1),
+                        None => DataValue::Null,
+                    }
+                })
+                .collect()
+        }
+        WindowFuncKind::Agg(kind) => {
+            // `count(*)` has no argument, and the column of positions is counted instead
+            let mut builder = ArrayBuilderImpl::from_type_of_array(keys.array_at(arg_start));
+            for row in rows {
+                builder.push(&row.get(arg_start));
+            }
+            let array = builder.finish();
+            let agg_call = BoundAggCall {
+                kind: kind.clone(),
+                args: vec![],
+                return_type: call.return_type.clone(),
+                distinct: false,
+            };
+            eval_frames(&agg_call, &call.frame, &array, &peers)?
+        }
+    };
+    Ok(values)
+}
+
+/// Evaluate the aggregation on the frame of each row.
+///
+/// If the frame starts from the first row of the partition, the state is updated incrementally.
+/// Otherwise, the aggregation is computed from scratch for every row.
+fn eval_frames(
+    agg_call: &BoundAggCall,
+    frame: &BoundWindowFrame,
+    array: &ArrayImpl,
+    peers: &[Range<usize>],
+) -> Result<Vec<DataValue>, ExecutorError> {
+    let len = array.len();
+    let frame_of = |i: usize| -> Range<usize> {
+        let peer = &peers[i];
+        let start = match (&frame.units, &frame.start) {
+            (_, WindowFrameBound::Preceding(None)) => 0,
+            (_, WindowFrameBound::Preceding(Some(n))) => i.saturating_sub(*n as usize),
+            (WindowFrameUnits::Rows, WindowFrameBound::CurrentRow) => i,
+            (_, WindowFrameBound::CurrentRow) => peer.start,
+            (_, WindowFrameBound::Following(n)) => i + n.unwrap_or(0) as usize,
+        };
+        let end = match (&frame.units, &frame.end) {
+            (_, WindowFrameBound::Following(None)) => len,
+            (_, WindowFrameBound::Following(Some(n))) => i + *n as usize + 1,
+            (WindowFrameUnits::Rows, WindowFrameBound::CurrentRow) => i + 1,
+            (_, WindowFrameBound::CurrentRow) => peer.end,
+            (_, WindowFrameBound::Preceding(n)) => (i + 1).saturating_sub(n.unwrap_or(0) as usize),
+        };
+        start.min(len)..end.min(len)
+    };
+
+    let mut values = Vec::with_capacity(len);
+    if frame.start == WindowFrameBound::Preceding(None) {
+        let mut state = create_agg_state(agg_call);
+        let mut end = 0;
+        for i in 0..len {
+            let range = frame_of(i);
+            if range.end > end {
+                state.update(&array.slice(end..range.end))?;
+                end = range.end;
+            }
+            values.push(state.output());
+        }
+    } else {
+        for i in 0..len {
+            let range = frame_of(i);
+            let mut state = create_agg_state(agg_call);
+            if range.start < range.end {
+                state.update(&array.slice(range))?;
+            }
+            values.push(state.output());
+        }
+    }
+    Ok(values)
+}
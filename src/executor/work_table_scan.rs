@@ -0,0 +1,18 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use super::*;
+use crate::array::DataChunk;
+
+/// The executor of scanning the working table of a recursive CTE.
+pub struct WorkTableScanExecutor {
+    pub chunks: Arc<Vec<DataChunk>>,
+}
+
+impl WorkTableScanExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = ExecutorError)]
+    pub async fn execute(self) {
+        for chunk in self.chunks.iter() {
+            yield chunk.clone();
+        }
+    }
+}
@@ -1,10 +1,15 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use crate::array::*;
 use crate::types::PhysicalDataTypeKind;
 
 pub mod abs;
+pub mod substring;
 
 pub use self::abs::*;
-#[derive(thiserror::Error, Debug, PartialEq)]
+pub use self::substring::*;
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
 pub enum FunctionError {
     #[error("invalid parameters {0}")]
     InvalidParameters(String),
@@ -12,7 +17,7 @@ pub enum FunctionError {
     InvalidDataTypes(String),
 }
 // Definition of function.
-pub trait Function {
+pub trait Function: Send + Sync {
     // Each function should have an unique name.
     fn name(&self) -> &str;
     // A function could support mutiple kinds of data types.
@@ -25,3 +30,46 @@ pub trait Function {
     // The execution logic of function.
     fn execute(&self, input: &DataChunk) -> Result<DataChunk, FunctionError>;
 }
+
+/// A registry of scalar functions.
+///
+/// Functions are registered by name. A name may have several implementations, and the first one
+/// accepting the argument types is used.
+pub struct FunctionRegistry {
+    functions: HashMap<String, Vec<Arc<dyn Function>>>,
+}
+
+impl Default for FunctionRegistry {
+    /// Create a registry with all built-in functions.
+    fn default() -> Self {
+        let mut registry = FunctionRegistry {
+            functions: HashMap::new(),
+        };
+        registry.register(Arc::new(AbsFunction {}));
+        registry.register(Arc::new(SubstringFunction {}));
+        registry
+    }
+}
+
+impl FunctionRegistry {
+    /// Register a function under its name.
+    pub fn register(&mut self, function: Arc<dyn Function>) {
+        self.functions
+            .entry(function.name().to_lowercase())
+            .or_default()
+            .push(function);
+    }
+
+    /// Find the function named `name` which accepts `input_types`, and return it with its return
+    /// type.
+    pub fn resolve(
+        &self,
+        name: &str,
+        input_types: &[PhysicalDataTypeKind],
+    ) -> Option<(Arc<dyn Function>, PhysicalDataTypeKind)> {
+        self.functions.get(name)?.iter().find_map(|function| {
+            let return_type = function.return_types(input_types).ok()?;
+            Some((function.clone(), return_type))
+        })
+    }
+}
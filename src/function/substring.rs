@@ -0,0 +1,88 @@
+use super::*;
+use crate::array::ArrayImpl;
+use crate::function::FunctionError::{InvalidDataTypes, InvalidParameters};
+
+/// `substring(string, start [, count])`, which returns `count` characters starting from the
+/// `start`-th character (counting from 1), or all characters after it if `count` is omitted.
+pub struct SubstringFunction {}
+
+impl Function for SubstringFunction {
+    fn name(&self) -> &str {
+        "substring"
+    }
+
+    fn return_types(
+        &self,
+        input_types: &[PhysicalDataTypeKind],
+    ) -> Result<PhysicalDataTypeKind, FunctionError> {
+        match input_types {
+            [PhysicalDataTypeKind::String, PhysicalDataTypeKind::Int32]
+            | [PhysicalDataTypeKind::String, PhysicalDataTypeKind::Int32, PhysicalDataTypeKind::Int32] => {
+                Ok(PhysicalDataTypeKind::String)
+            }
+            _ => Err(InvalidDataTypes("Data type is not supported".to_string())),
+        }
+    }
+
+    fn execute(&self, input: &DataChunk) -> Result<DataChunk, FunctionError> {
+        let (strings, starts, counts) = match input.column_count() {
+            2 => (input.array_at(0), input.array_at(1), None),
+            3 => (
+                input.array_at(0),
+                input.array_at(1),
+                Some(input.array_at(2)),
+            ),
+            _ => {
+                return Err(InvalidParameters(
+                    "The column size should be 2 or 3".to_string(),
+                ))
+            }
+        };
+        let (strings, starts, counts) = match (strings, starts, counts) {
+            (ArrayImpl::Utf8(strings), ArrayImpl::Int32(starts), None) => (strings, starts, None),
+            (
+                ArrayImpl::Utf8(strings),
+                ArrayImpl::Int32(starts),
+                Some(ArrayImpl::Int32(counts)),
+            ) => (strings, starts, Some(counts)),
+            _ => return Err(InvalidDataTypes("Data type is not supported".to_string())),
+        };
+
+        let mut builder = Utf8ArrayBuilder::with_capacity(strings.len());
+        for i in 0..strings.len() {
+            let count = match counts {
+                Some(counts) => match counts.get(i) {
+                    Some(count) if *count < 0 => {
+                        return Err(InvalidParameters(
+                            "negative substring length not allowed".to_string(),
+                        ))
+                    }
+                    Some(count) => Some(*count as i64),
+                    None => {
+                        builder.push(None);
+                        continue;
+                    }
+                },
+                None => None,
+            };
+            match (strings.get(i), starts.get(i)) {
+                (Some(string), Some(start)) => {
+                    // the characters before the first one are counted in `count`
+                    let start = *start as i64;
+                    let end = count.map(|count| start + count);
+                    let skip = (start - 1).max(0) as usize;
+                    let take = match end {
+                        Some(end) => (end - 1 - skip as i64).max(0) as usize,
+                        None => usize::MAX,
+                    };
+                    let substring: String = string.chars().skip(skip).take(take).collect();
+                    builder.push(Some(&substring));
+                }
+                _ => builder.push(None),
+            }
+        }
+        let res_arr: ArrayImpl = ArrayImpl::from(builder.finish());
+        let vec = vec![res_arr];
+        Ok(vec.into_iter().collect())
+    }
+}
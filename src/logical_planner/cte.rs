@@ -0,0 +1,33 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+//! Logical planner of common table expressions (CTEs).
+//!
+//! Each reference to a CTE is planned to a [`LogicalCteScan`] over the plan of the CTE. The plan
+//! of a recursive CTE is a [`LogicalRecursiveUnion`] of its two terms, where the references in
+//! the recursive term are planned to [`LogicalWorkTableScan`]s.
+//!
+//! [`LogicalCteScan`]: crate::optimizer::plan_nodes::LogicalCteScan
+//! [`LogicalWorkTableScan`]: crate::optimizer::plan_nodes::LogicalWorkTableScan
+
+use super::*;
+use crate::binder::BoundCte;
+use crate::optimizer::plan_nodes::LogicalRecursiveUnion;
+
+impl LogicalPlaner {
+    /// Generate the logical plan of the query of a CTE.
+    pub(super) fn plan_cte(&self, cte: &BoundCte) -> Result<PlanRef, LogicalPlanError> {
+        let plan = self.plan_select(cte.query.clone())?;
+        match &cte.recursive_query {
+            Some(recursive_query) => {
+                let recursive_plan = self.plan_select(recursive_query.clone())?;
+                Ok(Arc::new(LogicalRecursiveUnion::new(
+                    cte.id,
+                    cte.union_all,
+                    plan,
+                    recursive_plan,
+                )))
+            }
+            None => Ok(plan),
+        }
+    }
+}
@@ -8,11 +8,15 @@ use crate::types::ConvertError;
 
 mod copy;
 mod create;
+mod cte;
 mod delete;
 mod drop;
 mod explain;
 mod insert;
 mod select;
+mod set_operation;
+mod subquery;
+mod update;
 
 pub use copy::*;
 pub use create::*;
@@ -20,6 +24,7 @@ pub use delete::*;
 pub use drop::*;
 pub use explain::*;
 pub use insert::*;
+pub use update::*;
 
 /// The error type of logical planner.
 #[derive(thiserror::Error, Debug, PartialEq)]
@@ -30,6 +35,14 @@ pub enum LogicalPlanError {
     Convert(#[from] ConvertError),
     #[error("{0} must appear in the GROUP BY clause or be used in an aggregate function")]
     IllegalGroupBySQL(String),
+    #[error("unsupported subquery: {0}")]
+    UnsupportedSubquery(String),
+    #[error("window functions are not allowed in {0}")]
+    IllegalWindowFunction(String),
+    #[error("for SELECT DISTINCT, ORDER BY expressions must appear in select list")]
+    IllegalDistinctOrderBy,
+    #[error("SELECT DISTINCT ON expressions must match initial ORDER BY expressions")]
+    IllegalDistinctOnOrderBy,
 }
 
 #[derive(Default)]
@@ -47,6 +60,7 @@ impl LogicalPlaner {
             Select(stmt) => self.plan_select(stmt),
             Explain(stmt) => self.plan_explain(*stmt),
             Delete(stmt) => self.plan_delete(*stmt),
+            Update(stmt) => self.plan_update(*stmt),
         }
     }
 }
@@ -5,19 +5,28 @@
 //! A `select` statement will be planned to a compose of:
 //!
 //! - [`LogicalTableScan`] (from *) or dummy plan (no from)
+//! - [`LogicalCteScan`] (with *)
+//! - [`LogicalSetOperation`](crate::optimizer::plan_nodes::LogicalSetOperation) (union *)
 //! - [`LogicalFilter`] (where *)
+//! - [`LogicalWindow`] (over *)
+//! - [`LogicalApply`](crate::optimizer::plan_nodes::LogicalApply) (subqueries)
 //! - [`LogicalProjection`] (select *)
+//! - [`LogicalAggregate`] (select distinct *)
 //! - [`LogicalOrder`] (order by *)
+//! - [`LogicalDistinctOn`] (select distinct on *)
 use itertools::Itertools;
 
+use super::subquery::contains_subquery;
 use super::*;
 use crate::binder::{
-    BoundAggCall, BoundExpr, BoundInputRef, BoundOrderBy, BoundSelect, BoundTableRef, ExprVisitor,
+    BoundAggCall, BoundExpr, BoundInputRef, BoundOrderBy, BoundSelect, BoundSubqueryKind,
+    BoundTableRef, BoundWindowCall, ExprVisitor,
 };
 use crate::optimizer::logical_plan_rewriter::ExprRewriter;
 use crate::optimizer::plan_nodes::{
-    Internal, LogicalAggregate, LogicalFilter, LogicalJoin, LogicalLimit, LogicalOrder,
-    LogicalProjection, LogicalTableScan, LogicalValues,
+    Internal, LogicalAggregate, LogicalCteScan, LogicalDistinctOn, LogicalFilter, LogicalJoin,
+    LogicalLimit, LogicalOrder, LogicalProjection, LogicalTableScan, LogicalValues, LogicalWindow,
+    LogicalWorkTableScan,
 };
 
 impl LogicalPlaner {
@@ -27,8 +36,9 @@ impl LogicalPlaner {
         let mut with_row_handler = false;
 
         if let Some(table_ref) = &stmt.from_table {
-            // use `sorted` mode from the storage engine if the order by column is the primary key
-            if stmt.orderby.len() == 1 && !stmt.orderby[0].descending {
+            // use `sorted` mode from the storage engine if the order by column is the primary key,
+            // unless the order is lost by the aggregation of `distinct`
+            if stmt.orderby.len() == 1 && !stmt.orderby[0].descending && !stmt.select_distinct {
                 if let BoundExpr::ColumnRef(col_ref) = &stmt.orderby[0].expr {
                     if col_ref.is_primary_key {
                         is_sorted = true;
@@ -52,6 +62,20 @@ impl LogicalPlaner {
             }
             plan = self.plan_table_ref(table_ref, with_row_handler, is_sorted)?;
         } else {
+            if stmt.select_list.iter().any(contains_subquery) {
+                return Err(LogicalPlanError::UnsupportedSubquery(
+                    "subqueries are not supported in SELECT without FROM".into(),
+                ));
+            }
+            if stmt
+                .select_list
+                .iter()
+                .any(|expr| expr.contains_window_call())
+            {
+                return Err(LogicalPlanError::IllegalWindowFunction(
+                    "SELECT without FROM".into(),
+                ));
+            }
             plan = Arc::new(LogicalValues::new(
                 stmt.select_list
                     .iter()
@@ -67,8 +91,18 @@ impl LogicalPlaner {
         }
 
         let alias_rewrite = AliasRewriter;
+        // window functions are computed after WHERE, GROUP BY and HAVING
+        for (clause, exprs) in [
+            ("WHERE", stmt.where_clause.iter().collect_vec()),
+            ("GROUP BY", stmt.group_by.iter().collect_vec()),
+            ("HAVING", stmt.having.iter().collect_vec()),
+        ] {
+            if exprs.iter().any(|expr| expr.contains_window_call()) {
+                return Err(LogicalPlanError::IllegalWindowFunction(clause.into()));
+            }
+        }
         if let Some(expr) = stmt.where_clause {
-            plan = Arc::new(LogicalFilter::new(expr, plan));
+            plan = self.plan_filter(plan, expr)?;
         }
 
         let mut agg_extractor = AggExtractor::new();
@@ -99,16 +133,41 @@ impl LogicalPlaner {
             // In SQL: `select a from t order by b;`
             // A column(b) expression will be created to ensure that the above operators get the
             // correct binding
-            if !stmt.select_list.iter().any(|expr| {
-                if let BoundExpr::ExprWithAlias(alias) = expr {
-                    (*alias.expr) == node.expr
-                } else {
-                    expr == &node.expr
-                }
-            }) {
+            if !select_list_contains(&stmt.select_list, &node.expr) {
                 stmt.select_list.push(node.expr.clone());
             }
         }
+        if stmt.select_distinct && column_count != stmt.select_list.len() {
+            return Err(LogicalPlanError::IllegalDistinctOrderBy);
+        }
+
+        // The rows are sorted by the `DISTINCT ON` expressions first, so that the rows with the
+        // same values are adjacent. Those not in the order-by clause are appended to it.
+        for expr in &mut stmt.distinct_on {
+            agg_extractor.visit_having_expr(expr);
+            alias_rewrite.rewrite_expr(expr);
+        }
+        if !stmt.distinct_on.is_empty() {
+            let prefix_len = stmt.orderby.len().min(stmt.distinct_on.len());
+            if !stmt.orderby[..prefix_len]
+                .iter()
+                .all(|node| stmt.distinct_on.contains(&node.expr))
+            {
+                return Err(LogicalPlanError::IllegalDistinctOnOrderBy);
+            }
+            for expr in &stmt.distinct_on {
+                if !stmt.orderby.iter().any(|node| &node.expr == expr) {
+                    stmt.orderby.push(BoundOrderBy {
+                        expr: expr.clone(),
+                        descending: false,
+                        nulls_first: false,
+                    });
+                }
+                if !select_list_contains(&stmt.select_list, expr) {
+                    stmt.select_list.push(expr.clone());
+                }
+            }
+        }
 
         if !stmt.group_by.is_empty() || agg_extractor.has_aggregate() || stmt.having.is_some() {
             agg_extractor.validate_illegal_column(&stmt.select_list, &stmt.orderby)?;
@@ -128,24 +187,72 @@ impl LogicalPlaner {
             ));
         }
 
-        if stmt.having.is_some() {
-            plan = Arc::new(LogicalFilter::new(stmt.having.unwrap(), plan));
+        if let Some(having) = stmt.having {
+            plan = self.plan_filter(plan, having)?;
         }
 
-        let comparators = stmt.orderby;
+        // Window functions are computed after aggregation. Their results are appended to the
+        // output of the child, and referred by the projection.
+        let window_calls = extract_window_calls(&stmt.select_list);
+        if !window_calls.is_empty() {
+            plan = Arc::new(LogicalWindow::new(window_calls, plan));
+        }
+
+        // Scalar subqueries in the select list are joined before the projection. Since order-by
+        // expressions are resolved against the output of the projection, those containing
+        // subqueries are rewritten to refer to the corresponding select items.
+        let orderby_positions = stmt
+            .orderby
+            .iter()
+            .map(|node| {
+                if !contains_subquery(&node.expr) {
+                    return None;
+                }
+                stmt.select_list.iter().position(|expr| match expr {
+                    BoundExpr::ExprWithAlias(alias) => *alias.expr == node.expr,
+                    _ => *expr == node.expr,
+                })
+            })
+            .collect_vec();
+        plan = self.plan_scalar_subqueries(plan, stmt.select_list.iter_mut())?;
+        for (node, position) in stmt.orderby.iter_mut().zip(orderby_positions) {
+            if let Some(index) = position {
+                node.expr = BoundExpr::InputRef(BoundInputRef {
+                    index,
+                    return_type: node.expr.return_type().unwrap(),
+                });
+            }
+        }
 
-        // TODO: support the following clauses
-        assert!(!stmt.select_distinct, "TODO: plan distinct");
+        let comparators = stmt.orderby;
 
         let need_addtional_projection = column_count != stmt.select_list.len();
+        // `distinct` is planned as an aggregation grouping by all output columns
+        let mut distinct_keys = vec![];
+        if stmt.select_distinct {
+            distinct_keys = stmt
+                .select_list
+                .iter()
+                .map(|expr| match expr {
+                    BoundExpr::ExprWithAlias(alias) => (*alias.expr).clone(),
+                    _ => expr.clone(),
+                })
+                .collect_vec();
+        }
         let mut project = None;
         if !stmt.select_list.is_empty() {
             plan = Arc::new(LogicalProjection::new(stmt.select_list, plan));
             project = Some(plan.clone());
         }
+        if !distinct_keys.is_empty() {
+            plan = Arc::new(LogicalAggregate::new(vec![], distinct_keys, plan));
+        }
         if !comparators.is_empty() && !is_sorted {
             plan = Arc::new(LogicalOrder::new(comparators, plan));
         }
+        if !stmt.distinct_on.is_empty() {
+            plan = Arc::new(LogicalDistinctOn::new(stmt.distinct_on, plan));
+        }
         if stmt.limit.is_some() || stmt.offset.is_some() {
             let limit = match stmt.limit {
                 Some(limit) => match limit {
@@ -172,11 +279,18 @@ impl LogicalPlaner {
         if need_addtional_projection {
             let project = project.unwrap();
             let projection = project.as_logical_projection().unwrap();
-            let mut projection_list = Vec::with_capacity(column_count);
-            let project_expressions = projection.project_expressions();
-            for item in project_expressions.iter().take(column_count) {
-                projection_list.push(item.clone());
-            }
+            let projection_list = projection
+                .project_expressions()
+                .iter()
+                .take(column_count)
+                .enumerate()
+                .map(|(index, item)| {
+                    BoundExpr::InputRef(BoundInputRef {
+                        index,
+                        return_type: item.return_type().unwrap(),
+                    })
+                })
+                .collect();
             plan = Arc::new(LogicalProjection::new(projection_list, plan));
         }
         Ok(plan)
@@ -231,6 +345,30 @@ impl LogicalPlaner {
                 }
                 Ok(plan)
             }
+            BoundTableRef::CteRef { ref_id, cte, .. } => {
+                let plan = self.plan_cte(cte)?;
+                Ok(Arc::new(LogicalCteScan::new(
+                    cte.id,
+                    *ref_id,
+                    cte.column_descs.clone(),
+                    plan,
+                )))
+            }
+            BoundTableRef::WorkTableRef {
+                ref_id,
+                cte_id,
+                column_descs,
+                ..
+            } => Ok(Arc::new(LogicalWorkTableScan::new(
+                *cte_id,
+                *ref_id,
+                column_descs.clone(),
+            ))),
+            BoundTableRef::SetOperationRef {
+                ref_id,
+                set_operation,
+                ..
+            } => self.plan_set_operation(*ref_id, set_operation),
         }
     }
 }
@@ -272,7 +410,43 @@ impl AggExtractor {
                 self.validate_illegal_column_inner(&e.expr)?;
             }
             IsNull(isnull) => self.validate_illegal_column_inner(&isnull.expr)?,
-            AggCall(_) | Constant(_) | InputRef(_) | Alias(_) => {}
+            Subquery(subquery) => {
+                if let BoundSubqueryKind::In(expr) = &subquery.kind {
+                    self.validate_illegal_column_inner(expr)?;
+                }
+            }
+            Case(case) => {
+                for (condition, result) in &case.branches {
+                    self.validate_illegal_column_inner(condition)?;
+                    self.validate_illegal_column_inner(result)?;
+                }
+                if let Some(else_result) = &case.else_result {
+                    self.validate_illegal_column_inner(else_result)?;
+                }
+            }
+            InList(in_list) => {
+                self.validate_illegal_column_inner(&in_list.expr)?;
+                for item in &in_list.list {
+                    self.validate_illegal_column_inner(item)?;
+                }
+            }
+            FunctionCall(function_call) => {
+                for arg in &function_call.args {
+                    self.validate_illegal_column_inner(arg)?;
+                }
+            }
+            WindowCall(window_call) => {
+                for arg in &window_call.args {
+                    self.validate_illegal_column_inner(arg)?;
+                }
+                for expr in &window_call.partition_by {
+                    self.validate_illegal_column_inner(expr)?;
+                }
+                for order in &window_call.order_by {
+                    self.validate_illegal_column_inner(&order.expr)?;
+                }
+            }
+            AggCall(_) | Constant(_) | InputRef(_) | Alias(_) | CorrelatedRef(_) => {}
             ColumnRef(_) => {
                 return Err(LogicalPlanError::IllegalGroupBySQL(format!(r#"{}"#, expr)));
             }
@@ -355,6 +529,31 @@ impl AggExtractor {
     }
 }
 
+/// Returns true if the expression is an item of the select list, with or without an alias.
+fn select_list_contains(select_list: &[BoundExpr], expr: &BoundExpr) -> bool {
+    select_list.iter().any(|item| match item {
+        BoundExpr::ExprWithAlias(alias) => *alias.expr == *expr,
+        _ => item == expr,
+    })
+}
+
+/// Extracts distinct window calls in the select list.
+fn extract_window_calls(select_list: &[BoundExpr]) -> Vec<BoundWindowCall> {
+    struct Visitor(Vec<BoundWindowCall>);
+    impl ExprVisitor for Visitor {
+        fn visit_window_call(&mut self, expr: &BoundWindowCall) {
+            if !self.0.contains(expr) {
+                self.0.push(expr.clone());
+            }
+        }
+    }
+    let mut visitor = Visitor(vec![]);
+    for expr in select_list {
+        visitor.visit_expr(expr);
+    }
+    visitor.0
+}
+
 /// Alias rewriter rewrites alias expressions into actual expressions
 struct AliasRewriter;
 
@@ -414,6 +613,7 @@ impl<'a> AliasExtractor<'a> {
                 BoundOrderBy {
                     expr: input_ref,
                     descending: expr.descending,
+                    nulls_first: expr.nulls_first,
                 }
             }
             ColumnRef(_) => expr,
@@ -485,6 +685,7 @@ mod tests {
             kind: AggKind::Count,
             args: vec![],
             return_type: DataTypeKind::Int(None).not_null(),
+            distinct: false,
         });
         let v2_puls_2_plus_count = BoundExpr::BinaryOp(BoundBinaryOp {
             op: BinaryOperator::Plus,
@@ -506,6 +707,7 @@ mod tests {
         let order_by_v1 = BoundOrderBy {
             expr: v1,
             descending: false,
+            nulls_first: false,
         };
         assert!(
             validate_illegal_column(&mut [v2_plus_count_wildcard], &mut [v2], &[order_by_v1])
@@ -0,0 +1,31 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+//! Logical planner of set operations.
+//!
+//! A set operation is planned to a [`LogicalSetOperation`] over the plans of its two queries,
+//! whose output is referred to as a virtual table by the enclosing `select`.
+
+use super::*;
+use crate::binder::BoundSetOperation;
+use crate::catalog::TableRefId;
+use crate::optimizer::plan_nodes::LogicalSetOperation;
+
+impl LogicalPlaner {
+    /// Generate the logical plan of a set operation, whose output is the virtual table `ref_id`.
+    pub(super) fn plan_set_operation(
+        &self,
+        ref_id: TableRefId,
+        set_operation: &BoundSetOperation,
+    ) -> Result<PlanRef, LogicalPlanError> {
+        let left = self.plan_select(set_operation.left.clone())?;
+        let right = self.plan_select(set_operation.right.clone())?;
+        Ok(Arc::new(LogicalSetOperation::new(
+            set_operation.op.clone(),
+            set_operation.all,
+            ref_id,
+            set_operation.column_descs.clone(),
+            left,
+            right,
+        )))
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+//! Logical planner of subqueries.
+//!
+//! Subqueries are planned as applies between the outer plan and the plan of the subquery, which
+//! will be turned into joins by the optimizer:
+//!
+//! - `[NOT] EXISTS` and `[NOT] IN` which appear as conjuncts of a filter condition are planned as
+//!   semi applies (or anti applies if negated).
+//! - Scalar subqueries are planned as single applies, and the subquery expression is replaced with
+//!   a reference to the joined column.
+//!
+//! The conjuncts of a filter without subqueries are planned below the applies, so that they can
+//! be pushed down to the joins in the outer query.
+
+use super::*;
+use crate::binder::{
+    BoundBinaryOp, BoundExpr, BoundInputRef, BoundIsNull, BoundJoinOperator, BoundSubquery,
+    BoundSubqueryKind, BoundUnaryOp, ExprVisitor,
+};
+use crate::optimizer::expr_utils::{conjunctions, merge_conjunctions};
+use crate::optimizer::logical_plan_rewriter::ExprRewriter;
+use crate::optimizer::plan_nodes::{LogicalApply, LogicalFilter};
+use crate::parser::{BinaryOperator, UnaryOperator};
+use crate::types::{DataTypeExt, DataTypeKind, DataValue};
+
+impl LogicalPlaner {
+    /// Plan a filter on `plan` whose condition may contain subqueries.
+    pub(super) fn plan_filter(
+        &self,
+        mut plan: PlanRef,
+        expr: BoundExpr,
+    ) -> Result<PlanRef, LogicalPlanError> {
+        if !contains_subquery(&expr) {
+            return Ok(Arc::new(LogicalFilter::new(expr, plan)));
+        }
+        // filter by the conditions without subqueries first, so that they can be pushed down
+        let (subquery_conds, conds): (Vec<_>, Vec<_>) =
+            conjunctions(expr).into_iter().partition(contains_subquery);
+        if !conds.is_empty() {
+            plan = Arc::new(LogicalFilter::new(
+                merge_conjunctions(conds.into_iter()),
+                plan,
+            ));
+        }
+        let mut conds = vec![];
+        for cond in subquery_conds {
+            match cond {
+                BoundExpr::Subquery(subquery) if is_predicate(&subquery) => {
+                    plan = self.plan_semi_join(plan, subquery)?;
+                }
+                BoundExpr::UnaryOp(BoundUnaryOp {
+                    op: UnaryOperator::Not,
+                    expr,
+                    ..
+                }) if matches!(&*expr, BoundExpr::Subquery(subquery) if is_predicate(subquery)) => {
+                    let mut subquery = match *expr {
+                        BoundExpr::Subquery(subquery) => subquery,
+                        _ => unreachable!(),
+                    };
+                    subquery.negated = !subquery.negated;
+                    plan = self.plan_semi_join(plan, subquery)?;
+                }
+                mut cond => {
+                    plan = self.plan_scalar_subqueries(plan, [&mut cond])?;
+                    conds.push(cond);
+                }
+            }
+        }
+        if !conds.is_empty() {
+            plan = Arc::new(LogicalFilter::new(
+                merge_conjunctions(conds.into_iter()),
+                plan,
+            ));
+        }
+        Ok(plan)
+    }
+
+    /// Plan `[NOT] EXISTS` or `[NOT] IN` subquery as a semi (anti) apply.
+    fn plan_semi_join(
+        &self,
+        plan: PlanRef,
+        subquery: BoundSubquery,
+    ) -> Result<PlanRef, LogicalPlanError> {
+        let left_col_num = plan.out_types().len();
+        let right = self.plan_select(subquery.query)?;
+        let join_op = if subquery.negated {
+            BoundJoinOperator::LeftAnti
+        } else {
+            BoundJoinOperator::LeftSemi
+        };
+        let condition = match subquery.kind {
+            BoundSubqueryKind::Exists => BoundExpr::Constant(DataValue::Bool(true)),
+            BoundSubqueryKind::In(expr) => {
+                let item = BoundExpr::InputRef(BoundInputRef {
+                    index: left_col_num,
+                    return_type: right.out_types()[0].clone(),
+                });
+                let eq = binary_op(BinaryOperator::Eq, (*expr).clone(), item.clone());
+                if subquery.negated {
+                    // `x NOT IN (...)` is not true if `x` is null or there is any null in the
+                    // subquery, so these rows are also treated as matched by the anti join.
+                    let is_null = |expr: BoundExpr| {
+                        BoundExpr::IsNull(BoundIsNull {
+                            expr: Box::new(expr),
+                        })
+                    };
+                    binary_op(
+                        BinaryOperator::Or,
+                        binary_op(BinaryOperator::Or, eq, is_null(*expr)),
+                        is_null(item),
+                    )
+                } else {
+                    eq
+                }
+            }
+            BoundSubqueryKind::Scalar => unreachable!(),
+        };
+        Ok(Arc::new(LogicalApply::new(plan, right, join_op, condition)))
+    }
+
+    /// Join all scalar subqueries in `exprs` with `plan` by single applies, and replace them with
+    /// references to the joined columns.
+    pub(super) fn plan_scalar_subqueries<'a>(
+        &self,
+        mut plan: PlanRef,
+        exprs: impl IntoIterator<Item = &'a mut BoundExpr>,
+    ) -> Result<PlanRef, LogicalPlanError> {
+        let mut exprs = exprs.into_iter().collect::<Vec<_>>();
+        let mut collector = SubqueryCollector::default();
+        for expr in &exprs {
+            collector.visit_expr(expr);
+        }
+        if collector.has_predicate {
+            return Err(LogicalPlanError::UnsupportedSubquery(
+                "EXISTS and IN subqueries are only supported as conjuncts of a filter".into(),
+            ));
+        }
+
+        let mut replacer = SubqueryReplacer::default();
+        for subquery in collector.subqueries {
+            let index = plan.out_types().len();
+            let right = self.plan_select(subquery.query.clone())?;
+            let input_ref = BoundExpr::InputRef(BoundInputRef {
+                index,
+                return_type: subquery.return_type.clone(),
+            });
+            plan = Arc::new(LogicalApply::new(
+                plan,
+                right,
+                BoundJoinOperator::LeftSingle,
+                BoundExpr::Constant(DataValue::Bool(true)),
+            ));
+            replacer.mapping.push((subquery, input_ref));
+        }
+        for expr in &mut exprs {
+            replacer.rewrite_expr(expr);
+        }
+        Ok(plan)
+    }
+}
+
+fn binary_op(op: BinaryOperator, left: BoundExpr, right: BoundExpr) -> BoundExpr {
+    BoundExpr::BinaryOp(BoundBinaryOp {
+        op,
+        left_expr: Box::new(left),
+        right_expr: Box::new(right),
+        return_type: Some(DataTypeKind::Boolean.nullable()),
+    })
+}
+
+/// Returns whether the subquery is `EXISTS` or `IN`.
+fn is_predicate(subquery: &BoundSubquery) -> bool {
+    subquery.kind != BoundSubqueryKind::Scalar
+}
+
+/// Returns whether the expression contains any subquery.
+pub(super) fn contains_subquery(expr: &BoundExpr) -> bool {
+    struct Visitor(bool);
+    impl ExprVisitor for Visitor {
+        fn visit_subquery(&mut self, _: &BoundSubquery) {
+            self.0 = true;
+        }
+    }
+    let mut visitor = Visitor(false);
+    visitor.visit_expr(expr);
+    visitor.0
+}
+
+/// Collects distinct scalar subqueries in expressions.
+#[derive(Default)]
+struct SubqueryCollector {
+    subqueries: Vec<BoundSubquery>,
+    /// Whether there is any `EXISTS` or `IN` subquery.
+    has_predicate: bool,
+}
+
+impl ExprVisitor for SubqueryCollector {
+    fn visit_subquery(&mut self, expr: &BoundSubquery) {
+        if is_predicate(expr) {
+            self.has_predicate = true;
+        } else if !self.subqueries.contains(expr) {
+            self.subqueries.push(expr.clone());
+        }
+    }
+}
+
+/// Replaces subqueries with the given expressions.
+#[derive(Default)]
+struct SubqueryReplacer {
+    mapping: Vec<(BoundSubquery, BoundExpr)>,
+}
+
+impl ExprRewriter for SubqueryReplacer {
+    fn rewrite_subquery(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::Subquery(subquery) => {
+                if let Some((_, new_expr)) = self.mapping.iter().find(|(s, _)| s == subquery) {
+                    *expr = new_expr.clone();
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
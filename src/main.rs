@@ -45,6 +45,10 @@ struct Args {
     /// Whether to use minitrace
     #[clap(long)]
     enable_tracing: bool,
+
+    /// Whether to use the memo-based optimizer
+    #[clap(long)]
+    enable_cascades: bool,
 }
 
 // human-readable message
@@ -322,13 +326,14 @@ async fn main() -> Result<()> {
         .with(fmt_layer)
         .init();
 
-    let db = if args.memory {
+    let mut db = if args.memory {
         info!("using memory engine");
         Database::new_in_memory()
     } else {
         info!("using Secondary engine");
         Database::new_on_disk(SecondaryStorageOptions::default_for_cli()).await
     };
+    db.set_enable_cascades(args.enable_cascades);
 
     if let Some(file) = args.file {
         if file.ends_with(".sql") {
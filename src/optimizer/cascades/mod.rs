@@ -0,0 +1,336 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use itertools::Itertools;
+
+use super::logical_plan_rewriter::{PhysicalConverter, PlanRewriter};
+use super::plan_nodes::{Dummy, PlanNodeType, PlanRef};
+use super::rules::{BoxedImplementationRule, BoxedRule};
+
+/// The index of a group in the memo.
+type GroupId = usize;
+
+/// A memo-based optimizer in the style of Cascades.
+///
+/// The plan is inserted into a memo, where each group holds the equivalent logical expressions,
+/// whose children are groups. The rules are applied on every expression, with its children bound
+/// to each expression of the child groups, until no rule can be applied on a new binding. So a
+/// rule can be applied on the results of other rules, regardless of the order of the rules.
+///
+/// Then the implementation rules turn the expressions of each group into physical plans on the
+/// best plans of the child groups, and the one with the lowest estimated cost is chosen. The
+/// children are not required to provide any ordering, so sort merge joins and sort aggregations
+/// are only chosen if the best plans of the children are sorted.
+pub struct CascadesOptimizer {
+    pub rules: Vec<BoxedRule>,
+    pub implementations: Vec<BoxedImplementationRule>,
+}
+
+impl CascadesOptimizer {
+    /// Search the best physical plan of the logical plan.
+    pub fn optimize(&self, plan: PlanRef) -> PlanRef {
+        let mut memo = Memo::default();
+        let root = memo.insert(plan.clone(), None);
+        self.explore(&mut memo);
+        let mut best = vec![None; memo.groups.len()];
+        let mut converter = PhysicalConverter::new(&plan);
+        self.implement(&memo, root, &mut best, &mut converter).plan
+    }
+
+    /// Apply the rules on all bindings of the expressions in the memo.
+    fn explore(&self, memo: &mut Memo) {
+        // the group, the expression, the rule and the expressions of the children of each
+        // applied binding
+        let mut applied = HashSet::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for group in 0..memo.groups.len() {
+                let mut index = 0;
+                while index < memo.groups[group].exprs.len() {
+                    for (children, binding) in memo.bindings(group, index) {
+                        for (rule_index, rule) in self.rules.iter().enumerate() {
+                            if !applied.insert((group, index, rule_index, children.clone())) {
+                                continue;
+                            }
+                            changed = true;
+                            if let Ok(plan) = rule.apply(binding.clone()) {
+                                memo.insert(plan, Some(group));
+                            }
+                        }
+                    }
+                    index += 1;
+                }
+            }
+        }
+    }
+
+    /// Find the physical plan with the lowest cost of the group.
+    fn implement(
+        &self,
+        memo: &Memo,
+        group: GroupId,
+        best: &mut [Option<Best>],
+        converter: &mut PhysicalConverter,
+    ) -> Best {
+        if let Some(best) = &best[group] {
+            return best.clone();
+        }
+        let rows = memo.plan(group).estimated_cardinality() as f64;
+        let mut result: Option<Best> = None;
+        for expr in &memo.groups[group].exprs {
+            let children = (expr.children.iter())
+                .map(|child| self.implement(memo, *child, best, converter))
+                .collect_vec();
+            let plan = (expr.plan).clone_with_children(
+                &children
+                    .iter()
+                    .map(|child| child.plan.clone())
+                    .collect_vec(),
+            );
+            let mut candidates = (self.implementations.iter())
+                .flat_map(|rule| rule.implement(&plan))
+                .map(|plan| {
+                    let (cost, _) = cost_of(&plan, &children, rows);
+                    (plan, cost)
+                })
+                .collect_vec();
+            if candidates.is_empty() {
+                // The converter rewrites the physical children again, so the cost is computed on
+                // the best plans of the children.
+                let physical = converter.rewrite(plan);
+                let input_rows = children.iter().map(|child| child.rows).collect_vec();
+                let cost = children.iter().map(|child| child.cost).sum::<f64>()
+                    + operator_cost(&physical, &input_rows, rows);
+                candidates.push((physical, cost));
+            }
+            for (plan, cost) in candidates {
+                if result.as_ref().map_or(true, |best| cost < best.cost) {
+                    result = Some(Best { plan, cost, rows });
+                }
+            }
+        }
+        best[group] = result.clone();
+        result.unwrap()
+    }
+}
+
+/// The best physical plan of a group.
+#[derive(Clone)]
+struct Best {
+    plan: PlanRef,
+    cost: f64,
+    rows: f64,
+}
+
+/// The estimated cost and rows of the physical plan, whose leaves are the best plans of the child
+/// groups. The plan nodes above the children are assumed to output the rows of the group.
+fn cost_of(plan: &PlanRef, children: &[Best], rows: f64) -> (f64, f64) {
+    let address = |plan: &PlanRef| Arc::as_ptr(plan) as *const ();
+    if let Some(child) = (children.iter()).find(|child| address(&child.plan) == address(plan)) {
+        return (child.cost, child.rows);
+    }
+    let inputs = (plan.children().iter())
+        .map(|child| cost_of(child, children, rows))
+        .collect_vec();
+    let input_rows = inputs.iter().map(|(_, rows)| *rows).collect_vec();
+    let cost =
+        inputs.iter().map(|(cost, _)| *cost).sum::<f64>() + operator_cost(plan, &input_rows, rows);
+    (cost, rows)
+}
+
+/// The estimated cost of the physical plan node, excluding its children.
+fn operator_cost(plan: &PlanRef, input_rows: &[f64], rows: f64) -> f64 {
+    let input = input_rows.iter().sum::<f64>();
+    match plan.node_type() {
+        // the hash table is built on the left side
+        PlanNodeType::PhysicalHashJoin => 2.0 * input_rows[0] + input_rows[1] + rows,
+        PlanNodeType::PhysicalNestedLoopJoin => input_rows[0] * input_rows[1] + rows,
+        PlanNodeType::PhysicalHashAgg => 2.0 * input + rows,
+        PlanNodeType::PhysicalOrder => input * input.max(2.0).log2() + rows,
+        // only the indexes of the table are read
+        PlanNodeType::PhysicalMetadataScan => 0.0,
+        _ => input + rows,
+    }
+}
+
+/// The groups of equivalent logical expressions.
+#[derive(Default)]
+struct Memo {
+    groups: Vec<Group>,
+    /// The group of each expression in the memo.
+    group_of_exprs: HashMap<ExprKey, GroupId>,
+}
+
+/// The identity of a logical expression: the type and the serialized content of the plan node
+/// with its children replaced by placeholders, and the groups of the children.
+type ExprKey = (PlanNodeType, String, Vec<GroupId>);
+
+#[derive(Default)]
+struct Group {
+    exprs: Vec<Expr>,
+}
+
+/// A logical expression in a group.
+struct Expr {
+    /// The plan of the expression, whose children are plans in the child groups.
+    plan: PlanRef,
+    children: Vec<GroupId>,
+}
+
+impl Memo {
+    /// Insert the plan into the group, or into a new group if `group` is `None`. The children of
+    /// the plan which are not in the memo are inserted into new groups. Returns the group of the
+    /// plan.
+    fn insert(&mut self, plan: PlanRef, group: Option<GroupId>) -> GroupId {
+        let children = (plan.children().into_iter())
+            .map(|child| self.insert(child, None))
+            .collect_vec();
+        let key = Self::key_of(&plan, children.clone());
+        if let Some(group) = self.group_of_exprs.get(&key) {
+            return *group;
+        }
+        let group = match group {
+            // the plan can not be a child of itself
+            Some(group) if children.contains(&group) => return group,
+            Some(group) => group,
+            None => {
+                self.groups.push(Group::default());
+                self.groups.len() - 1
+            }
+        };
+        self.groups[group].exprs.push(Expr { plan, children });
+        self.group_of_exprs.insert(key, group);
+        group
+    }
+
+    fn key_of(plan: &PlanRef, children: Vec<GroupId>) -> ExprKey {
+        let placeholders = vec![Arc::new(Dummy {}) as PlanRef; children.len()];
+        let node = plan.clone_with_children(&placeholders);
+        let content = serde_json::to_string(&node).expect("failed to serialize the plan");
+        (plan.node_type(), content, children)
+    }
+
+    /// A logical plan of the group.
+    fn plan(&self, group: GroupId) -> &PlanRef {
+        &self.groups[group].exprs[0].plan
+    }
+
+    /// Bind the children of the expression to each combination of the expressions in the child
+    /// groups. Returns the indexes of the expressions of the children, and the bound plan.
+    fn bindings(&self, group: GroupId, index: usize) -> Vec<(Vec<usize>, PlanRef)> {
+        let expr = &self.groups[group].exprs[index];
+        if expr.children.is_empty() {
+            return vec![(vec![], expr.plan.clone())];
+        }
+        (expr.children.iter())
+            .map(|child| 0..self.groups[*child].exprs.len())
+            .multi_cartesian_product()
+            .map(|indexes| {
+                let children = (expr.children.iter().zip(&indexes))
+                    .map(|(child, index)| self.groups[*child].exprs[*index].plan.clone())
+                    .collect_vec();
+                (indexes, expr.plan.clone_with_children(&children))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binder::{BoundExpr, BoundJoinOperator};
+    use crate::optimizer::plan_nodes::*;
+    use crate::optimizer::rules::*;
+    use crate::optimizer::test_utils::*;
+    use crate::types::DataValue;
+
+    #[test]
+    /// Optimize
+    /// ```text
+    /// Filter(cond: a.x = b.x)
+    ///   Join(cond: true)
+    ///     TableScan(a: x)
+    ///     TableScan(b: x)
+    /// ```
+    /// where `a` has 1000 rows and `b` has 10 rows, into
+    /// ```text
+    /// Projection(a.x, b.x)
+    ///   HashJoin(cond: b.x = a.x)
+    ///     TableScan(b: x)
+    ///     TableScan(a: x)
+    /// ```
+    fn test_cascades_optimizer() {
+        let statistics = statistics(&[(0, 1000, &[1000]), (1, 10, &[10])]);
+        let join = LogicalJoin::create(
+            scan(0, &["a.x"]),
+            scan(1, &["b.x"]),
+            BoundJoinOperator::Inner,
+            BoundExpr::Constant(DataValue::Bool(true)),
+        );
+        let filter = LogicalFilter::new(eq(0, 1), join.into_plan_ref());
+        let rules: Vec<BoxedRule> = vec![Box::new(FilterJoinRule {})];
+        let implementations: Vec<BoxedImplementationRule> = vec![
+            Box::new(JoinImplementationRule {}),
+            Box::new(AggImplementationRule {}),
+        ];
+        let cascades_optimizer = CascadesOptimizer {
+            rules,
+            implementations,
+        };
+        let plan = cascades_optimizer.optimize(statistics.annotate(filter.into_plan_ref()));
+
+        let projection = plan.as_physical_projection().unwrap();
+        let join = projection.child();
+        let join = join.as_physical_hash_join().unwrap();
+        let table_id = |plan: PlanRef| {
+            let scan = plan.as_physical_table_scan().unwrap();
+            scan.logical().table_ref_id()
+        };
+        assert_eq!(table_id(join.left()), table_ref_id(1));
+        assert_eq!(table_id(join.right()), table_ref_id(0));
+    }
+
+    #[test]
+    /// Implement `Join(cond: a.x = b.x)` of two tables with one row each by a nested loop join,
+    /// which is cheaper than the hash join chosen by the heuristic converter on the equal keys.
+    fn test_cascades_implementation() {
+        let statistics = statistics(&[(0, 1, &[1]), (1, 1, &[1])]);
+        let join = LogicalJoin::create(
+            scan(0, &["a.x"]),
+            scan(1, &["b.x"]),
+            BoundJoinOperator::Inner,
+            eq(0, 1),
+        );
+        let plan = statistics.annotate(join.into_plan_ref());
+
+        let heuristic = PhysicalConverter::new(&plan).rewrite(plan.clone());
+        assert!(heuristic.as_physical_hash_join().is_ok());
+
+        let cascades_optimizer = CascadesOptimizer {
+            rules: vec![],
+            implementations: vec![Box::new(JoinImplementationRule {})],
+        };
+        let plan = cascades_optimizer.optimize(plan);
+        assert!(plan.as_physical_nested_loop_join().is_ok());
+    }
+
+    #[test]
+    fn test_memo_deduplication() {
+        let filter = |child| {
+            LogicalFilter::new(BoundExpr::Constant(DataValue::Bool(true)), child).into_plan_ref()
+        };
+        let mut memo = Memo::default();
+        let group = memo.insert(filter(scan(0, &["x"])), None);
+        // the plans equal to the inserted ones are not inserted again
+        assert_eq!(memo.insert(filter(scan(0, &["x"])), None), group);
+        assert_eq!(memo.insert(filter(scan(0, &["x"])), Some(group)), group);
+        assert_eq!(memo.groups.len(), 2);
+        assert_eq!(memo.groups[group].exprs.len(), 1);
+        // the plans on different children are different
+        assert_ne!(memo.insert(filter(scan(1, &["x"])), None), group);
+        assert_eq!(memo.groups.len(), 4);
+    }
+}
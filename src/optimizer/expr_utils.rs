@@ -3,7 +3,7 @@
 use bit_set::BitSet;
 
 use super::logical_plan_rewriter::{BoolExprSimplificationRule, ExprRewriter};
-use crate::binder::BoundBinaryOp;
+use crate::binder::{BoundBinaryOp, BoundSubqueryKind};
 use crate::optimizer::BoundExpr;
 use crate::optimizer::BoundExpr::BinaryOp;
 use crate::parser::BinaryOperator::And;
@@ -42,6 +42,29 @@ where
     ret
 }
 
+/// Remove the branches of a `CASE` expression whose conditions are constantly false or null.
+///
+/// The expression is replaced by the result of its first branch if the condition of the branch is
+/// constantly true, or by its `ELSE` result if no branch is left.
+pub fn simplify_case(expr: &mut BoundExpr) {
+    let case = match expr {
+        BoundExpr::Case(case) => case,
+        _ => unreachable!(),
+    };
+    case.branches.retain(|(condition, _)| {
+        !matches!(
+            condition,
+            BoundExpr::Constant(DataValue::Bool(false) | DataValue::Null)
+        )
+    });
+    let new = match (case.branches.first(), &case.else_result) {
+        (Some((BoundExpr::Constant(DataValue::Bool(true)), result)), _) => result.clone(),
+        (None, Some(else_result)) => (**else_result).clone(),
+        _ => return,
+    };
+    *expr = new;
+}
+
 pub fn input_col_refs(expr: &BoundExpr) -> BitSet {
     let mut set = BitSet::default();
     input_col_refs_inner(expr, &mut set);
@@ -69,8 +92,44 @@ pub fn input_col_refs_inner(expr: &BoundExpr, input_set: &mut BitSet) {
         TypeCast(cast) => input_col_refs_inner(cast.expr.as_ref(), input_set),
         IsNull(isnull) => input_col_refs_inner(isnull.expr.as_ref(), input_set),
         ExprWithAlias(inner) => input_col_refs_inner(inner.expr.as_ref(), input_set),
-        Constant(_) => {}
-        Alias(_) => {}
+        Subquery(subquery) => {
+            if let BoundSubqueryKind::In(expr) = &subquery.kind {
+                input_col_refs_inner(expr.as_ref(), input_set);
+            }
+        }
+        Case(case) => {
+            for (condition, result) in &case.branches {
+                input_col_refs_inner(condition, input_set);
+                input_col_refs_inner(result, input_set);
+            }
+            if let Some(else_result) = &case.else_result {
+                input_col_refs_inner(else_result.as_ref(), input_set);
+            }
+        }
+        InList(in_list) => {
+            input_col_refs_inner(&in_list.expr, input_set);
+            for item in &in_list.list {
+                input_col_refs_inner(item, input_set);
+            }
+        }
+        FunctionCall(function_call) => {
+            for arg in &function_call.args {
+                input_col_refs_inner(arg, input_set);
+            }
+        }
+        WindowCall(window_call) => {
+            for arg in &window_call.args {
+                input_col_refs_inner(arg, input_set);
+            }
+            for expr in &window_call.partition_by {
+                input_col_refs_inner(expr, input_set);
+            }
+            for order in &window_call.order_by {
+                input_col_refs_inner(&order.expr, input_set);
+            }
+        }
+        // the column of a correlated reference belongs to the outer plan
+        Constant(_) | Alias(_) | CorrelatedRef(_) => {}
     };
 }
 
@@ -94,7 +153,43 @@ pub fn shift_input_col_refs(expr: &mut BoundExpr, delta: i32) {
         TypeCast(cast) => shift_input_col_refs(&mut *cast.expr, delta),
         IsNull(isnull) => shift_input_col_refs(&mut *isnull.expr, delta),
         ExprWithAlias(inner) => shift_input_col_refs(&mut *inner.expr, delta),
-        Constant(_) => {}
-        Alias(_) => {}
+        Subquery(subquery) => {
+            if let BoundSubqueryKind::In(expr) = &mut subquery.kind {
+                shift_input_col_refs(&mut *expr, delta);
+            }
+        }
+        Case(case) => {
+            for (condition, result) in &mut case.branches {
+                shift_input_col_refs(condition, delta);
+                shift_input_col_refs(result, delta);
+            }
+            if let Some(else_result) = &mut case.else_result {
+                shift_input_col_refs(&mut *else_result, delta);
+            }
+        }
+        InList(in_list) => {
+            shift_input_col_refs(&mut in_list.expr, delta);
+            for item in &mut in_list.list {
+                shift_input_col_refs(item, delta);
+            }
+        }
+        FunctionCall(function_call) => {
+            for arg in &mut function_call.args {
+                shift_input_col_refs(arg, delta);
+            }
+        }
+        WindowCall(window_call) => {
+            for arg in &mut window_call.args {
+                shift_input_col_refs(arg, delta);
+            }
+            for expr in &mut window_call.partition_by {
+                shift_input_col_refs(expr, delta);
+            }
+            for order in &mut window_call.order_by {
+                shift_input_col_refs(&mut order.expr, delta);
+            }
+        }
+        // the column of a correlated reference belongs to the outer plan
+        Constant(_) | Alias(_) | CorrelatedRef(_) => {}
     };
 }
@@ -124,7 +124,7 @@ mod tests {
         // a + 0 should be converted to a
         let _ = db.run(create_stmt).await;
 
-        let plans = db.generate_execution_plan(sql0).unwrap();
+        let plans = db.generate_execution_plan(sql0).await.unwrap();
         assert_eq!(plans.len(), 1);
         let buf = Vec::new();
         let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
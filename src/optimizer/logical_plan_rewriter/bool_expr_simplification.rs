@@ -3,6 +3,7 @@
 use super::*;
 use crate::binder::BoundExpr;
 use crate::binder::BoundExpr::*;
+use crate::optimizer::expr_utils::simplify_case;
 use crate::optimizer::plan_nodes::Dummy;
 use crate::parser::BinaryOperator::*;
 use crate::types::DataValue::*;
@@ -43,6 +44,22 @@ impl ExprRewriter for BoolExprSimplificationRule {
         };
         *expr = new;
     }
+
+    fn rewrite_case(&self, expr: &mut BoundExpr) {
+        match expr {
+            Case(case) => {
+                for (condition, result) in &mut case.branches {
+                    self.rewrite_expr(condition);
+                    self.rewrite_expr(result);
+                }
+                if let Some(else_result) = &mut case.else_result {
+                    self.rewrite_expr(else_result.as_mut());
+                }
+            }
+            _ => unreachable!(),
+        }
+        simplify_case(expr);
+    }
 }
 
 impl PlanRewriter for BoolExprSimplificationRule {
@@ -4,7 +4,10 @@ use BoundExpr::*;
 
 use super::*;
 use crate::array::ArrayImpl;
-use crate::binder::BoundExpr;
+use crate::binder::{BoundBinaryOp, BoundExpr};
+use crate::optimizer::expr_utils::simplify_case;
+use crate::parser::BinaryOperator;
+use crate::types::{DataTypeExt, DataTypeKind, DataValue};
 
 /// Constant folding rule aims to evalute the constant expression before query execution.
 ///
@@ -22,6 +25,10 @@ impl ExprRewriter for ConstantFoldingRule {
                 self.rewrite_expr(&mut *op.left_expr);
                 self.rewrite_expr(&mut *op.right_expr);
                 if let (Constant(v1), Constant(v2)) = (&*op.left_expr, &*op.right_expr) {
+                    // NULL can not be converted into an array
+                    if *v1 == DataValue::Null || *v2 == DataValue::Null {
+                        return;
+                    }
                     let res = ArrayImpl::from(v1)
                         .binary_op(&op.op, &ArrayImpl::from(v2))
                         .get(0);
@@ -37,6 +44,9 @@ impl ExprRewriter for ConstantFoldingRule {
             UnaryOp(op) => {
                 self.rewrite_expr(&mut *op.expr);
                 if let Constant(v) = &*op.expr {
+                    if *v == DataValue::Null {
+                        return;
+                    }
                     let res = ArrayImpl::from(v).unary_op(&op.op).get(0);
                     *expr = Constant(res);
                 }
@@ -50,6 +60,10 @@ impl ExprRewriter for ConstantFoldingRule {
             TypeCast(cast) => {
                 self.rewrite_expr(&mut *cast.expr);
                 if let Constant(v) = &*cast.expr {
+                    // keep the cast, so that the type of NULL is known
+                    if *v == DataValue::Null {
+                        return;
+                    }
                     if let Ok(array) = ArrayImpl::from(v).try_cast(cast.ty.clone()) {
                         let res = array.get(0);
                         *expr = Constant(res);
@@ -61,6 +75,47 @@ impl ExprRewriter for ConstantFoldingRule {
             _ => unreachable!(),
         }
     }
+
+    fn rewrite_case(&self, expr: &mut BoundExpr) {
+        match expr {
+            Case(case) => {
+                for (condition, result) in &mut case.branches {
+                    self.rewrite_expr(condition);
+                    self.rewrite_expr(result);
+                }
+                if let Some(else_result) = &mut case.else_result {
+                    self.rewrite_expr(else_result.as_mut());
+                }
+            }
+            _ => unreachable!(),
+        }
+        simplify_case(expr);
+    }
+
+    fn rewrite_in_list(&self, expr: &mut BoundExpr) {
+        let in_list = match expr {
+            InList(in_list) => in_list,
+            _ => unreachable!(),
+        };
+        self.rewrite_expr(&mut in_list.expr);
+        for item in &mut in_list.list {
+            self.rewrite_expr(item);
+        }
+        // `x IN (y)` is the same as `x = y`, which can be pushed down to the storage
+        if in_list.list.len() == 1 {
+            *expr = BinaryOp(BoundBinaryOp {
+                op: if in_list.negated {
+                    BinaryOperator::NotEq
+                } else {
+                    BinaryOperator::Eq
+                },
+                left_expr: in_list.expr.clone(),
+                right_expr: Box::new(in_list.list[0].clone()),
+                return_type: Some(DataTypeKind::Boolean.nullable()),
+            });
+            self.rewrite_binary_op(expr);
+        }
+    }
 }
 
 impl PlanRewriter for ConstantFoldingRule {
@@ -90,4 +145,8 @@ impl PlanRewriter for ConstantFoldingRule {
     fn rewrite_logical_values(&mut self, plan: &LogicalValues) -> PlanRef {
         Arc::new(plan.clone_with_rewrite_expr(self))
     }
+    fn rewrite_logical_update(&mut self, plan: &LogicalUpdate) -> PlanRef {
+        let child = self.rewrite(plan.child());
+        Arc::new(plan.clone_with_rewrite_expr(child, self))
+    }
 }
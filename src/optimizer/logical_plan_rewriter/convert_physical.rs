@@ -1,11 +1,38 @@
 // Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
 
+use std::collections::HashMap;
+
 use super::super::plan_nodes::*;
 use super::*;
-use crate::binder::BoundJoinOperator;
+use crate::binder::{BoundExpr, BoundInputRef, BoundJoinOperator};
 use crate::optimizer::expr_utils::merge_conjunctions;
+use crate::optimizer::PlanVisitor;
+use crate::types::DataValue;
+
 /// Convert all logical plan nodes to physical.
-pub struct PhysicalConverter;
+#[derive(Default)]
+pub struct PhysicalConverter {
+    /// The number of references to each CTE.
+    cte_references: HashMap<usize, usize>,
+}
+
+impl PhysicalConverter {
+    /// Create a converter for `plan`.
+    pub fn new(plan: &PlanRef) -> Self {
+        struct CteCounter(HashMap<usize, usize>);
+        impl PlanVisitor<()> for CteCounter {
+            fn visit_logical_cte_scan(&mut self, plan: &LogicalCteScan) -> Option<()> {
+                *self.0.entry(plan.cte_id()).or_default() += 1;
+                self.visit(plan.child())
+            }
+        }
+        let mut counter = CteCounter(HashMap::new());
+        counter.visit(plan.clone());
+        PhysicalConverter {
+            cte_references: counter.0,
+        }
+    }
+}
 
 impl PlanRewriter for PhysicalConverter {
     fn rewrite_logical_table_scan(&mut self, logical: &LogicalTableScan) -> PlanRef {
@@ -35,43 +62,37 @@ impl PlanRewriter for PhysicalConverter {
         Arc::new(PhysicalTopN::new(logical))
     }
 
+    fn rewrite_logical_apply(&mut self, _logical: &LogicalApply) -> PlanRef {
+        unreachable!("applies are either decorrelated or rejected by the optimizer")
+    }
+
     fn rewrite_logical_join(&mut self, logical_join: &LogicalJoin) -> PlanRef {
         let left = self.rewrite(logical_join.left());
         let right = self.rewrite(logical_join.right());
         let predicate = logical_join.predicate();
-        // FIXME: Currently just Inner join use HashJoin
-        if !predicate.eq_keys().is_empty() && logical_join.join_op() == BoundJoinOperator::Inner {
-            // TODO: Currently hash join just use one column pair as hash index
-            // TODO: Currently HashJoinExecutor ignores the condition, so for correctness we pull
-            // the conditions as a filter operator. And this transformation is only correct for
-            // inner join
-
-            let join = Arc::new(PhysicalHashJoin::new(LogicalJoin::create(
-                left,
-                right,
-                BoundJoinOperator::Inner,
-                merge_conjunctions(logical_join.predicate().eq_conds().into_iter()),
-            )));
-
-            let need_pull_filter = !predicate.left_conds().is_empty()
-                || !predicate.right_conds().is_empty()
-                || !predicate.other_conds().is_empty();
-            if need_pull_filter {
-                return Arc::new(PhysicalFilter::new(LogicalFilter::new(
-                    merge_conjunctions(
-                        predicate
-                            .left_conds()
-                            .iter()
-                            .cloned()
-                            .chain(predicate.right_conds().iter().cloned())
-                            .chain(predicate.other_conds().iter().cloned()),
-                    ),
-                    join,
-                )));
-            } else {
-                return join;
-            };
+        if logical_join.join_op() == BoundJoinOperator::Inner {
+            if let Some(plan) = sort_merge_join(logical_join, left.clone(), right.clone()) {
+                return plan;
+            }
+        }
+        // the conditions other than the equal keys are evaluated inside the hash join
+        if !predicate.eq_keys().is_empty() {
+            // the hash table is built on the left side, which should be the smaller one
+            let swappable = !matches!(
+                logical_join.join_op(),
+                BoundJoinOperator::LeftSemi
+                    | BoundJoinOperator::LeftAnti
+                    | BoundJoinOperator::LeftSingle
+            );
+            if swappable && right.estimated_cardinality() < left.estimated_cardinality() {
+                return swapped_hash_join(logical_join, left, right);
+            }
+            return Arc::new(PhysicalHashJoin::new(
+                logical_join.clone_with_left_right(left, right),
+            ));
         }
+        // joins without equal keys remain nested loop joins, such as the single joins of the
+        // uncorrelated scalar subqueries, whose right side has at most one row
         Arc::new(PhysicalNestedLoopJoin::new(
             logical_join.clone_with_left_right(left, right),
         ))
@@ -109,6 +130,12 @@ impl PlanRewriter for PhysicalConverter {
         Arc::new(PhysicalDelete::new(logical))
     }
 
+    fn rewrite_logical_update(&mut self, logical: &LogicalUpdate) -> PlanRef {
+        let child = self.rewrite(logical.child());
+        let logical = logical.clone_with_child(child);
+        Arc::new(PhysicalUpdate::new(logical))
+    }
+
     fn rewrite_logical_create_table(&mut self, logical: &LogicalCreateTable) -> PlanRef {
         Arc::new(PhysicalCreateTable::new(logical.clone()))
     }
@@ -123,6 +150,39 @@ impl PlanRewriter for PhysicalConverter {
         Arc::new(PhysicalCopyToFile::new(logical))
     }
 
+    fn rewrite_logical_cte_scan(&mut self, logical: &LogicalCteScan) -> PlanRef {
+        let child = self.rewrite(logical.child());
+        // inline the CTE if it is only referenced once
+        if self.cte_references[&logical.cte_id()] == 1 {
+            return child;
+        }
+        Arc::new(PhysicalCteScan::new(logical.clone_with_child(child)))
+    }
+
+    fn rewrite_logical_recursive_union(&mut self, logical: &LogicalRecursiveUnion) -> PlanRef {
+        let left = self.rewrite(logical.left());
+        let right = self.rewrite(logical.right());
+        Arc::new(PhysicalRecursiveUnion::new(
+            logical.clone_with_left_right(left, right),
+        ))
+    }
+
+    fn rewrite_logical_work_table_scan(&mut self, logical: &LogicalWorkTableScan) -> PlanRef {
+        Arc::new(PhysicalWorkTableScan::new(logical.clone()))
+    }
+
+    fn rewrite_logical_metadata_scan(&mut self, logical: &LogicalMetadataScan) -> PlanRef {
+        Arc::new(PhysicalMetadataScan::new(logical.clone()))
+    }
+
+    fn rewrite_logical_set_operation(&mut self, logical: &LogicalSetOperation) -> PlanRef {
+        let left = self.rewrite(logical.left());
+        let right = self.rewrite(logical.right());
+        Arc::new(PhysicalSetOperation::new(
+            logical.clone_with_left_right(left, right),
+        ))
+    }
+
     fn rewrite_logical_aggregate(&mut self, logical: &LogicalAggregate) -> PlanRef {
         if logical.group_keys().is_empty() {
             Arc::new(PhysicalSimpleAgg::new(
@@ -132,7 +192,123 @@ impl PlanRewriter for PhysicalConverter {
         } else {
             let child = self.rewrite(logical.child());
             let logical = logical.clone_with_child(child);
+            if is_sorted_by_group_keys(&logical) {
+                return Arc::new(PhysicalSortAgg::new(logical));
+            }
             Arc::new(PhysicalHashAgg::new(logical))
         }
     }
+
+    fn rewrite_logical_window(&mut self, logical: &LogicalWindow) -> PlanRef {
+        let child = self.rewrite(logical.child());
+        let logical = logical.clone_with_child(child);
+        Arc::new(PhysicalWindow::new(logical))
+    }
+
+    fn rewrite_logical_distinct_on(&mut self, logical: &LogicalDistinctOn) -> PlanRef {
+        let child = self.rewrite(logical.child());
+        let logical = logical.clone_with_child(child);
+        Arc::new(PhysicalDistinctOn::new(logical))
+    }
+}
+
+/// Create a hash join of `join` with its children swapped, and a projection on top of it to
+/// restore the order of the output columns.
+pub(crate) fn swapped_hash_join(join: &LogicalJoin, left: PlanRef, right: PlanRef) -> PlanRef {
+    struct Swapper {
+        left_col_num: usize,
+        right_col_num: usize,
+    }
+    impl ExprRewriter for Swapper {
+        fn rewrite_input_ref(&self, expr: &mut BoundExpr) {
+            match expr {
+                BoundExpr::InputRef(input_ref) if input_ref.index < self.left_col_num => {
+                    input_ref.index += self.right_col_num;
+                }
+                BoundExpr::InputRef(input_ref) => input_ref.index -= self.left_col_num,
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    let left_col_num = left.out_types().len();
+    let right_col_num = right.out_types().len();
+    let join_op = match join.join_op() {
+        BoundJoinOperator::LeftOuter => BoundJoinOperator::RightOuter,
+        BoundJoinOperator::RightOuter => BoundJoinOperator::LeftOuter,
+        join_op => join_op,
+    };
+    let mut condition = join.predicate().to_on_clause();
+    Swapper {
+        left_col_num,
+        right_col_num,
+    }
+    .rewrite_expr(&mut condition);
+    let swapped = Arc::new(PhysicalHashJoin::new(LogicalJoin::create(
+        right, left, join_op, condition,
+    )));
+
+    let exprs = (join.out_types().into_iter().enumerate())
+        .map(|(index, return_type)| {
+            let index = if index < left_col_num {
+                index + right_col_num
+            } else {
+                index - left_col_num
+            };
+            BoundExpr::InputRef(BoundInputRef { index, return_type })
+        })
+        .collect();
+    Arc::new(PhysicalProjection::new(LogicalProjection::new(
+        exprs, swapped,
+    )))
+}
+
+/// Returns true if the child of the aggregation is sorted by the group keys, in any order.
+pub(crate) fn is_sorted_by_group_keys(agg: &LogicalAggregate) -> bool {
+    let ordering = agg.child().ordering();
+    let group_keys = agg.group_keys();
+    group_keys.len() <= ordering.len()
+        && group_keys.iter().all(|key| match key {
+            BoundExpr::InputRef(input_ref) => {
+                ordering[..group_keys.len()].contains(&input_ref.index)
+            }
+            _ => false,
+        })
+}
+
+/// Create a sort merge join of the inner join if both children are sorted by one of its equal
+/// keys. The other conditions are evaluated by a filter on top of it.
+pub(crate) fn sort_merge_join(
+    join: &LogicalJoin,
+    left: PlanRef,
+    right: PlanRef,
+) -> Option<PlanRef> {
+    let left_col_num = left.out_types().len();
+    let left_ordering = left.ordering();
+    let right_ordering = right.ordering();
+    let predicate = join.predicate();
+    let position = predicate.eq_keys().iter().position(|(l, r)| {
+        left_ordering.first() == Some(&l.index)
+            && right_ordering.first() == Some(&(r.index - left_col_num))
+    })?;
+
+    let mut eq_conds = predicate.eq_conds();
+    let eq_cond = eq_conds.remove(position);
+    let merge_join = Arc::new(PhysicalSortMergeJoin::new(LogicalJoin::create(
+        left,
+        right,
+        BoundJoinOperator::Inner,
+        eq_cond,
+    )));
+    let condition = merge_conjunctions(
+        eq_conds
+            .into_iter()
+            .chain(std::iter::once(predicate.non_eq_cond())),
+    );
+    if condition == BoundExpr::Constant(DataValue::Bool(true)) {
+        return Some(merge_join);
+    }
+    Some(Arc::new(PhysicalFilter::new(LogicalFilter::new(
+        condition, merge_join,
+    ))))
 }
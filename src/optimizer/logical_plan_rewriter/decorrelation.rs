@@ -0,0 +1,409 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::collections::HashMap;
+
+use bit_set::BitSet;
+use itertools::Itertools;
+
+use super::*;
+use crate::binder::{
+    bind_coalesce, AggKind, BoundBinaryOp, BoundCorrelatedRef, BoundExpr, BoundInputRef,
+    BoundJoinOperator, ExprVisitor,
+};
+use crate::optimizer::expr_utils::{conjunctions, input_col_refs, merge_conjunctions};
+use crate::optimizer::PlanVisitor;
+use crate::parser::BinaryOperator;
+use crate::types::{DataTypeExt, DataTypeKind, DataValue};
+
+/// Decorrelation rule turns [`LogicalApply`] into [`LogicalJoin`].
+///
+/// An apply whose right side has no correlated reference is simply a join. Otherwise, the
+/// correlated predicates in the filter of the subquery are pulled up into the join condition.
+/// The following shapes of subquery are supported:
+///
+/// - `Projection(Filter(X))`: columns of `X` used by the correlated predicates are appended to the
+///   projection, so that the predicates can be evaluated by the join.
+/// - `Projection(Aggregate(Filter(X)))` without group keys in a scalar subquery, whose correlated
+///   predicates are all equal conditions: the inner sides of the conditions become the group keys,
+///   the apply becomes a left outer join on the keys, and the projection is evaluated above the
+///   join. The results of `count` are replaced with 0 for the rows without any joined group.
+///
+/// For example, in TPC-H Q17:
+/// ```text
+/// Apply: op Left Single
+///   Scan: part
+///   Projection: 0.2 * InputRef #0
+///     Aggregate: avg(l_quantity)
+///       Filter: l_partkey = p_partkey (outer)
+///         Scan: lineitem
+/// ```
+/// will be rewritten into
+/// ```text
+/// Projection: (columns of part), 0.2 * InputRef #n+1
+///   Join: op Left Outer, p_partkey = InputRef #n
+///     Scan: part
+///     Aggregate: avg(l_quantity), group by l_partkey
+///       Scan: lineitem
+/// ```
+///
+/// Applies which can not be decorrelated are left unchanged, and are reported as unsupported by
+/// the optimizer.
+#[derive(Default)]
+pub struct DecorrelationRule;
+
+impl PlanRewriter for DecorrelationRule {
+    fn rewrite_logical_apply(&mut self, apply: &LogicalApply) -> PlanRef {
+        let left = self.rewrite(apply.left());
+        let right = self.rewrite(apply.right());
+        if !is_correlated(&right) {
+            return Arc::new(LogicalJoin::create(
+                left,
+                right,
+                apply.join_op(),
+                apply.condition().clone(),
+            ));
+        }
+        match decorrelate(&left, &right, apply.join_op(), apply.condition()) {
+            Some(plan) => plan,
+            None => Arc::new(apply.clone_with_left_right(left, right)),
+        }
+    }
+}
+
+fn decorrelate(
+    left: &PlanRef,
+    right: &PlanRef,
+    join_op: BoundJoinOperator,
+    condition: &BoundExpr,
+) -> Option<PlanRef> {
+    let left_col_num = left.out_types().len();
+    let projection = right.as_logical_projection().ok()?;
+    let child = projection.child();
+    if let Ok(agg) = child.as_logical_aggregate() {
+        return decorrelate_aggregate(left, projection, agg, join_op, condition);
+    }
+    let mut exprs = projection.project_expressions().to_vec();
+    let output_col_num = exprs.len();
+    let (correlated, plan) = pull_correlated_predicates(&child)?;
+
+    // append inner columns used by the correlated predicates to the projection
+    let mut used_cols = BitSet::new();
+    for pred in &correlated {
+        used_cols.union_with(&input_col_refs(pred));
+    }
+    let types = plan.out_types();
+    let mut rewriter = CorrelatedPredicateRewriter::default();
+    for (i, index) in used_cols.iter().enumerate() {
+        rewriter
+            .mapping
+            .insert(index, left_col_num + output_col_num + i);
+        exprs.push(BoundExpr::InputRef(BoundInputRef {
+            index,
+            return_type: types[index].clone(),
+        }));
+    }
+    let right: PlanRef = Arc::new(LogicalProjection::new(exprs, plan));
+    if is_correlated(&right) {
+        return None;
+    }
+
+    let conds = correlated.into_iter().map(|mut pred| {
+        rewriter.rewrite_expr(&mut pred);
+        pred
+    });
+    let condition = merge_conjunctions(std::iter::once(condition.clone()).chain(conds));
+    let join: PlanRef = Arc::new(LogicalJoin::create(left.clone(), right, join_op, condition));
+    if matches!(
+        join_op,
+        BoundJoinOperator::LeftSemi | BoundJoinOperator::LeftAnti
+    ) {
+        return Some(join);
+    }
+    // remove the columns appended to the right side, whose columns are null if no row is joined
+    let types = join.out_types();
+    let exprs = (0..left_col_num + output_col_num)
+        .map(|index| {
+            BoundExpr::InputRef(BoundInputRef {
+                index,
+                return_type: match index < left_col_num {
+                    true => types[index].clone(),
+                    false => types[index].kind().nullable(),
+                },
+            })
+        })
+        .collect();
+    Some(Arc::new(LogicalProjection::new(exprs, join)))
+}
+
+/// Decorrelate `Projection(Aggregate(Filter(X)))` in a scalar subquery.
+///
+/// The aggregation is grouped by the inner sides of the correlated predicates and joined by a left
+/// outer join, and the projection is evaluated above the join. An aggregation without group keys
+/// returns one row even if its input is empty, where `count` returns 0 rather than null, so the
+/// results of `count` are replaced with 0 when no group is joined.
+fn decorrelate_aggregate(
+    left: &PlanRef,
+    projection: &LogicalProjection,
+    agg: &LogicalAggregate,
+    join_op: BoundJoinOperator,
+    condition: &BoundExpr,
+) -> Option<PlanRef> {
+    if join_op != BoundJoinOperator::LeftSingle
+        || *condition != BoundExpr::Constant(DataValue::Bool(true))
+        || !agg.group_keys().is_empty()
+    {
+        return None;
+    }
+    let left_col_num = left.out_types().len();
+    let (correlated, plan) = pull_correlated_predicates(&agg.child())?;
+    let mut keys = vec![];
+    let mut outer_exprs = vec![];
+    for pred in correlated {
+        let (inner, outer) = split_equal_condition(pred)?;
+        keys.push(inner);
+        outer_exprs.push(outer);
+    }
+
+    // the output of the aggregate becomes `keys ++ agg_calls`
+    let key_types = keys
+        .iter()
+        .map(|key| key.return_type().unwrap())
+        .collect_vec();
+    let rewriter = AggOutputRewriter {
+        offset: left_col_num + keys.len(),
+        counts: (agg.agg_calls().iter())
+            .positions(|call| matches!(call.kind, AggKind::Count | AggKind::RowCount))
+            .collect(),
+    };
+    let right: PlanRef = Arc::new(LogicalAggregate::new(agg.agg_calls().to_vec(), keys, plan));
+    if is_correlated(&right) {
+        return None;
+    }
+
+    let predicate_rewriter = CorrelatedPredicateRewriter::default();
+    let conds = outer_exprs
+        .into_iter()
+        .zip(key_types)
+        .enumerate()
+        .map(|(i, (mut outer, ty))| {
+            predicate_rewriter.rewrite_expr(&mut outer);
+            let key = BoundExpr::InputRef(BoundInputRef {
+                index: left_col_num + i,
+                return_type: ty,
+            });
+            BoundExpr::BinaryOp(BoundBinaryOp {
+                op: BinaryOperator::Eq,
+                left_expr: Box::new(outer),
+                right_expr: Box::new(key),
+                return_type: Some(DataTypeKind::Boolean.nullable()),
+            })
+        });
+    let join: PlanRef = Arc::new(LogicalJoin::create(
+        left.clone(),
+        right,
+        BoundJoinOperator::LeftOuter,
+        merge_conjunctions(conds),
+    ));
+
+    // restore the output of the apply
+    let types = join.out_types();
+    let mut exprs = (0..left_col_num)
+        .map(|index| {
+            BoundExpr::InputRef(BoundInputRef {
+                index,
+                return_type: types[index].clone(),
+            })
+        })
+        .collect_vec();
+    for expr in projection.project_expressions() {
+        let mut expr = expr.clone();
+        rewriter.rewrite_expr(&mut expr);
+        exprs.push(expr);
+    }
+    Some(Arc::new(LogicalProjection::new(exprs, join)))
+}
+
+/// Split the conjuncts of the filter on top of `plan` into correlated predicates and the rest.
+///
+/// Returns the correlated predicates and the plan with the rest predicates.
+fn pull_correlated_predicates(plan: &PlanRef) -> Option<(Vec<BoundExpr>, PlanRef)> {
+    let filter = plan.as_logical_filter().ok()?;
+    let (correlated, uncorrelated): (Vec<_>, Vec<_>) = conjunctions(filter.expr().clone())
+        .into_iter()
+        .partition(contains_correlated_ref);
+    let child = if uncorrelated.is_empty() {
+        filter.child()
+    } else {
+        Arc::new(LogicalFilter::new(
+            merge_conjunctions(uncorrelated.into_iter()),
+            filter.child(),
+        ))
+    };
+    Some((correlated, child))
+}
+
+/// Split an equal condition into the inner side and the outer side, where the outer side only
+/// refers to columns of the outer query.
+fn split_equal_condition(pred: BoundExpr) -> Option<(BoundExpr, BoundExpr)> {
+    let is_outer =
+        |expr: &BoundExpr| contains_correlated_ref(expr) && input_col_refs(expr).is_empty();
+    match pred {
+        BoundExpr::BinaryOp(BoundBinaryOp {
+            op: BinaryOperator::Eq,
+            left_expr,
+            right_expr,
+            ..
+        }) => {
+            if is_outer(&*right_expr) && !contains_correlated_ref(&left_expr) {
+                Some((*left_expr, *right_expr))
+            } else if is_outer(&*left_expr) && !contains_correlated_ref(&right_expr) {
+                Some((*right_expr, *left_expr))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn contains_correlated_ref(expr: &BoundExpr) -> bool {
+    struct Visitor(bool);
+    impl ExprVisitor for Visitor {
+        fn visit_correlated_ref(&mut self, _: &BoundCorrelatedRef) {
+            self.0 = true;
+        }
+    }
+    let mut visitor = Visitor(false);
+    visitor.visit_expr(expr);
+    visitor.0
+}
+
+/// Returns true if the plan has any apply, which can not be implemented by a physical plan.
+pub fn contains_apply(plan: &PlanRef) -> bool {
+    struct ApplyChecker(bool);
+    impl PlanVisitor<()> for ApplyChecker {
+        fn visit_logical_apply(&mut self, _: &LogicalApply) -> Option<()> {
+            self.0 = true;
+            None
+        }
+    }
+    let mut checker = ApplyChecker(false);
+    checker.visit(plan.clone());
+    checker.0
+}
+
+/// Returns true if the plan refers to columns of the outer query.
+fn is_correlated(plan: &PlanRef) -> bool {
+    let mut checker = CorrelationChecker(false);
+    checker.visit(plan.clone());
+    checker.0
+}
+
+/// Finds correlated references in a plan.
+struct CorrelationChecker(bool);
+
+impl CorrelationChecker {
+    fn check<'a>(&mut self, exprs: impl IntoIterator<Item = &'a BoundExpr>) {
+        self.0 |= exprs.into_iter().any(contains_correlated_ref);
+    }
+}
+
+impl PlanVisitor<()> for CorrelationChecker {
+    fn visit_logical_projection(&mut self, plan: &LogicalProjection) -> Option<()> {
+        self.check(plan.project_expressions());
+        self.visit(plan.child())
+    }
+
+    fn visit_logical_filter(&mut self, plan: &LogicalFilter) -> Option<()> {
+        self.check([plan.expr()]);
+        self.visit(plan.child())
+    }
+
+    fn visit_logical_aggregate(&mut self, plan: &LogicalAggregate) -> Option<()> {
+        self.check(plan.group_keys());
+        self.check(plan.agg_calls().iter().flat_map(|call| &call.args));
+        self.visit(plan.child())
+    }
+
+    fn visit_logical_order(&mut self, plan: &LogicalOrder) -> Option<()> {
+        self.check(plan.comparators().iter().map(|order| &order.expr));
+        self.visit(plan.child())
+    }
+
+    fn visit_logical_join(&mut self, plan: &LogicalJoin) -> Option<()> {
+        self.check([&plan.predicate().to_on_clause()]);
+        self.visit(plan.left());
+        self.visit(plan.right())
+    }
+
+    fn visit_logical_apply(&mut self, plan: &LogicalApply) -> Option<()> {
+        // correlated references in the right side belong to this apply
+        self.check([plan.condition()]);
+        self.visit(plan.left())
+    }
+}
+
+/// Rewrites correlated predicates into join conditions.
+///
+/// Correlated references are replaced by the columns of the left side, and inner columns are
+/// remapped to the columns appended to the right side.
+#[derive(Default)]
+struct CorrelatedPredicateRewriter {
+    mapping: HashMap<usize, usize>,
+}
+
+impl ExprRewriter for CorrelatedPredicateRewriter {
+    fn rewrite_input_ref(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::InputRef(input_ref) => {
+                input_ref.index = self.mapping[&input_ref.index];
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_correlated_ref(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::CorrelatedRef(correlated_ref) => {
+                *expr = (*correlated_ref.expr).clone();
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Rewrites the projection on an aggregation to be evaluated on the join of the aggregation.
+///
+/// The outputs of the aggregation are shifted by `offset`, results of `count` are replaced with 0
+/// if they are null, and correlated references are replaced by the columns of the left side.
+struct AggOutputRewriter {
+    offset: usize,
+    /// The positions of `count` in the aggregate calls.
+    counts: Vec<usize>,
+}
+
+impl ExprRewriter for AggOutputRewriter {
+    fn rewrite_input_ref(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::InputRef(input_ref) => {
+                let is_count = self.counts.contains(&input_ref.index);
+                input_ref.index += self.offset;
+                input_ref.return_type = input_ref.return_type.kind().nullable();
+                if is_count {
+                    let zero = BoundExpr::Constant(DataValue::Int32(0));
+                    *expr = bind_coalesce(vec![expr.clone(), zero]).unwrap();
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn rewrite_correlated_ref(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::CorrelatedRef(correlated_ref) => {
+                *expr = (*correlated_ref.expr).clone();
+            }
+            _ => unreachable!(),
+        }
+    }
+}
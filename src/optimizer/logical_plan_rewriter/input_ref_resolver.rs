@@ -2,7 +2,8 @@
 
 use super::*;
 use crate::binder::*;
-use crate::catalog::ColumnRefId;
+use crate::catalog::{ColumnDesc, ColumnRefId, TableRefId};
+use crate::types::ColumnId;
 
 /// Resolves column references into physical indices into the `DataChunk`.
 ///
@@ -15,6 +16,9 @@ pub struct InputRefResolver {
     /// not be touched. For other plans that change columns (e.g. SeqScan, Join, Projection,
     /// Aggregate), this variable should be set before the function returns.
     bindings: Vec<Option<BoundExpr>>,
+    /// The output columns of the outer plan, which is used to resolve correlated references in
+    /// the right side of an apply.
+    outer_bindings: Vec<Option<BoundExpr>>,
 }
 
 impl InputRefResolver {
@@ -44,6 +48,37 @@ impl InputRefResolver {
             BoundExpr::UnaryOp(expr) => self.rewrite_expr(expr.expr.as_mut()),
             BoundExpr::TypeCast(expr) => self.rewrite_expr(expr.expr.as_mut()),
             BoundExpr::IsNull(expr) => self.rewrite_expr(expr.expr.as_mut()),
+            BoundExpr::Case(case) => {
+                for (condition, result) in &mut case.branches {
+                    self.rewrite_expr(condition);
+                    self.rewrite_expr(result);
+                }
+                if let Some(else_result) = &mut case.else_result {
+                    self.rewrite_expr(else_result.as_mut());
+                }
+            }
+            BoundExpr::InList(in_list) => {
+                self.rewrite_expr(&mut in_list.expr);
+                for item in &mut in_list.list {
+                    self.rewrite_expr(item);
+                }
+            }
+            BoundExpr::FunctionCall(function_call) => {
+                for arg in &mut function_call.args {
+                    self.rewrite_expr(arg);
+                }
+            }
+            BoundExpr::WindowCall(window_call) => {
+                for arg in &mut window_call.args {
+                    self.rewrite_expr(arg);
+                }
+                for expr in &mut window_call.partition_by {
+                    self.rewrite_expr(expr);
+                }
+                for order in &mut window_call.order_by {
+                    self.rewrite_expr(&mut order.expr);
+                }
+            }
             _ => unreachable!(),
         }
     }
@@ -73,15 +108,76 @@ impl ExprRewriter for InputRefResolver {
     fn rewrite_is_null(&self, expr: &mut BoundExpr) {
         self.rewrite_template(expr);
     }
+
+    fn rewrite_case(&self, expr: &mut BoundExpr) {
+        self.rewrite_template(expr);
+    }
+
+    fn rewrite_in_list(&self, expr: &mut BoundExpr) {
+        self.rewrite_template(expr);
+    }
+
+    fn rewrite_function_call(&self, expr: &mut BoundExpr) {
+        self.rewrite_template(expr);
+    }
+
+    fn rewrite_window_call(&self, expr: &mut BoundExpr) {
+        self.rewrite_template(expr);
+    }
+
+    fn rewrite_correlated_ref(&self, expr: &mut BoundExpr) {
+        match expr {
+            BoundExpr::CorrelatedRef(expr) => {
+                let resolver = Self {
+                    bindings: self.outer_bindings.clone(),
+                    outer_bindings: vec![],
+                };
+                resolver.rewrite_expr(expr.expr.as_mut());
+            }
+            _ => unreachable!(),
+        }
+    }
 }
 
 impl PlanRewriter for InputRefResolver {
     fn rewrite_logical_join(&mut self, join: &LogicalJoin) -> PlanRef {
         let left = self.rewrite(join.left());
-        let mut resolver = Self::default();
+        let mut resolver = Self {
+            bindings: vec![],
+            outer_bindings: self.outer_bindings.clone(),
+        };
         let right = resolver.rewrite(join.right());
+        let left_bindings_num = self.bindings.len();
         self.bindings.append(&mut resolver.bindings);
-        Arc::new(join.clone_with_rewrite_expr(left, right, self))
+        let ret = Arc::new(join.clone_with_rewrite_expr(left, right, self));
+        // semi and anti joins only output columns from the left side
+        if matches!(
+            join.join_op(),
+            BoundJoinOperator::LeftSemi | BoundJoinOperator::LeftAnti
+        ) {
+            self.bindings.truncate(left_bindings_num);
+        }
+        ret
+    }
+
+    fn rewrite_logical_apply(&mut self, apply: &LogicalApply) -> PlanRef {
+        let left = self.rewrite(apply.left());
+        // correlated references in the right side are resolved by the output of the left side
+        let mut resolver = Self {
+            bindings: vec![],
+            outer_bindings: self.bindings.clone(),
+        };
+        let right = resolver.rewrite(apply.right());
+        let left_bindings_num = self.bindings.len();
+        self.bindings.append(&mut resolver.bindings);
+        let ret = Arc::new(apply.clone_with_rewrite_expr(left, right, self));
+        if matches!(
+            apply.join_op(),
+            BoundJoinOperator::LeftSemi | BoundJoinOperator::LeftAnti
+        ) {
+            self.bindings.truncate(left_bindings_num);
+        }
+        ret
     }
 
     fn rewrite_logical_table_scan(&mut self, plan: &LogicalTableScan) -> PlanRef {
@@ -116,6 +212,26 @@ impl PlanRewriter for InputRefResolver {
         Arc::new(plan.clone())
     }
 
+    fn rewrite_logical_cte_scan(&mut self, plan: &LogicalCteScan) -> PlanRef {
+        // the plan of a CTE is resolved independently
+        let child = Self::default().rewrite(plan.child());
+        self.bindings = virtual_table_bindings(plan.table_ref_id(), plan.column_descs());
+        Arc::new(plan.clone_with_child(child))
+    }
+
+    fn rewrite_logical_work_table_scan(&mut self, plan: &LogicalWorkTableScan) -> PlanRef {
+        self.bindings = virtual_table_bindings(plan.table_ref_id(), plan.column_descs());
+        Arc::new(plan.clone())
+    }
+
+    fn rewrite_logical_set_operation(&mut self, plan: &LogicalSetOperation) -> PlanRef {
+        // both sides are resolved independently
+        let left = Self::default().rewrite(plan.left());
+        let right = Self::default().rewrite(plan.right());
+        self.bindings = virtual_table_bindings(plan.table_ref_id(), plan.column_descs());
+        Arc::new(plan.clone_with_left_right(left, right))
+    }
+
     fn rewrite_logical_projection(&mut self, proj: &LogicalProjection) -> PlanRef {
         let new_child = self.rewrite(proj.child());
         let bindings = proj
@@ -147,6 +263,19 @@ impl PlanRewriter for InputRefResolver {
         self.bindings = bindings;
         ret
     }
+
+    fn rewrite_logical_window(&mut self, plan: &LogicalWindow) -> PlanRef {
+        let new_child = self.rewrite(plan.child());
+        let ret = Arc::new(plan.clone_with_rewrite_expr(new_child, self));
+        // window calls are appended to the columns of the child
+        let window_calls = plan
+            .window_calls()
+            .iter()
+            .map(|e| Some(BoundExpr::WindowCall(e.clone())));
+        self.bindings.extend(window_calls);
+        ret
+    }
+
     fn rewrite_logical_filter(&mut self, plan: &LogicalFilter) -> PlanRef {
         let new_child = self.rewrite(plan.child());
         Arc::new(plan.clone_with_rewrite_expr(new_child, self))
@@ -156,9 +285,39 @@ impl PlanRewriter for InputRefResolver {
         let child = self.rewrite(plan.child());
         Arc::new(plan.clone_with_rewrite_expr(child, self))
     }
+
+    fn rewrite_logical_distinct_on(&mut self, plan: &LogicalDistinctOn) -> PlanRef {
+        let child = self.rewrite(plan.child());
+        Arc::new(plan.clone_with_rewrite_expr(child, self))
+    }
+
     fn rewrite_logical_values(&mut self, plan: &LogicalValues) -> PlanRef {
         Arc::new(plan.clone_with_rewrite_expr(self))
     }
+
+    fn rewrite_logical_update(&mut self, plan: &LogicalUpdate) -> PlanRef {
+        let child = self.rewrite(plan.child());
+        Arc::new(plan.clone_with_rewrite_expr(child, self))
+    }
+}
+
+/// Returns the column references to all columns of a virtual table, which is the reference to a
+/// CTE.
+fn virtual_table_bindings(
+    table_ref_id: TableRefId,
+    column_descs: &[ColumnDesc],
+) -> Vec<Option<BoundExpr>> {
+    column_descs
+        .iter()
+        .enumerate()
+        .map(|(col_id, col_desc)| {
+            Some(BoundExpr::ColumnRef(BoundColumnRef {
+                column_ref_id: ColumnRefId::from_table(table_ref_id, col_id as ColumnId),
+                is_primary_key: col_desc.is_primary(),
+                desc: col_desc.clone(),
+            }))
+        })
+        .collect()
 }
 
 /// Resolves select expression into `InputRef` using group by expressions
@@ -213,7 +372,39 @@ impl AggInputRefResolver {
                 self.resolve_select_expr(&mut expr_with_alias.expr, group_keys)
             }
             IsNull(isnull) => self.resolve_select_expr(&mut isnull.expr, group_keys),
-            Constant(_) | ColumnRef(_) | InputRef(_) | Alias(_) => {}
+            Case(case) => {
+                for (condition, result) in &mut case.branches {
+                    self.resolve_select_expr(condition, group_keys);
+                    self.resolve_select_expr(result, group_keys);
+                }
+                if let Some(else_result) = &mut case.else_result {
+                    self.resolve_select_expr(else_result, group_keys);
+                }
+            }
+            InList(in_list) => {
+                self.resolve_select_expr(&mut in_list.expr, group_keys);
+                for item in &mut in_list.list {
+                    self.resolve_select_expr(item, group_keys);
+                }
+            }
+            FunctionCall(function_call) => {
+                for arg in &mut function_call.args {
+                    self.resolve_select_expr(arg, group_keys);
+                }
+            }
+            WindowCall(window_call) => {
+                for arg in &mut window_call.args {
+                    self.resolve_select_expr(arg, group_keys);
+                }
+                for expr in &mut window_call.partition_by {
+                    self.resolve_select_expr(expr, group_keys);
+                }
+                for order in &mut window_call.order_by {
+                    self.resolve_select_expr(&mut order.expr, group_keys);
+                }
+            }
+            Constant(_) | ColumnRef(_) | InputRef(_) | Alias(_) | Subquery(_) => {}
+            CorrelatedRef(_) => {}
         }
     }
 }
@@ -241,6 +432,7 @@ mod tests {
                 desc: DataTypeKind::Int(None).not_null().to_column("v1".into()),
             })],
             return_type: DataTypeKind::Int(None).not_null(),
+            distinct: false,
         });
         let v2_plus_1_expr = BoundExpr::BinaryOp(BoundBinaryOp {
             op: BinaryOperator::Plus,
@@ -292,6 +484,7 @@ mod tests {
                 desc: DataTypeKind::Int(None).not_null().to_column("v1".into()),
             })],
             return_type: DataTypeKind::Int(None).not_null(),
+            distinct: false,
         });
         let v2_expr = BoundExpr::ColumnRef(BoundColumnRef {
             column_ref_id: ColumnRefId::new(0, 0, 0, 1),
@@ -0,0 +1,445 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use super::*;
+use crate::binder::{BoundExpr, BoundInputRef, BoundJoinOperator};
+use crate::optimizer::estimated_selectivity;
+use crate::optimizer::expr_utils::{
+    conjunctions, input_col_refs, merge_conjunctions, shift_input_col_refs,
+};
+use crate::types::DataValue;
+
+/// The maximum number of relations to reorder. The search takes `O(3^n)` time, so the joins of
+/// more relations are kept as written. It also keeps the sets of relations within `u32`.
+const MAX_REORDER_RELATIONS: usize = 10;
+
+/// Reorder inner joins to minimize the estimated rows of intermediate results.
+///
+/// Each tree of inner joins, with the filter on it, is flattened into relations, which are the
+/// inputs of the joins, and the conditions on them. The best tree is searched by dynamic
+/// programming over the subsets of relations, where the cost of a tree is the sum of the estimated
+/// rows of its joins. Joins without conditions are avoided whenever possible, and the written tree
+/// is kept unless a cheaper one is found.
+pub struct JoinReorder;
+
+impl JoinReorder {
+    /// Flatten the tree of inner joins into the graph, and return the shape of the tree.
+    fn flatten(&mut self, plan: PlanRef, offset: usize, graph: &mut JoinGraph) -> JoinTree {
+        match plan.as_logical_join() {
+            Ok(join) if join.join_op() == BoundJoinOperator::Inner => {
+                let left_col_num = join.left().out_types().len();
+                let left = self.flatten(join.left(), offset, graph);
+                let right = self.flatten(join.right(), offset + left_col_num, graph);
+                graph.add_conditions(join.predicate().to_on_clause(), offset);
+                JoinTree::Join(Box::new(left), Box::new(right))
+            }
+            _ => {
+                let plan = self.rewrite(plan);
+                graph.relations.push((plan, offset));
+                JoinTree::Relation(graph.relations.len() - 1)
+            }
+        }
+    }
+
+    /// Rewrite the relations of the tree of inner joins, and keep the joins as written.
+    fn rewrite_relations(&mut self, plan: PlanRef) -> PlanRef {
+        match plan.as_logical_join() {
+            Ok(join) if join.join_op() == BoundJoinOperator::Inner => {
+                let left = self.rewrite_relations(join.left());
+                let right = self.rewrite_relations(join.right());
+                Arc::new(join.clone_with_left_right(left, right))
+            }
+            _ => self.rewrite(plan),
+        }
+    }
+}
+
+/// The number of relations of the tree of inner joins.
+fn num_relations(plan: &PlanRef) -> usize {
+    match plan.as_logical_join() {
+        Ok(join) if join.join_op() == BoundJoinOperator::Inner => {
+            num_relations(&join.left()) + num_relations(&join.right())
+        }
+        _ => 1,
+    }
+}
+
+impl PlanRewriter for JoinReorder {
+    fn rewrite_logical_join(&mut self, join: &LogicalJoin) -> PlanRef {
+        if join.join_op() != BoundJoinOperator::Inner {
+            let left = self.rewrite(join.left());
+            let right = self.rewrite(join.right());
+            return Arc::new(join.clone_with_left_right(left, right));
+        }
+        let plan = join.clone_as_plan_ref();
+        if num_relations(&plan) > MAX_REORDER_RELATIONS {
+            return self.rewrite_relations(plan);
+        }
+        let mut graph = JoinGraph::default();
+        let written = self.flatten(plan, 0, &mut graph);
+        let tree = graph.reorder(written);
+        graph.build(&tree)
+    }
+
+    fn rewrite_logical_filter(&mut self, filter: &LogicalFilter) -> PlanRef {
+        // the conditions of a filter on inner joins are placed together with the join conditions
+        let is_inner_join = (filter.child().as_logical_join())
+            .map_or(false, |join| join.join_op() == BoundJoinOperator::Inner);
+        if !is_inner_join {
+            let child = self.rewrite(filter.child());
+            return Arc::new(filter.clone_with_child(child));
+        }
+        if num_relations(&filter.child()) > MAX_REORDER_RELATIONS {
+            let child = self.rewrite_relations(filter.child());
+            return Arc::new(filter.clone_with_child(child));
+        }
+        let mut graph = JoinGraph::default();
+        let written = self.flatten(filter.child(), 0, &mut graph);
+        graph.add_conditions(filter.expr().clone(), 0);
+        let tree = graph.reorder(written);
+        graph.build(&tree)
+    }
+}
+
+/// The shape of a join tree, whose leaves are the indexes of relations.
+enum JoinTree {
+    Relation(usize),
+    Join(Box<JoinTree>, Box<JoinTree>),
+}
+
+/// The relations and the conditions of a tree of inner joins.
+#[derive(Default)]
+struct JoinGraph {
+    /// The relations, and the index of their first column in the output of the tree.
+    relations: Vec<(PlanRef, usize)>,
+    /// The conditions on the output of the tree.
+    conditions: Vec<BoundExpr>,
+}
+
+impl JoinGraph {
+    /// Add the conjunctions of the condition on the columns starting from `offset`.
+    fn add_conditions(&mut self, condition: BoundExpr, offset: usize) {
+        for mut cond in conjunctions(condition) {
+            if cond != BoundExpr::Constant(DataValue::Bool(true)) {
+                shift_input_col_refs(&mut cond, offset as i32);
+                self.conditions.push(cond);
+            }
+        }
+    }
+
+    /// The set of relations referenced by the condition.
+    fn relations_of(&self, cond: &BoundExpr) -> u32 {
+        let mut relations = 0;
+        for index in input_col_refs(cond).iter() {
+            let relation = (self.relations.iter())
+                .rposition(|(_, offset)| *offset <= index)
+                .unwrap();
+            relations |= 1 << relation;
+        }
+        relations
+    }
+
+    /// Search the tree with the lowest cost. Returns the written tree if it is the best one.
+    fn reorder(&self, written: JoinTree) -> JoinTree {
+        let num = self.relations.len();
+        let conditions: Vec<u32> = (self.conditions.iter())
+            .map(|cond| self.relations_of(cond))
+            .collect();
+
+        // the estimated rows of the joins of each set of relations
+        let relation_rows: Vec<f64> = (self.relations.iter())
+            .map(|(plan, _)| plan.estimated_cardinality() as f64)
+            .collect();
+        let distinct_values = |index: usize| {
+            let (plan, offset) = (self.relations.iter())
+                .rfind(|(_, offset)| *offset <= index)
+                .unwrap();
+            plan.estimated_distinct_values(index - offset)
+        };
+        let selectivities: Vec<f64> = (self.conditions.iter())
+            .map(|cond| estimated_selectivity(cond, &distinct_values))
+            .collect();
+        let rows: Vec<f64> = (0..1u32 << num)
+            .map(|set| {
+                let relations = (0..num)
+                    .filter(|i| set & (1 << i) != 0)
+                    .map(|i| relation_rows[i]);
+                let conditions = (conditions.iter().zip(&selectivities))
+                    .filter(|(relations, _)| *relations & !set == 0)
+                    .map(|(_, selectivity)| *selectivity);
+                relations.chain(conditions).product()
+            })
+            .collect();
+        let joined = |left: u32, right: u32| {
+            let set = left | right;
+            let connected = conditions.iter().any(|relations| {
+                relations & left != 0 && relations & right != 0 && relations & !set == 0
+            });
+            (!connected as usize, rows[set as usize])
+        };
+
+        // the number of joins without conditions and the cost of the best tree of each set,
+        // and the left side of its root
+        let mut best: Vec<Option<(usize, f64, u32)>> = vec![None; 1 << num];
+        for i in 0..num {
+            best[1 << i] = Some((0, 0.0, 0));
+        }
+        for set in 1..1u32 << num {
+            if set.count_ones() < 2 {
+                continue;
+            }
+            // the relation with the lowest index is always on the left side
+            let lowest = set & set.wrapping_neg();
+            let mut left = (set - 1) & set;
+            while left != 0 {
+                if left & lowest != 0 {
+                    let right = set ^ left;
+                    let (left_cross, left_cost, _) = best[left as usize].unwrap();
+                    let (right_cross, right_cost, _) = best[right as usize].unwrap();
+                    let (cross, rows) = joined(left, right);
+                    let candidate = (
+                        left_cross + right_cross + cross,
+                        left_cost + right_cost + rows,
+                    );
+                    if best[set as usize].map_or(true, |(cross, cost, _)| candidate < (cross, cost))
+                    {
+                        best[set as usize] = Some((candidate.0, candidate.1, left));
+                    }
+                }
+                left = (left - 1) & set;
+            }
+        }
+
+        fn cost_of(
+            tree: &JoinTree,
+            joined: &impl Fn(u32, u32) -> (usize, f64),
+        ) -> (u32, usize, f64) {
+            match tree {
+                JoinTree::Relation(i) => (1 << i, 0, 0.0),
+                JoinTree::Join(left, right) => {
+                    let (left, left_cross, left_cost) = cost_of(left, joined);
+                    let (right, right_cross, right_cost) = cost_of(right, joined);
+                    let (cross, rows) = joined(left, right);
+                    (
+                        left | right,
+                        left_cross + right_cross + cross,
+                        left_cost + right_cost + rows,
+                    )
+                }
+            }
+        }
+        fn tree_of(set: u32, best: &[Option<(usize, f64, u32)>]) -> JoinTree {
+            if set.count_ones() == 1 {
+                return JoinTree::Relation(set.trailing_zeros() as usize);
+            }
+            let (_, _, left) = best[set as usize].unwrap();
+            JoinTree::Join(
+                Box::new(tree_of(left, best)),
+                Box::new(tree_of(set ^ left, best)),
+            )
+        }
+
+        let all = (1u32 << num) - 1;
+        let (_, written_cross, written_cost) = cost_of(&written, &joined);
+        let (best_cross, best_cost, _) = best[all as usize].unwrap();
+        // tolerate the rounding errors of costs
+        if (best_cross, best_cost * (1.0 + 1e-9)) < (written_cross, written_cost) {
+            tree_of(all, &best)
+        } else {
+            written
+        }
+    }
+
+    /// Build the plan of the tree. Each condition is evaluated by the lowest join which covers
+    /// all its relations, and a projection restores the order of the output columns.
+    fn build(&self, tree: &JoinTree) -> PlanRef {
+        let conditions: Vec<(u32, &BoundExpr)> = (self.conditions.iter())
+            .map(|cond| (self.relations_of(cond), cond))
+            .collect();
+        let (mut plan, _, columns) = self.build_tree(tree, &conditions);
+
+        let constants = (conditions.iter())
+            .filter(|(relations, _)| *relations == 0)
+            .map(|(_, cond)| (*cond).clone())
+            .collect_vec();
+        if !constants.is_empty() {
+            plan = Arc::new(LogicalFilter::new(
+                merge_conjunctions(constants.into_iter()),
+                plan,
+            ));
+        }
+        if columns.iter().enumerate().all(|(i, column)| i == *column) {
+            return plan;
+        }
+        let out_types = plan.out_types();
+        let mut exprs = vec![None; columns.len()];
+        for (index, column) in columns.iter().enumerate() {
+            exprs[*column] = Some(BoundExpr::InputRef(BoundInputRef {
+                index,
+                return_type: out_types[index].clone(),
+            }));
+        }
+        Arc::new(LogicalProjection::new(
+            exprs.into_iter().map(Option::unwrap).collect(),
+            plan,
+        ))
+    }
+
+    /// Build the plan of the tree, and return the set of relations in it and the columns of the
+    /// whole tree it outputs.
+    fn build_tree(
+        &self,
+        tree: &JoinTree,
+        conditions: &[(u32, &BoundExpr)],
+    ) -> (PlanRef, u32, Vec<usize>) {
+        match tree {
+            JoinTree::Relation(i) => {
+                let (plan, offset) = &self.relations[*i];
+                let set = 1 << i;
+                let columns = (*offset..*offset + plan.out_types().len()).collect_vec();
+                let conds = conditions_at(conditions, set, &[], &columns);
+                if conds.is_empty() {
+                    return (plan.clone(), set, columns);
+                }
+                let filter =
+                    LogicalFilter::new(merge_conjunctions(conds.into_iter()), plan.clone());
+                (Arc::new(filter), set, columns)
+            }
+            JoinTree::Join(left, right) => {
+                let (left, left_set, mut columns) = self.build_tree(left, conditions);
+                let (right, right_set, right_columns) = self.build_tree(right, conditions);
+                columns.extend(right_columns);
+                let set = left_set | right_set;
+                let conds = conditions_at(conditions, set, &[left_set, right_set], &columns);
+                let join = LogicalJoin::create(
+                    left,
+                    right,
+                    BoundJoinOperator::Inner,
+                    merge_conjunctions(conds.into_iter()),
+                );
+                (Arc::new(join), set, columns)
+            }
+        }
+    }
+}
+
+/// The conditions evaluated on the relations of `set` but not on any of its `children`, with their
+/// input references mapped to the positions in `columns`.
+fn conditions_at(
+    conditions: &[(u32, &BoundExpr)],
+    set: u32,
+    children: &[u32],
+    columns: &[usize],
+) -> Vec<BoundExpr> {
+    struct ColumnMapper(HashMap<usize, usize>);
+    impl ExprRewriter for ColumnMapper {
+        fn rewrite_input_ref(&self, expr: &mut BoundExpr) {
+            if let BoundExpr::InputRef(input_ref) = expr {
+                input_ref.index = self.0[&input_ref.index];
+            }
+        }
+    }
+    let mapper = ColumnMapper(
+        (columns.iter().enumerate())
+            .map(|(position, column)| (*column, position))
+            .collect(),
+    );
+    (conditions.iter())
+        .filter(|(relations, _)| {
+            *relations != 0
+                && relations & !set == 0
+                && children.iter().all(|child| relations & !child != 0)
+        })
+        .map(|(_, cond)| {
+            let mut cond = (*cond).clone();
+            mapper.rewrite_expr(&mut cond);
+            cond
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimizer::test_utils::*;
+
+    #[test]
+    /// Reorder
+    /// ```text
+    /// Join(cond: a.x = b.x and c.y = b.y)
+    ///   Join(cond: true)
+    ///     TableScan(a: x)
+    ///     TableScan(c: y)
+    ///   TableScan(b: x, y)
+    /// ```
+    /// where `a` and `c` have 1000 rows and `b` has 10 rows, and `a.x` is unique, into
+    /// ```text
+    /// Projection(a.x, c.y, b.x, b.y)
+    ///   Join(cond: c.y = b.y)
+    ///     Join(cond: a.x = b.x)
+    ///       TableScan(a: x)
+    ///       TableScan(b: x, y)
+    ///     TableScan(c: y)
+    /// ```
+    fn test_reorder_join() {
+        let statistics = statistics(&[(0, 1000, &[1000]), (1, 10, &[10, 10]), (2, 1000, &[10])]);
+
+        let written = LogicalJoin::create(
+            LogicalJoin::create(
+                scan(0, &["a.x"]),
+                scan(2, &["c.y"]),
+                BoundJoinOperator::Inner,
+                BoundExpr::Constant(DataValue::Bool(true)),
+            )
+            .into_plan_ref(),
+            scan(1, &["b.x", "b.y"]),
+            BoundJoinOperator::Inner,
+            merge_conjunctions([eq(0, 2), eq(1, 3)].into_iter()),
+        );
+        let plan = JoinReorder.rewrite(statistics.annotate(written.into_plan_ref()));
+
+        let projection = plan.as_logical_projection().unwrap();
+        assert_eq!(
+            projection.project_expressions().to_vec(),
+            [0, 3, 1, 2].map(input_ref).to_vec()
+        );
+        let join = projection.child();
+        let join = join.as_logical_join().unwrap();
+        assert_eq!(join.predicate().to_on_clause(), eq(2, 3));
+        let table_id = |plan: PlanRef| plan.as_logical_table_scan().unwrap().table_ref_id();
+        assert_eq!(table_id(join.right()), table_ref_id(2));
+        let left = join.left();
+        let left = left.as_logical_join().unwrap();
+        assert_eq!(left.predicate().to_on_clause(), eq(0, 1));
+        assert_eq!(table_id(left.left()), table_ref_id(0));
+        assert_eq!(table_id(left.right()), table_ref_id(1));
+    }
+
+    #[test]
+    /// The joins of more relations than `MAX_REORDER_RELATIONS` are kept as written, even if there
+    /// are more than 32 relations.
+    fn test_keep_joins_of_many_relations() {
+        let mut written = scan(0, &["x"]);
+        for i in 1..40 {
+            written = LogicalJoin::create(
+                written,
+                scan(i as u32, &["x"]),
+                BoundJoinOperator::Inner,
+                eq(i - 1, i),
+            )
+            .into_plan_ref();
+        }
+        let written = LogicalFilter::new(eq(0, 39), written).into_plan_ref();
+        let plan = JoinReorder.rewrite(written.clone());
+
+        let explain = |plan: PlanRef| {
+            let mut explain = String::new();
+            plan.explain(0, &mut explain).unwrap();
+            explain
+        };
+        assert_eq!(explain(plan), explain(written));
+    }
+}
@@ -9,15 +9,19 @@ mod bool_expr_simplification;
 mod constant_folding;
 mod constant_moving;
 mod convert_physical;
+mod decorrelation;
 mod input_ref_resolver;
+mod join_reorder;
 
 pub use arith_expr_simplification::*;
 pub use bool_expr_simplification::*;
 pub use constant_folding::*;
 pub use constant_moving::*;
 pub use convert_physical::*;
+pub use decorrelation::*;
 pub use input_ref_resolver::*;
 use itertools::Itertools;
+pub use join_reorder::*;
 use paste::paste;
 
 pub use crate::binder::ExprRewriter;
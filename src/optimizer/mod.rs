@@ -3,19 +3,26 @@
 use bit_set::BitSet;
 
 use crate::binder::*;
+use crate::logical_planner::LogicalPlanError;
 
-mod expr_utils;
+mod cascades;
+pub(crate) mod expr_utils;
 mod heuristic;
 pub mod logical_plan_rewriter;
 pub mod plan_nodes;
 mod plan_visitor;
 mod rules;
+mod statistics;
+#[cfg(test)]
+mod test_utils;
 
+use self::cascades::CascadesOptimizer;
 use self::heuristic::HeuristicOptimizer;
 use self::logical_plan_rewriter::*;
 use self::plan_nodes::PlanRef;
 pub use self::plan_visitor::*;
 use self::rules::*;
+pub use self::statistics::*;
 
 /// The optimizer will do query optimization.
 ///
@@ -23,13 +30,32 @@ use self::rules::*;
 /// expression extraction) , and cost-based optimization (Join reordering and join algorithm
 /// selection). It takes Plan as input and returns a new Plan which could be used to
 /// generate phyiscal plan.
+///
+/// The rules are applied either by the memo-based `CascadesOptimizer`, which also selects the
+/// physical operators by cost, or by the `HeuristicOptimizer`, which applies at most one rule on
+/// each node.
 #[derive(Default)]
 pub struct Optimizer {
     pub enable_filter_scan: bool,
+    /// Answer the aggregations on tables from the metadata in the storage.
+    pub enable_metadata_scan: bool,
+    /// Use the memo-based optimizer instead of the heuristic one.
+    pub enable_cascades: bool,
+    /// The statistics of the tables, from which the cardinalities of plan nodes are estimated.
+    pub statistics: Statistics,
 }
 
 impl Optimizer {
-    pub fn optimize(&mut self, mut plan: PlanRef) -> PlanRef {
+    pub fn optimize(&mut self, mut plan: PlanRef) -> Result<PlanRef, LogicalPlanError> {
+        plan = self.statistics.annotate(plan);
+        // turn subqueries into joins before other optimizations
+        let mut decorrelation_rule = DecorrelationRule;
+        plan = decorrelation_rule.rewrite(plan);
+        if contains_apply(&plan) {
+            return Err(LogicalPlanError::UnsupportedSubquery(
+                "correlated subqueries which can not be decorrelated".into(),
+            ));
+        }
         let mut constant_folding_rule = ConstantFoldingRule;
         let mut constant_moving_rule = ConstantMovingRule;
         let mut arith_expr_simplification_rule = ArithExprSimplificationRule;
@@ -46,11 +72,35 @@ impl Optimizer {
         if self.enable_filter_scan {
             rules.push(Box::new(FilterScanRule {}));
         }
+        if self.enable_metadata_scan {
+            rules.push(Box::new(MetadataScanRule {}));
+        }
+        if self.enable_cascades {
+            // the joins are reordered before the search, together with the filters on them, so
+            // the filters are pushed down to the joins first
+            let pushdown_optimizer = HeuristicOptimizer {
+                rules: vec![Box::new(FilterJoinRule {})],
+            };
+            plan = pushdown_optimizer.optimize(plan);
+            plan = JoinReorder.rewrite(plan);
+            let out_types_num = plan.out_types().len();
+            plan = plan.prune_col(BitSet::from_iter(0..out_types_num));
+            let implementations: Vec<BoxedImplementationRule> = vec![
+                Box::new(JoinImplementationRule {}),
+                Box::new(AggImplementationRule {}),
+            ];
+            let cascades_optimizer = CascadesOptimizer {
+                rules,
+                implementations,
+            };
+            return Ok(cascades_optimizer.optimize(plan));
+        }
         let hep_optimizer = HeuristicOptimizer { rules };
         plan = hep_optimizer.optimize(plan);
+        plan = JoinReorder.rewrite(plan);
         let out_types_num = plan.out_types().len();
         plan = plan.prune_col(BitSet::from_iter(0..out_types_num));
-        let mut phy_converter = PhysicalConverter;
-        phy_converter.rewrite(plan)
+        let mut phy_converter = PhysicalConverter::new(&plan);
+        Ok(phy_converter.rewrite(plan))
     }
 }
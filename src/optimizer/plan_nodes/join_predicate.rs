@@ -151,6 +151,16 @@ impl JoinPredicate {
             .map(|(_, right)| right.clone())
             .collect()
     }
+    /// Get the conjunction of all conditions except the eq conds.
+    pub fn non_eq_cond(&self) -> BoundExpr {
+        merge_conjunctions(
+            self.left_conds
+                .iter()
+                .cloned()
+                .chain(self.right_conds.iter().cloned())
+                .chain(self.other_conds.iter().cloned()),
+        )
+    }
     pub fn to_on_clause(&self) -> BoundExpr {
         merge_conjunctions(
             self.left_conds
@@ -54,6 +54,14 @@ impl LogicalAggregate {
 
         LogicalAggregate::new(new_agg_calls, new_keys, new_child)
     }
+
+    /// The estimated number of distinct values of the group key in the child.
+    fn estimated_distinct_values_of_key(&self, key: &BoundExpr) -> usize {
+        match key {
+            BoundExpr::InputRef(input_ref) => self.child.estimated_distinct_values(input_ref.index),
+            _ => self.child.estimated_cardinality(),
+        }
+    }
 }
 
 impl PlanTreeNodeUnary for LogicalAggregate {
@@ -87,8 +95,24 @@ impl PlanNode for LogicalAggregate {
             .collect()
     }
 
+    /// The number of groups is the product of the distinct values of the group keys.
     fn estimated_cardinality(&self) -> usize {
-        self.child().estimated_cardinality()
+        if self.group_keys.is_empty() {
+            return 1;
+        }
+        let rows = self.child.estimated_cardinality();
+        (self.group_keys.iter())
+            .map(|key| self.estimated_distinct_values_of_key(key))
+            .fold(1, usize::saturating_mul)
+            .min(rows)
+    }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        let rows = self.estimated_cardinality();
+        match self.group_keys.get(index) {
+            Some(key) => self.estimated_distinct_values_of_key(key).min(rows),
+            None => rows,
+        }
     }
 }
 impl fmt::Display for LogicalAggregate {
@@ -111,21 +135,25 @@ mod tests {
                     kind: AggKind::Sum,
                     args: vec![],
                     return_type: DataTypeKind::Double.not_null(),
+                    distinct: false,
                 },
                 BoundAggCall {
                     kind: AggKind::Avg,
                     args: vec![],
                     return_type: DataTypeKind::Double.not_null(),
+                    distinct: false,
                 },
                 BoundAggCall {
                     kind: AggKind::Count,
                     args: vec![],
                     return_type: DataTypeKind::Double.not_null(),
+                    distinct: false,
                 },
                 BoundAggCall {
                     kind: AggKind::RowCount,
                     args: vec![],
                     return_type: DataTypeKind::Double.not_null(),
+                    distinct: false,
                 },
             ],
             vec![],
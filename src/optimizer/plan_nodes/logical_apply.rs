@@ -0,0 +1,101 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+use crate::binder::BoundJoinOperator;
+use crate::optimizer::logical_plan_rewriter::ExprRewriter;
+
+/// The logical plan of apply, which joins each row of the left side with the result of a subquery.
+///
+/// Different from [`LogicalJoin`], the right side may refer to columns of the left side through
+/// correlated references. The optimizer should decorrelate it into a [`LogicalJoin`].
+#[derive(Debug, Clone, Serialize)]
+pub struct LogicalApply {
+    left_plan: PlanRef,
+    right_plan: PlanRef,
+    join_op: BoundJoinOperator,
+    condition: BoundExpr,
+    schema: Vec<ColumnDesc>,
+}
+
+impl LogicalApply {
+    pub fn new(
+        left_plan: PlanRef,
+        right_plan: PlanRef,
+        join_op: BoundJoinOperator,
+        condition: BoundExpr,
+    ) -> Self {
+        let mut schema = left_plan.schema();
+        // semi and anti applies only output columns from the left side
+        if !matches!(
+            join_op,
+            BoundJoinOperator::LeftSemi | BoundJoinOperator::LeftAnti
+        ) {
+            schema.append(&mut right_plan.schema());
+        }
+        LogicalApply {
+            left_plan,
+            right_plan,
+            join_op,
+            condition,
+            schema,
+        }
+    }
+
+    /// Get a reference to the logical apply's join op.
+    pub fn join_op(&self) -> BoundJoinOperator {
+        self.join_op
+    }
+
+    /// Get a reference to the logical apply's condition.
+    pub fn condition(&self) -> &BoundExpr {
+        &self.condition
+    }
+
+    pub fn clone_with_rewrite_expr(
+        &self,
+        left: PlanRef,
+        right: PlanRef,
+        rewriter: &impl ExprRewriter,
+    ) -> Self {
+        let mut new_condition = self.condition.clone();
+        rewriter.rewrite_expr(&mut new_condition);
+        LogicalApply::new(left, right, self.join_op, new_condition)
+    }
+}
+impl PlanTreeNodeBinary for LogicalApply {
+    fn left(&self) -> PlanRef {
+        self.left_plan.clone()
+    }
+    fn right(&self) -> PlanRef {
+        self.right_plan.clone()
+    }
+
+    #[must_use]
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(left, right, self.join_op, self.condition.clone())
+    }
+}
+impl_plan_tree_node_for_binary!(LogicalApply);
+impl PlanNode for LogicalApply {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.schema.clone()
+    }
+
+    fn estimated_cardinality(&self) -> usize {
+        self.left().estimated_cardinality()
+    }
+}
+
+impl fmt::Display for LogicalApply {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "LogicalApply: op {:?}, condition {:?}",
+            self.join_op, self.condition
+        )
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+use crate::catalog::{ColumnDesc, TableRefId};
+
+/// The logical plan of a reference to a CTE.
+///
+/// The output of the CTE's plan is referred to as the virtual table `table_ref_id`. A CTE which is
+/// referenced more than once will be materialized once and shared by all references.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogicalCteScan {
+    cte_id: usize,
+    table_ref_id: TableRefId,
+    column_descs: Vec<ColumnDesc>,
+    child: PlanRef,
+}
+
+impl LogicalCteScan {
+    pub fn new(
+        cte_id: usize,
+        table_ref_id: TableRefId,
+        column_descs: Vec<ColumnDesc>,
+        child: PlanRef,
+    ) -> Self {
+        Self {
+            cte_id,
+            table_ref_id,
+            column_descs,
+            child,
+        }
+    }
+
+    /// Get a reference to the logical cte scan's cte id.
+    pub fn cte_id(&self) -> usize {
+        self.cte_id
+    }
+
+    /// Get a reference to the logical cte scan's table ref id.
+    pub fn table_ref_id(&self) -> TableRefId {
+        self.table_ref_id
+    }
+
+    /// Get a reference to the logical cte scan's column descs.
+    pub fn column_descs(&self) -> &[ColumnDesc] {
+        self.column_descs.as_ref()
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalCteScan {
+    fn child(&self) -> PlanRef {
+        self.child.clone()
+    }
+    #[must_use]
+    fn clone_with_child(&self, child: PlanRef) -> Self {
+        Self::new(
+            self.cte_id,
+            self.table_ref_id,
+            self.column_descs.clone(),
+            child,
+        )
+    }
+}
+impl_plan_tree_node_for_unary!(LogicalCteScan);
+impl PlanNode for LogicalCteScan {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.column_descs.clone()
+    }
+
+    fn estimated_cardinality(&self) -> usize {
+        self.child().estimated_cardinality()
+    }
+}
+
+impl fmt::Display for LogicalCteScan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "LogicalCteScan: cte #{}", self.cte_id)
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+use crate::binder::BoundExpr;
+use crate::optimizer::logical_plan_rewriter::ExprRewriter;
+
+/// The logical plan of `DISTINCT ON`.
+///
+/// The child must be sorted by the `DISTINCT ON` expressions. Only the first row of each group of
+/// rows with the same values of the expressions is output.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogicalDistinctOn {
+    distinct_on: Vec<BoundExpr>,
+    child: PlanRef,
+}
+
+impl LogicalDistinctOn {
+    pub fn new(distinct_on: Vec<BoundExpr>, child: PlanRef) -> Self {
+        LogicalDistinctOn { distinct_on, child }
+    }
+
+    /// Get a reference to the logical distinct on's expressions.
+    pub fn distinct_on(&self) -> &[BoundExpr] {
+        self.distinct_on.as_ref()
+    }
+
+    pub fn clone_with_rewrite_expr(
+        &self,
+        new_child: PlanRef,
+        rewriter: &impl ExprRewriter,
+    ) -> Self {
+        let mut new_exprs = self.distinct_on().to_vec();
+        for expr in &mut new_exprs {
+            rewriter.rewrite_expr(expr);
+        }
+        LogicalDistinctOn::new(new_exprs, new_child)
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalDistinctOn {
+    fn child(&self) -> PlanRef {
+        self.child.clone()
+    }
+    #[must_use]
+    fn clone_with_child(&self, child: PlanRef) -> Self {
+        Self::new(self.distinct_on().to_vec(), child)
+    }
+}
+impl_plan_tree_node_for_unary!(LogicalDistinctOn);
+impl PlanNode for LogicalDistinctOn {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.child.schema()
+    }
+
+    fn estimated_cardinality(&self) -> usize {
+        self.child().estimated_cardinality()
+    }
+}
+
+impl fmt::Display for LogicalDistinctOn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "LogicalDistinctOn: {:?}", self.distinct_on)
+    }
+}
@@ -8,6 +8,7 @@ use serde::Serialize;
 use super::*;
 use crate::binder::{BoundExpr, ExprVisitor};
 use crate::optimizer::logical_plan_rewriter::ExprRewriter;
+use crate::optimizer::{estimated_selectivity, round_rows};
 
 /// The logical plan of filter operation.
 #[derive(Debug, Clone, Serialize)]
@@ -52,7 +53,14 @@ impl PlanNode for LogicalFilter {
     }
 
     fn estimated_cardinality(&self) -> usize {
-        self.child().estimated_cardinality()
+        let selectivity = estimated_selectivity(&self.expr, &|index| {
+            self.child.estimated_distinct_values(index)
+        });
+        round_rows(self.child.estimated_cardinality() as f64 * selectivity)
+    }
+
+    fn ordering(&self) -> Vec<usize> {
+        self.child.ordering()
     }
 
     fn prune_col(&self, required_cols: BitSet) -> PlanRef {
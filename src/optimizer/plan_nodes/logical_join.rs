@@ -7,6 +7,7 @@ use serde::Serialize;
 use super::*;
 use crate::binder::BoundJoinOperator;
 use crate::optimizer::logical_plan_rewriter::ExprRewriter;
+use crate::optimizer::{estimated_selectivity, round_rows};
 
 /// The logical plan of join, it only records join tables and operators.
 ///
@@ -29,7 +30,13 @@ impl LogicalJoin {
         predicate: JoinPredicate,
     ) -> Self {
         let mut schema = left_plan.schema();
-        schema.append(&mut right_plan.schema());
+        // semi and anti joins only output columns from the left side
+        if !matches!(
+            join_op,
+            BoundJoinOperator::LeftSemi | BoundJoinOperator::LeftAnti
+        ) {
+            schema.append(&mut right_plan.schema());
+        }
         LogicalJoin {
             left_plan,
             right_plan,
@@ -95,8 +102,42 @@ impl PlanNode for LogicalJoin {
         self.schema.clone()
     }
 
+    /// The rows of the inner join are estimated from the selectivity of the condition on the
+    /// cross product of both sides.
     fn estimated_cardinality(&self) -> usize {
-        self.left().estimated_cardinality() * self.right().estimated_cardinality()
+        let left = self.left_plan.estimated_cardinality();
+        let right = self.right_plan.estimated_cardinality();
+        let left_col_num = self.left_plan.out_types().len();
+        let selectivity = estimated_selectivity(&self.predicate.to_on_clause(), &|index| {
+            if index < left_col_num {
+                self.left_plan.estimated_distinct_values(index)
+            } else {
+                self.right_plan
+                    .estimated_distinct_values(index - left_col_num)
+            }
+        });
+        let inner = round_rows(left as f64 * right as f64 * selectivity);
+        match self.join_op {
+            BoundJoinOperator::Inner => inner,
+            BoundJoinOperator::LeftOuter => inner.max(left),
+            BoundJoinOperator::RightOuter => inner.max(right),
+            BoundJoinOperator::FullOuter => inner.max(left).max(right),
+            BoundJoinOperator::LeftSemi => inner.min(left),
+            BoundJoinOperator::LeftAnti => left - inner.min(left),
+            BoundJoinOperator::LeftSingle => left,
+        }
+    }
+
+    /// The distinct values are not bounded by the rows of the join, so that the estimation of a
+    /// tree of joins does not visit the children again and again.
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        let left_col_num = self.left_plan.out_types().len();
+        if index < left_col_num {
+            self.left_plan.estimated_distinct_values(index)
+        } else {
+            self.right_plan
+                .estimated_distinct_values(index - left_col_num)
+        }
     }
 }
 
@@ -32,10 +32,6 @@ impl LogicalLimit {
     pub fn limit(&self) -> usize {
         self.limit
     }
-    #[allow(dead_code)]
-    fn estimated_cardinality(&self) -> usize {
-        self.limit
-    }
 }
 impl PlanTreeNodeUnary for LogicalLimit {
     fn child(&self) -> PlanRef {
@@ -51,6 +47,16 @@ impl PlanNode for LogicalLimit {
     fn schema(&self) -> Vec<ColumnDesc> {
         self.child.schema()
     }
+
+    fn estimated_cardinality(&self) -> usize {
+        (self.child.estimated_cardinality())
+            .saturating_sub(self.offset)
+            .min(self.limit)
+    }
+
+    fn ordering(&self) -> Vec<usize> {
+        self.child.ordering()
+    }
 }
 
 impl fmt::Display for LogicalLimit {
@@ -0,0 +1,64 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+use crate::binder::BoundAggCall;
+
+/// The logical plan of answering aggregations without group keys on a table from the metadata in
+/// the storage, such as the number of rows and the minimum and maximum values of the blocks,
+/// instead of scanning the table.
+///
+/// The aggregations are `count(*)`, `min` and `max` on the output columns of `scan`, which has no
+/// filter.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogicalMetadataScan {
+    agg_calls: Vec<BoundAggCall>,
+    /// The scan to aggregate on, if the metadata can not answer the aggregations.
+    scan: LogicalTableScan,
+}
+
+impl LogicalMetadataScan {
+    pub fn new(agg_calls: Vec<BoundAggCall>, scan: LogicalTableScan) -> Self {
+        Self { agg_calls, scan }
+    }
+
+    /// Get a reference to the logical metadata scan's agg calls.
+    pub fn agg_calls(&self) -> &[BoundAggCall] {
+        self.agg_calls.as_ref()
+    }
+
+    /// Get a reference to the logical metadata scan's scan.
+    pub fn scan(&self) -> &LogicalTableScan {
+        &self.scan
+    }
+}
+impl PlanTreeNodeLeaf for LogicalMetadataScan {}
+impl_plan_tree_node_for_leaf!(LogicalMetadataScan);
+
+impl PlanNode for LogicalMetadataScan {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.agg_calls
+            .iter()
+            .map(|agg_call| {
+                agg_call
+                    .return_type
+                    .clone()
+                    .to_column(format!("{}", agg_call.kind))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for LogicalMetadataScan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "LogicalMetadataScan: table #{}, {} agg calls",
+            self.scan.table_ref_id().table_id,
+            self.agg_calls.len()
+        )
+    }
+}
@@ -54,6 +54,10 @@ impl PlanNode for LogicalOrder {
     fn estimated_cardinality(&self) -> usize {
         self.child().estimated_cardinality()
     }
+
+    fn ordering(&self) -> Vec<usize> {
+        ordering_of_comparators(&self.comparators)
+    }
 }
 
 impl fmt::Display for LogicalOrder {
@@ -106,6 +106,27 @@ impl PlanNode for LogicalProjection {
     fn estimated_cardinality(&self) -> usize {
         self.child().estimated_cardinality()
     }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        match &self.project_expressions[index] {
+            BoundExpr::InputRef(input_ref) => self.child.estimated_distinct_values(input_ref.index),
+            BoundExpr::Constant(_) => 1,
+            _ => self.estimated_cardinality(),
+        }
+    }
+
+    /// The ordering of the child is kept as long as the sorted columns are projected.
+    fn ordering(&self) -> Vec<usize> {
+        self.child
+            .ordering()
+            .into_iter()
+            .map_while(|index| {
+                self.project_expressions.iter().position(|expr| {
+                    matches!(expr, BoundExpr::InputRef(input_ref) if input_ref.index == index)
+                })
+            })
+            .collect()
+    }
 }
 
 impl fmt::Display for LogicalProjection {
@@ -0,0 +1,74 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+
+/// The logical plan of a recursive CTE.
+///
+/// The left side is the non-recursive term. The right side is the recursive term, which reads the
+/// rows produced by the last iteration from [`LogicalWorkTableScan`]s of the same CTE.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogicalRecursiveUnion {
+    cte_id: usize,
+    union_all: bool,
+    left_plan: PlanRef,
+    right_plan: PlanRef,
+}
+
+impl LogicalRecursiveUnion {
+    pub fn new(cte_id: usize, union_all: bool, left_plan: PlanRef, right_plan: PlanRef) -> Self {
+        Self {
+            cte_id,
+            union_all,
+            left_plan,
+            right_plan,
+        }
+    }
+
+    /// Get a reference to the logical recursive union's cte id.
+    pub fn cte_id(&self) -> usize {
+        self.cte_id
+    }
+
+    /// Get a reference to the logical recursive union's union all.
+    pub fn union_all(&self) -> bool {
+        self.union_all
+    }
+}
+
+impl PlanTreeNodeBinary for LogicalRecursiveUnion {
+    fn left(&self) -> PlanRef {
+        self.left_plan.clone()
+    }
+    fn right(&self) -> PlanRef {
+        self.right_plan.clone()
+    }
+
+    #[must_use]
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(self.cte_id, self.union_all, left, right)
+    }
+}
+impl_plan_tree_node_for_binary!(LogicalRecursiveUnion);
+impl PlanNode for LogicalRecursiveUnion {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.left_plan.schema()
+    }
+
+    fn estimated_cardinality(&self) -> usize {
+        self.left().estimated_cardinality()
+    }
+}
+
+impl fmt::Display for LogicalRecursiveUnion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "LogicalRecursiveUnion: cte #{}, all {}",
+            self.cte_id, self.union_all
+        )
+    }
+}
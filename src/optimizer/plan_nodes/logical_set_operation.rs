@@ -0,0 +1,110 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+use crate::catalog::{ColumnDesc, TableRefId};
+use crate::parser::SetOperator;
+
+/// The logical plan of a set operation, which combines the rows of its two children.
+///
+/// The output is referred to as the virtual table `table_ref_id`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogicalSetOperation {
+    op: SetOperator,
+    all: bool,
+    table_ref_id: TableRefId,
+    column_descs: Vec<ColumnDesc>,
+    left_plan: PlanRef,
+    right_plan: PlanRef,
+}
+
+impl LogicalSetOperation {
+    pub fn new(
+        op: SetOperator,
+        all: bool,
+        table_ref_id: TableRefId,
+        column_descs: Vec<ColumnDesc>,
+        left_plan: PlanRef,
+        right_plan: PlanRef,
+    ) -> Self {
+        Self {
+            op,
+            all,
+            table_ref_id,
+            column_descs,
+            left_plan,
+            right_plan,
+        }
+    }
+
+    /// Get a reference to the logical set operation's op.
+    pub fn op(&self) -> &SetOperator {
+        &self.op
+    }
+
+    /// Get a reference to the logical set operation's all.
+    pub fn all(&self) -> bool {
+        self.all
+    }
+
+    /// Get a reference to the logical set operation's table ref id.
+    pub fn table_ref_id(&self) -> TableRefId {
+        self.table_ref_id
+    }
+
+    /// Get a reference to the logical set operation's column descs.
+    pub fn column_descs(&self) -> &[ColumnDesc] {
+        self.column_descs.as_ref()
+    }
+}
+
+impl PlanTreeNodeBinary for LogicalSetOperation {
+    fn left(&self) -> PlanRef {
+        self.left_plan.clone()
+    }
+    fn right(&self) -> PlanRef {
+        self.right_plan.clone()
+    }
+
+    #[must_use]
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(
+            self.op.clone(),
+            self.all,
+            self.table_ref_id,
+            self.column_descs.clone(),
+            left,
+            right,
+        )
+    }
+}
+impl_plan_tree_node_for_binary!(LogicalSetOperation);
+impl PlanNode for LogicalSetOperation {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.column_descs.clone()
+    }
+
+    fn estimated_cardinality(&self) -> usize {
+        let left = self.left().estimated_cardinality();
+        let right = self.right().estimated_cardinality();
+        match self.op {
+            SetOperator::Union => left + right,
+            SetOperator::Intersect => left.min(right),
+            SetOperator::Except => left,
+        }
+    }
+}
+
+impl fmt::Display for LogicalSetOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "LogicalSetOperation: {}{}",
+            self.op,
+            if self.all { " ALL" } else { "" }
+        )
+    }
+}
@@ -7,7 +7,8 @@ use serde::Serialize;
 
 use super::*;
 use crate::catalog::{ColumnDesc, TableRefId};
-use crate::types::ColumnId;
+use crate::optimizer::{estimated_selectivity, round_rows, TableStatistics, DEFAULT_ROW_COUNT};
+use crate::types::{ColumnId, DataValue};
 /// The logical plan of sequential scan operation.
 #[derive(Debug, Clone, Serialize)]
 pub struct LogicalTableScan {
@@ -17,6 +18,13 @@ pub struct LogicalTableScan {
     with_row_handler: bool,
     is_sorted: bool,
     expr: Option<BoundExpr>,
+    /// The range of the primary key to scan, which is inclusive on both ends. An empty key means
+    /// the range is unbounded on that end.
+    begin_sort_key: Vec<DataValue>,
+    end_sort_key: Vec<DataValue>,
+    /// The statistics of the table, from which the cardinalities are estimated.
+    #[serde(skip)]
+    statistics: Option<TableStatistics>,
 }
 
 impl LogicalTableScan {
@@ -35,9 +43,32 @@ impl LogicalTableScan {
             with_row_handler,
             is_sorted,
             expr,
+            begin_sort_key: vec![],
+            end_sort_key: vec![],
+            statistics: None,
         }
     }
 
+    /// Scan the rows whose primary key is in the range.
+    #[must_use]
+    pub fn with_sort_key_range(
+        self,
+        begin_sort_key: Vec<DataValue>,
+        end_sort_key: Vec<DataValue>,
+    ) -> Self {
+        Self {
+            begin_sort_key,
+            end_sort_key,
+            ..self
+        }
+    }
+
+    /// Attach the statistics of the table.
+    #[must_use]
+    pub fn with_statistics(self, statistics: Option<TableStatistics>) -> Self {
+        Self { statistics, ..self }
+    }
+
     /// Get a reference to the logical table scan's table ref id.
     pub fn table_ref_id(&self) -> TableRefId {
         self.table_ref_id
@@ -67,6 +98,37 @@ impl LogicalTableScan {
     pub fn expr(&self) -> Option<&BoundExpr> {
         self.expr.as_ref()
     }
+
+    /// Get a reference to the logical table scan's begin sort key.
+    pub fn begin_sort_key(&self) -> &[DataValue] {
+        &self.begin_sort_key
+    }
+
+    /// Get a reference to the logical table scan's end sort key.
+    pub fn end_sort_key(&self) -> &[DataValue] {
+        &self.end_sort_key
+    }
+
+    /// Get a reference to the logical table scan's statistics.
+    pub fn statistics(&self) -> Option<&TableStatistics> {
+        self.statistics.as_ref()
+    }
+
+    /// The number of rows of the table, or [`DEFAULT_ROW_COUNT`] without statistics.
+    fn table_rows(&self) -> usize {
+        self.statistics
+            .as_ref()
+            .map_or(DEFAULT_ROW_COUNT, |statistics| statistics.row_count)
+    }
+
+    /// The number of distinct values of the column in the table. Columns without statistics are
+    /// assumed to be unique.
+    fn table_distinct_values(&self, index: usize) -> usize {
+        let rows = self.table_rows();
+        (self.statistics.as_ref().zip(self.column_ids.get(index)))
+            .and_then(|(statistics, id)| statistics.distinct_values.get(id))
+            .map_or(rows, |distinct_values| (*distinct_values).min(rows))
+    }
 }
 impl PlanTreeNodeLeaf for LogicalTableScan {}
 impl_plan_tree_node_for_leaf!(LogicalTableScan);
@@ -76,9 +138,33 @@ impl PlanNode for LogicalTableScan {
         self.column_descs.clone()
     }
 
-    // TODO: get statistics from storage system
     fn estimated_cardinality(&self) -> usize {
-        1
+        let rows = self.table_rows();
+        match &self.expr {
+            Some(expr) => {
+                let selectivity =
+                    estimated_selectivity(expr, &|index| self.table_distinct_values(index));
+                round_rows(rows as f64 * selectivity)
+            }
+            None => rows,
+        }
+    }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        self.table_distinct_values(index)
+            .min(self.estimated_cardinality())
+    }
+
+    /// A sorted scan is sorted on the primary key.
+    fn ordering(&self) -> Vec<usize> {
+        if !self.is_sorted {
+            return vec![];
+        }
+        self.column_descs
+            .iter()
+            .position(|desc| desc.is_primary())
+            .into_iter()
+            .collect()
     }
 
     fn prune_col(&self, required_cols: BitSet) -> PlanRef {
@@ -98,6 +184,9 @@ impl PlanNode for LogicalTableScan {
             with_row_handler: self.with_row_handler,
             is_sorted: self.is_sorted,
             expr: self.expr.clone(),
+            begin_sort_key: self.begin_sort_key.clone(),
+            end_sort_key: self.end_sort_key.clone(),
+            statistics: self.statistics.clone(),
         }
         .into_plan_ref()
     }
@@ -67,7 +67,13 @@ impl PlanNode for LogicalTopN {
     }
 
     fn estimated_cardinality(&self) -> usize {
-        self.limit
+        (self.child.estimated_cardinality())
+            .saturating_sub(self.offset)
+            .min(self.limit)
+    }
+
+    fn ordering(&self) -> Vec<usize> {
+        ordering_of_comparators(&self.comparators)
     }
 }
 
@@ -47,7 +47,11 @@ impl LogicalValues {
 impl PlanTreeNodeLeaf for LogicalValues {}
 impl_plan_tree_node_for_leaf!(LogicalValues);
 
-impl PlanNode for LogicalValues {}
+impl PlanNode for LogicalValues {
+    fn estimated_cardinality(&self) -> usize {
+        self.values.len()
+    }
+}
 
 impl fmt::Display for LogicalValues {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
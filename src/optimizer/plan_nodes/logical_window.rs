@@ -0,0 +1,84 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+use crate::binder::BoundWindowCall;
+use crate::optimizer::logical_plan_rewriter::ExprRewriter;
+
+/// The logical plan of window functions.
+///
+/// It outputs all columns of the child, followed by one column for each window call.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogicalWindow {
+    window_calls: Vec<BoundWindowCall>,
+    child: PlanRef,
+}
+
+impl LogicalWindow {
+    pub fn new(window_calls: Vec<BoundWindowCall>, child: PlanRef) -> Self {
+        LogicalWindow {
+            window_calls,
+            child,
+        }
+    }
+
+    /// Get a reference to the logical window's window calls.
+    pub fn window_calls(&self) -> &[BoundWindowCall] {
+        self.window_calls.as_ref()
+    }
+
+    pub fn clone_with_rewrite_expr(
+        &self,
+        new_child: PlanRef,
+        rewriter: &impl ExprRewriter,
+    ) -> Self {
+        let mut new_calls = self.window_calls().to_vec();
+        for call in &mut new_calls {
+            for arg in &mut call.args {
+                rewriter.rewrite_expr(arg);
+            }
+            for expr in &mut call.partition_by {
+                rewriter.rewrite_expr(expr);
+            }
+            for order in &mut call.order_by {
+                rewriter.rewrite_expr(&mut order.expr);
+            }
+        }
+        LogicalWindow::new(new_calls, new_child)
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalWindow {
+    fn child(&self) -> PlanRef {
+        self.child.clone()
+    }
+    #[must_use]
+    fn clone_with_child(&self, child: PlanRef) -> Self {
+        Self::new(self.window_calls().to_vec(), child)
+    }
+}
+impl_plan_tree_node_for_unary!(LogicalWindow);
+impl PlanNode for LogicalWindow {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        let mut schema = self.child.schema();
+        schema.extend(
+            self.window_calls
+                .iter()
+                .map(|call| call.return_type.clone().to_column(format!("{}", call.kind))),
+        );
+        schema
+    }
+
+    fn estimated_cardinality(&self) -> usize {
+        self.child().estimated_cardinality()
+    }
+}
+
+impl fmt::Display for LogicalWindow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "LogicalWindow: {} window calls", self.window_calls.len())
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+use crate::catalog::{ColumnDesc, TableRefId};
+
+/// The logical plan of scanning the working table of a recursive CTE, which holds the rows
+/// produced by the last iteration.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogicalWorkTableScan {
+    cte_id: usize,
+    table_ref_id: TableRefId,
+    column_descs: Vec<ColumnDesc>,
+}
+
+impl LogicalWorkTableScan {
+    pub fn new(cte_id: usize, table_ref_id: TableRefId, column_descs: Vec<ColumnDesc>) -> Self {
+        Self {
+            cte_id,
+            table_ref_id,
+            column_descs,
+        }
+    }
+
+    /// Get a reference to the logical work table scan's cte id.
+    pub fn cte_id(&self) -> usize {
+        self.cte_id
+    }
+
+    /// Get a reference to the logical work table scan's table ref id.
+    pub fn table_ref_id(&self) -> TableRefId {
+        self.table_ref_id
+    }
+
+    /// Get a reference to the logical work table scan's column descs.
+    pub fn column_descs(&self) -> &[ColumnDesc] {
+        self.column_descs.as_ref()
+    }
+}
+impl PlanTreeNodeLeaf for LogicalWorkTableScan {}
+impl_plan_tree_node_for_leaf!(LogicalWorkTableScan);
+
+impl PlanNode for LogicalWorkTableScan {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.column_descs.clone()
+    }
+}
+
+impl fmt::Display for LogicalWorkTableScan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "LogicalWorkTableScan: cte #{}", self.cte_id)
+    }
+}
@@ -21,7 +21,7 @@ use downcast_rs::{impl_downcast, Downcast};
 use erased_serde::serialize_trait_object;
 use paste::paste;
 
-use crate::binder::{BoundExpr, BoundInputRef};
+use crate::binder::{BoundExpr, BoundInputRef, BoundOrderBy};
 use crate::types::DataType;
 
 mod plan_tree_node;
@@ -34,25 +34,36 @@ pub use join_predicate::*;
 mod dummy;
 mod internal;
 mod logical_aggregate;
+mod logical_apply;
 mod logical_copy_from_file;
 mod logical_copy_to_file;
 mod logical_create_table;
+mod logical_cte_scan;
 mod logical_delete;
+mod logical_distinct_on;
 mod logical_drop;
 mod logical_explain;
 mod logical_filter;
 mod logical_insert;
 mod logical_join;
 mod logical_limit;
+mod logical_metadata_scan;
 mod logical_order;
 mod logical_projection;
+mod logical_recursive_union;
+mod logical_set_operation;
 mod logical_table_scan;
 mod logical_top_n;
+mod logical_update;
 mod logical_values;
+mod logical_window;
+mod logical_work_table_scan;
 mod physical_copy_from_file;
 mod physical_copy_to_file;
 mod physical_create_table;
+mod physical_cte_scan;
 mod physical_delete;
+mod physical_distinct_on;
 mod physical_drop;
 mod physical_explain;
 mod physical_filter;
@@ -60,36 +71,55 @@ mod physical_hash_agg;
 mod physical_hash_join;
 mod physical_insert;
 mod physical_limit;
+mod physical_metadata_scan;
 mod physical_nested_loop_join;
 mod physical_order;
 mod physical_projection;
+mod physical_recursive_union;
+mod physical_set_operation;
 mod physical_simple_agg;
+mod physical_sort_agg;
+mod physical_sort_merge_join;
 mod physical_table_scan;
 mod physical_top_n;
+mod physical_update;
 mod physical_values;
+mod physical_window;
+mod physical_work_table_scan;
 
 pub use dummy::*;
 pub use internal::*;
 pub use logical_aggregate::*;
+pub use logical_apply::*;
 pub use logical_copy_from_file::*;
 pub use logical_copy_to_file::*;
 pub use logical_create_table::*;
+pub use logical_cte_scan::*;
 pub use logical_delete::*;
+pub use logical_distinct_on::*;
 pub use logical_drop::*;
 pub use logical_explain::*;
 pub use logical_filter::*;
 pub use logical_insert::*;
 pub use logical_join::*;
 pub use logical_limit::*;
+pub use logical_metadata_scan::*;
 pub use logical_order::*;
 pub use logical_projection::*;
+pub use logical_recursive_union::*;
+pub use logical_set_operation::*;
 pub use logical_table_scan::*;
 pub use logical_top_n::*;
+pub use logical_update::*;
 pub use logical_values::*;
+pub use logical_window::*;
+pub use logical_work_table_scan::*;
 pub use physical_copy_from_file::*;
 pub use physical_copy_to_file::*;
 pub use physical_create_table::*;
+pub use physical_cte_scan::*;
 pub use physical_delete::*;
+pub use physical_distinct_on::*;
 pub use physical_drop::*;
 pub use physical_explain::*;
 pub use physical_filter::*;
@@ -97,13 +127,21 @@ pub use physical_hash_agg::*;
 pub use physical_hash_join::*;
 pub use physical_insert::*;
 pub use physical_limit::*;
+pub use physical_metadata_scan::*;
 pub use physical_nested_loop_join::*;
 pub use physical_order::*;
 pub use physical_projection::*;
+pub use physical_recursive_union::*;
+pub use physical_set_operation::*;
 pub use physical_simple_agg::*;
+pub use physical_sort_agg::*;
+pub use physical_sort_merge_join::*;
 pub use physical_table_scan::*;
 pub use physical_top_n::*;
+pub use physical_update::*;
 pub use physical_values::*;
+pub use physical_window::*;
+pub use physical_work_table_scan::*;
 
 use crate::catalog::ColumnDesc;
 
@@ -149,6 +187,25 @@ pub trait PlanNode:
     fn estimated_cardinality(&self) -> usize {
         1
     }
+
+    /// Estimated number of distinct values in the output column. By default, the columns of the
+    /// first child are passed through.
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        let rows = self.estimated_cardinality();
+        match self.children().first() {
+            Some(child) if index < child.out_types().len() => {
+                child.estimated_distinct_values(index).min(rows)
+            }
+            _ => rows,
+        }
+    }
+
+    /// The indexes of the output columns that the output rows are sorted on, in ascending order
+    /// with NULLs last. Empty if the output is not known to be sorted.
+    fn ordering(&self) -> Vec<usize> {
+        vec![]
+    }
+
     /// transform the plan node to only output the required columns ordered by index number, only
     /// logical plan node will use it, though all plan node impl it.
     fn prune_col(&self, required_cols: BitSet) -> PlanRef {
@@ -185,6 +242,12 @@ impl dyn PlanNode {
         let indented_self =
             format!("{}", self).replace("\n  ", &format!("\n{}", " ".repeat(level * 2 + 4)));
         write!(f, "{}{}", " ".repeat(level * 2), indented_self)?;
+        writeln!(
+            f,
+            "{}estimated rows: {}",
+            " ".repeat(level * 2 + 4),
+            self.estimated_cardinality()
+        )?;
         for child in self.children() {
             child.explain(level + 1, f)?;
         }
@@ -194,6 +257,20 @@ impl dyn PlanNode {
 
 serialize_trait_object!(PlanNode);
 
+/// The ordering of the rows sorted by the comparators, which is the leading comparators on columns
+/// in ascending order with NULLs last.
+fn ordering_of_comparators(comparators: &[BoundOrderBy]) -> Vec<usize> {
+    comparators
+        .iter()
+        .map_while(|comparator| match &comparator.expr {
+            BoundExpr::InputRef(input_ref) if !comparator.descending && !comparator.nulls_first => {
+                Some(input_ref.index)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 /// All Plan nodes
 ///
 /// You can use it as follows:
@@ -220,13 +297,22 @@ macro_rules! for_all_plan_nodes {
             LogicalFilter,
             LogicalExplain,
             LogicalJoin,
+            LogicalApply,
             LogicalAggregate,
             LogicalOrder,
             LogicalLimit,
             LogicalTopN,
             LogicalDelete,
+            LogicalUpdate,
             LogicalCopyFromFile,
             LogicalCopyToFile,
+            LogicalCteScan,
+            LogicalRecursiveUnion,
+            LogicalWorkTableScan,
+            LogicalSetOperation,
+            LogicalWindow,
+            LogicalDistinctOn,
+            LogicalMetadataScan,
             PhysicalTableScan,
             PhysicalInsert,
             PhysicalValues,
@@ -243,8 +329,18 @@ macro_rules! for_all_plan_nodes {
             PhysicalLimit,
             PhysicalTopN,
             PhysicalDelete,
+            PhysicalUpdate,
             PhysicalCopyFromFile,
-            PhysicalCopyToFile
+            PhysicalCopyToFile,
+            PhysicalCteScan,
+            PhysicalRecursiveUnion,
+            PhysicalWorkTableScan,
+            PhysicalSetOperation,
+            PhysicalWindow,
+            PhysicalDistinctOn,
+            PhysicalSortAgg,
+            PhysicalSortMergeJoin,
+            PhysicalMetadataScan
         }
     };
 }
@@ -255,6 +351,7 @@ pub trait WithPlanNodeType {
 macro_rules! enum_plan_node_type {
     ([], $($node_name:ident),*) => {
         /// each enum value represent a [`PlanNode`] struct type, help us to dispatch and downcast
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub enum PlanNodeType {
             $( $node_name ),*
         }
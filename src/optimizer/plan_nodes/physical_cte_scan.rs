@@ -0,0 +1,52 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+
+/// The physical plan of a reference to a materialized CTE.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhysicalCteScan {
+    logical: LogicalCteScan,
+}
+
+impl PhysicalCteScan {
+    pub fn new(logical: LogicalCteScan) -> Self {
+        Self { logical }
+    }
+
+    /// Get a reference to the physical cte scan's logical.
+    pub fn logical(&self) -> &LogicalCteScan {
+        &self.logical
+    }
+}
+impl PlanTreeNodeUnary for PhysicalCteScan {
+    fn child(&self) -> PlanRef {
+        self.logical.child()
+    }
+    #[must_use]
+    fn clone_with_child(&self, child: PlanRef) -> Self {
+        Self::new(self.logical().clone_with_child(child))
+    }
+}
+impl_plan_tree_node_for_unary!(PhysicalCteScan);
+impl PlanNode for PhysicalCteScan {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.logical.schema()
+    }
+
+    fn estimated_cardinality(&self) -> usize {
+        self.logical().estimated_cardinality()
+    }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        self.logical().estimated_distinct_values(index)
+    }
+}
+impl fmt::Display for PhysicalCteScan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "PhysicalCteScan: cte #{}", self.logical().cte_id())
+    }
+}
@@ -38,7 +38,11 @@ impl PlanNode for PhysicalHashAgg {
     }
 
     fn estimated_cardinality(&self) -> usize {
-        self.child().estimated_cardinality()
+        self.logical().estimated_cardinality()
+    }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        self.logical().estimated_distinct_values(index)
     }
 }
 impl fmt::Display for PhysicalHashAgg {
@@ -0,0 +1,46 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+
+/// The physical plan of answering aggregations from the metadata in the storage.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhysicalMetadataScan {
+    logical: LogicalMetadataScan,
+}
+
+impl PhysicalMetadataScan {
+    pub fn new(logical: LogicalMetadataScan) -> Self {
+        Self { logical }
+    }
+
+    /// Get a reference to the physical metadata scan's logical.
+    pub fn logical(&self) -> &LogicalMetadataScan {
+        &self.logical
+    }
+}
+
+impl PlanTreeNodeLeaf for PhysicalMetadataScan {}
+impl_plan_tree_node_for_leaf!(PhysicalMetadataScan);
+impl PlanNode for PhysicalMetadataScan {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.logical().schema()
+    }
+}
+
+impl fmt::Display for PhysicalMetadataScan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "PhysicalMetadataScan: table #{}",
+            self.logical().scan().table_ref_id().table_id
+        )?;
+        for agg in self.logical().agg_calls() {
+            writeln!(f, "  {}", agg)?
+        }
+        Ok(())
+    }
+}
@@ -44,7 +44,11 @@ impl PlanNode for PhysicalNestedLoopJoin {
     }
 
     fn estimated_cardinality(&self) -> usize {
-        self.left().estimated_cardinality() * self.right().estimated_cardinality()
+        self.logical().estimated_cardinality()
+    }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        self.logical().estimated_distinct_values(index)
     }
 }
 /// Currently, we only use default join ordering.
@@ -40,7 +40,15 @@ impl PlanNode for PhysicalOrder {
     }
 
     fn estimated_cardinality(&self) -> usize {
-        self.child().estimated_cardinality()
+        self.logical().estimated_cardinality()
+    }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        self.logical().estimated_distinct_values(index)
+    }
+
+    fn ordering(&self) -> Vec<usize> {
+        self.logical().ordering()
     }
 }
 impl fmt::Display for PhysicalOrder {
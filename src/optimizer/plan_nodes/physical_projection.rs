@@ -21,10 +21,6 @@ impl PhysicalProjection {
     pub fn logical(&self) -> &LogicalProjection {
         &self.logical
     }
-    #[allow(dead_code)]
-    fn estimated_cardinality(&self) -> usize {
-        self.child().estimated_cardinality()
-    }
 }
 
 impl PlanTreeNodeUnary for PhysicalProjection {
@@ -41,6 +37,18 @@ impl PlanNode for PhysicalProjection {
     fn schema(&self) -> Vec<ColumnDesc> {
         self.logical().schema()
     }
+
+    fn estimated_cardinality(&self) -> usize {
+        self.logical().estimated_cardinality()
+    }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        self.logical().estimated_distinct_values(index)
+    }
+
+    fn ordering(&self) -> Vec<usize> {
+        self.logical().ordering()
+    }
 }
 
 impl fmt::Display for PhysicalProjection {
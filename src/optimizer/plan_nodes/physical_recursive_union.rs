@@ -0,0 +1,62 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+
+/// The physical plan of a recursive CTE.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhysicalRecursiveUnion {
+    logical: LogicalRecursiveUnion,
+}
+
+impl PhysicalRecursiveUnion {
+    pub fn new(logical: LogicalRecursiveUnion) -> Self {
+        Self { logical }
+    }
+
+    /// Get a reference to the physical recursive union's logical.
+    pub fn logical(&self) -> &LogicalRecursiveUnion {
+        &self.logical
+    }
+}
+
+impl PlanTreeNodeBinary for PhysicalRecursiveUnion {
+    fn left(&self) -> PlanRef {
+        self.logical.left()
+    }
+    fn right(&self) -> PlanRef {
+        self.logical.right()
+    }
+
+    #[must_use]
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_left_right(left, right))
+    }
+}
+impl_plan_tree_node_for_binary!(PhysicalRecursiveUnion);
+impl PlanNode for PhysicalRecursiveUnion {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.logical.schema()
+    }
+
+    fn estimated_cardinality(&self) -> usize {
+        self.logical().estimated_cardinality()
+    }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        self.logical().estimated_distinct_values(index)
+    }
+}
+impl fmt::Display for PhysicalRecursiveUnion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "PhysicalRecursiveUnion: cte #{}, all {}",
+            self.logical().cte_id(),
+            self.logical().union_all()
+        )
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+
+/// The physical plan of a set operation.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhysicalSetOperation {
+    logical: LogicalSetOperation,
+}
+
+impl PhysicalSetOperation {
+    pub fn new(logical: LogicalSetOperation) -> Self {
+        Self { logical }
+    }
+
+    /// Get a reference to the physical set operation's logical.
+    pub fn logical(&self) -> &LogicalSetOperation {
+        &self.logical
+    }
+}
+
+impl PlanTreeNodeBinary for PhysicalSetOperation {
+    fn left(&self) -> PlanRef {
+        self.logical.left()
+    }
+    fn right(&self) -> PlanRef {
+        self.logical.right()
+    }
+
+    #[must_use]
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_left_right(left, right))
+    }
+}
+impl_plan_tree_node_for_binary!(PhysicalSetOperation);
+impl PlanNode for PhysicalSetOperation {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.logical.schema()
+    }
+
+    fn estimated_cardinality(&self) -> usize {
+        self.logical().estimated_cardinality()
+    }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        self.logical().estimated_distinct_values(index)
+    }
+}
+impl fmt::Display for PhysicalSetOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "PhysicalSetOperation: {}{}",
+            self.logical().op(),
+            if self.logical().all() { " ALL" } else { "" }
+        )
+    }
+}
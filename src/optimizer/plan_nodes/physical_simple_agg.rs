@@ -48,7 +48,7 @@ impl PlanNode for PhysicalSimpleAgg {
     }
 
     fn estimated_cardinality(&self) -> usize {
-        self.child().estimated_cardinality()
+        1
     }
 }
 
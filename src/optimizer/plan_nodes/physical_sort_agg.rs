@@ -0,0 +1,73 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+
+/// The physical plan of sort aggregation, whose child is sorted by the group keys.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhysicalSortAgg {
+    logical: LogicalAggregate,
+}
+
+impl PhysicalSortAgg {
+    pub fn new(logical: LogicalAggregate) -> Self {
+        Self { logical }
+    }
+
+    /// Get a reference to the physical sort agg's logical.
+    pub fn logical(&self) -> &LogicalAggregate {
+        &self.logical
+    }
+}
+impl PlanTreeNodeUnary for PhysicalSortAgg {
+    fn child(&self) -> PlanRef {
+        self.logical.child()
+    }
+    #[must_use]
+    fn clone_with_child(&self, child: PlanRef) -> Self {
+        Self::new(self.logical().clone_with_child(child))
+    }
+}
+impl_plan_tree_node_for_unary!(PhysicalSortAgg);
+impl PlanNode for PhysicalSortAgg {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.logical.schema()
+    }
+
+    fn estimated_cardinality(&self) -> usize {
+        self.logical().estimated_cardinality()
+    }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        self.logical().estimated_distinct_values(index)
+    }
+
+    /// The groups are output in the order of the child, with the group keys in the front.
+    fn ordering(&self) -> Vec<usize> {
+        let group_keys = self.logical().group_keys();
+        self.child()
+            .ordering()
+            .into_iter()
+            .map_while(|index| {
+                group_keys.iter().position(
+                    |key| matches!(key, BoundExpr::InputRef(input_ref) if input_ref.index == index),
+                )
+            })
+            .collect()
+    }
+}
+impl fmt::Display for PhysicalSortAgg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "PhysicalSortAgg:")?;
+        for group_key in self.logical().group_keys().iter() {
+            writeln!(f, "  {}", group_key)?
+        }
+        for agg in self.logical().agg_calls().iter() {
+            writeln!(f, "  {}", agg)?
+        }
+        Ok(())
+    }
+}
@@ -31,9 +31,16 @@ impl PlanNode for PhysicalTableScan {
     fn schema(&self) -> Vec<ColumnDesc> {
         self.logical().schema()
     }
-    // TODO: get statistics from storage system
     fn estimated_cardinality(&self) -> usize {
-        1
+        self.logical().estimated_cardinality()
+    }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        self.logical().estimated_distinct_values(index)
+    }
+
+    fn ordering(&self) -> Vec<usize> {
+        self.logical().ordering()
     }
 }
 
@@ -39,7 +39,15 @@ impl PlanNode for PhysicalTopN {
     }
 
     fn estimated_cardinality(&self) -> usize {
-        self.logical().limit()
+        self.logical().estimated_cardinality()
+    }
+
+    fn estimated_distinct_values(&self, index: usize) -> usize {
+        self.logical().estimated_distinct_values(index)
+    }
+
+    fn ordering(&self) -> Vec<usize> {
+        self.logical().ordering()
     }
 }
 
@@ -0,0 +1,53 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+
+/// The physical plan of `UPDATE`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhysicalUpdate {
+    logical: LogicalUpdate,
+}
+
+impl PhysicalUpdate {
+    pub fn new(logical: LogicalUpdate) -> Self {
+        Self { logical }
+    }
+
+    /// Get a reference to the physical update's logical.
+    pub fn logical(&self) -> &LogicalUpdate {
+        &self.logical
+    }
+}
+
+impl PlanTreeNodeUnary for PhysicalUpdate {
+    fn child(&self) -> PlanRef {
+        self.logical.child()
+    }
+    #[must_use]
+    fn clone_with_child(&self, child: PlanRef) -> Self {
+        Self::new(self.logical().clone_with_child(child))
+    }
+}
+impl_plan_tree_node_for_unary!(PhysicalUpdate);
+impl PlanNode for PhysicalUpdate {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.logical().schema()
+    }
+}
+impl fmt::Display for PhysicalUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "PhysicalUpdate: table {}",
+            self.logical().table_ref_id().table_id
+        )?;
+        for expr in self.logical().value_exprs().iter() {
+            writeln!(f, "  {}", expr)?
+        }
+        Ok(())
+    }
+}
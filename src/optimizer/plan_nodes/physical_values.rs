@@ -29,6 +29,10 @@ impl PlanNode for PhysicalValues {
     fn schema(&self) -> Vec<ColumnDesc> {
         self.logical().schema()
     }
+
+    fn estimated_cardinality(&self) -> usize {
+        self.logical().estimated_cardinality()
+    }
 }
 impl fmt::Display for PhysicalValues {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -0,0 +1,37 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::*;
+
+/// The physical plan of scanning the working table of a recursive CTE.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhysicalWorkTableScan {
+    logical: LogicalWorkTableScan,
+}
+
+impl PhysicalWorkTableScan {
+    pub fn new(logical: LogicalWorkTableScan) -> Self {
+        Self { logical }
+    }
+
+    /// Get a reference to the physical work table scan's logical.
+    pub fn logical(&self) -> &LogicalWorkTableScan {
+        &self.logical
+    }
+}
+
+impl PlanTreeNodeLeaf for PhysicalWorkTableScan {}
+impl_plan_tree_node_for_leaf!(PhysicalWorkTableScan);
+impl PlanNode for PhysicalWorkTableScan {
+    fn schema(&self) -> Vec<ColumnDesc> {
+        self.logical().schema()
+    }
+}
+impl fmt::Display for PhysicalWorkTableScan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "PhysicalWorkTableScan: cte #{}", self.logical().cte_id())
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::sync::Arc;
+
+use super::*;
+use crate::optimizer::logical_plan_rewriter::is_sorted_by_group_keys;
+use crate::optimizer::plan_nodes::{
+    PhysicalHashAgg, PhysicalSimpleAgg, PhysicalSortAgg, PlanTreeNodeUnary,
+};
+
+/// Implement aggregations by simple aggregations if there is no group key, otherwise by hash
+/// aggregations, and sort aggregations if the input is sorted by the group keys.
+pub struct AggImplementationRule {}
+
+impl ImplementationRule for AggImplementationRule {
+    fn implement(&self, plan: &PlanRef) -> Vec<PlanRef> {
+        let agg = match plan.as_logical_aggregate() {
+            Ok(agg) => agg,
+            Err(_) => return vec![],
+        };
+        if agg.group_keys().is_empty() {
+            return vec![Arc::new(PhysicalSimpleAgg::new(
+                agg.agg_calls().to_vec(),
+                agg.child(),
+            ))];
+        }
+        let mut plans: Vec<PlanRef> = vec![Arc::new(PhysicalHashAgg::new(agg.clone()))];
+        if is_sorted_by_group_keys(agg) {
+            plans.push(Arc::new(PhysicalSortAgg::new(agg.clone())));
+        }
+        plans
+    }
+}
@@ -19,6 +19,14 @@ impl Rule for FilterJoinRule {
         let filter = plan.as_logical_filter()?;
         let child = filter.child();
         let join = child.as_logical_join()?;
+        // the output of semi and anti joins is their left side, on which the filter is evaluated
+        if matches!(
+            join.join_op(),
+            BoundJoinOperator::LeftSemi | BoundJoinOperator::LeftAnti
+        ) {
+            let left = Arc::new(LogicalFilter::new(filter.expr().clone(), join.left()));
+            return Ok(Arc::new(join.clone_with_left_right(left, join.right())));
+        }
         if join.join_op() != BoundJoinOperator::Inner {
             return Err(());
         }
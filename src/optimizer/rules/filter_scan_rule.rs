@@ -3,7 +3,13 @@
 use std::sync::Arc;
 
 use super::*;
+use crate::array::LikePattern;
+use crate::binder::{BoundBinaryOp, BoundExpr};
+use crate::catalog::ColumnDesc;
+use crate::optimizer::expr_utils::conjunctions;
 use crate::optimizer::plan_nodes::{LogicalTableScan, PlanTreeNodeUnary};
+use crate::parser::BinaryOperator;
+use crate::types::DataValue;
 
 pub struct FilterScanRule {}
 
@@ -12,13 +18,188 @@ impl Rule for FilterScanRule {
         let filter = plan.as_logical_filter()?;
         let child = filter.child();
         let scan = child.as_logical_table_scan()?.clone();
-        Ok(Arc::new(LogicalTableScan::new(
-            scan.table_ref_id(),
-            scan.column_ids().to_vec(),
-            scan.column_descs().to_vec(),
-            scan.with_row_handler(),
-            scan.is_sorted(),
-            Some(filter.expr().clone()),
-        )))
+        let (begin_sort_key, end_sort_key) = sort_key_range(filter.expr(), scan.column_descs());
+        Ok(Arc::new(
+            LogicalTableScan::new(
+                scan.table_ref_id(),
+                scan.column_ids().to_vec(),
+                scan.column_descs().to_vec(),
+                scan.with_row_handler(),
+                scan.is_sorted(),
+                Some(filter.expr().clone()),
+            )
+            .with_sort_key_range(begin_sort_key, end_sort_key)
+            .with_statistics(scan.statistics().cloned()),
+        ))
+    }
+}
+
+/// Derive the range of the primary key from the comparisons between the primary key and
+/// constants, and the `LIKE` patterns with literal prefixes on the primary key in the filter. The
+/// range is inclusive on both ends, and might contain rows which do not satisfy the filter, so the
+/// filter is still required.
+fn sort_key_range(
+    expr: &BoundExpr,
+    column_descs: &[ColumnDesc],
+) -> (Vec<DataValue>, Vec<DataValue>) {
+    let pk = match column_descs.iter().position(|desc| desc.is_primary()) {
+        Some(pk) => pk,
+        None => return (vec![], vec![]),
+    };
+    let pk_type = column_descs[pk].datatype().physical_kind();
+    let mut begin: Option<DataValue> = None;
+    let mut end: Option<DataValue> = None;
+    for cond in conjunctions(expr.clone()) {
+        let BoundBinaryOp {
+            op,
+            left_expr,
+            right_expr,
+            ..
+        } = match &cond {
+            BoundExpr::BinaryOp(binary_op) => binary_op,
+            _ => continue,
+        };
+        let (op, value) = match (&**left_expr, &**right_expr) {
+            (BoundExpr::InputRef(input_ref), BoundExpr::Constant(value))
+                if input_ref.index == pk =>
+            {
+                (op.clone(), value)
+            }
+            (BoundExpr::Constant(value), BoundExpr::InputRef(input_ref))
+                if input_ref.index == pk =>
+            {
+                let op = match op {
+                    BinaryOperator::Gt => BinaryOperator::Lt,
+                    BinaryOperator::GtEq => BinaryOperator::LtEq,
+                    BinaryOperator::Lt => BinaryOperator::Gt,
+                    BinaryOperator::LtEq => BinaryOperator::GtEq,
+                    // the primary key is the pattern
+                    BinaryOperator::Like => continue,
+                    op => op.clone(),
+                };
+                (op, value)
+            }
+            _ => continue,
+        };
+        // the keys are compared with the values of the primary key in the storage
+        if value.data_type().map(|ty| ty.physical_kind()) != Some(pk_type.clone()) {
+            continue;
+        }
+        let (lower, upper) = match (op, value) {
+            (BinaryOperator::Eq, value) => (Some(value.clone()), Some(value.clone())),
+            (BinaryOperator::Gt | BinaryOperator::GtEq, value) => (Some(value.clone()), None),
+            (BinaryOperator::Lt | BinaryOperator::LtEq, value) => (None, Some(value.clone())),
+            (BinaryOperator::Like, DataValue::String(pattern)) => {
+                match LikePattern::new(pattern).range() {
+                    Some((lower, upper)) => {
+                        (Some(DataValue::String(lower)), upper.map(DataValue::String))
+                    }
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+        if let Some(lower) = lower {
+            if begin.as_ref().map_or(true, |begin| lower > *begin) {
+                begin = Some(lower);
+            }
+        }
+        if let Some(upper) = upper {
+            if end.as_ref().map_or(true, |end| upper < *end) {
+                end = Some(upper);
+            }
+        }
+    }
+    (begin.into_iter().collect(), end.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binder::BoundInputRef;
+    use crate::types::{DataTypeExt, DataTypeKind};
+
+    #[test]
+    fn test_sort_key_range() {
+        let column_descs = vec![
+            DataTypeKind::Int(None)
+                .not_null()
+                .to_column("v1".to_string()),
+            DataTypeKind::Int(None)
+                .not_null()
+                .to_column_primary_key("v2".to_string()),
+        ];
+        let cmp = |op, index, value| {
+            BoundExpr::BinaryOp(BoundBinaryOp {
+                op,
+                left_expr: Box::new(BoundExpr::InputRef(BoundInputRef {
+                    index,
+                    return_type: DataTypeKind::Int(None).not_null(),
+                })),
+                right_expr: Box::new(BoundExpr::Constant(value)),
+                return_type: Some(DataTypeKind::Boolean.nullable()),
+            })
+        };
+        let and = |left, right| {
+            BoundExpr::BinaryOp(BoundBinaryOp {
+                op: BinaryOperator::And,
+                left_expr: Box::new(left),
+                right_expr: Box::new(right),
+                return_type: Some(DataTypeKind::Boolean.nullable()),
+            })
+        };
+        // v2 > 1 and v2 >= 3 and v1 < 5 and v2 <= 10 and v2 < 20
+        let expr = and(
+            and(
+                cmp(BinaryOperator::Gt, 1, DataValue::Int32(1)),
+                cmp(BinaryOperator::GtEq, 1, DataValue::Int32(3)),
+            ),
+            and(
+                cmp(BinaryOperator::Lt, 0, DataValue::Int32(5)),
+                and(
+                    cmp(BinaryOperator::LtEq, 1, DataValue::Int32(10)),
+                    cmp(BinaryOperator::Lt, 1, DataValue::Int32(20)),
+                ),
+            ),
+        );
+        assert_eq!(
+            sort_key_range(&expr, &column_descs),
+            (vec![DataValue::Int32(3)], vec![DataValue::Int32(10)])
+        );
+
+        // v2 = 7, where the constant of another type is ignored
+        let expr = and(
+            cmp(BinaryOperator::Eq, 1, DataValue::Int32(7)),
+            cmp(BinaryOperator::Lt, 1, DataValue::Int64(5)),
+        );
+        assert_eq!(
+            sort_key_range(&expr, &column_descs),
+            (vec![DataValue::Int32(7)], vec![DataValue::Int32(7)])
+        );
+
+        // s like 'ab%' and s < 'abc' and 'a' like s
+        let column_descs = vec![DataTypeKind::Varchar(None)
+            .not_null()
+            .to_column_primary_key("s".to_string())];
+        let string = |s: &str| DataValue::String(s.into());
+        let expr = and(
+            and(
+                cmp(BinaryOperator::Like, 0, string("ab%")),
+                cmp(BinaryOperator::Lt, 0, string("abc")),
+            ),
+            BoundExpr::BinaryOp(BoundBinaryOp {
+                op: BinaryOperator::Like,
+                left_expr: Box::new(BoundExpr::Constant(string("a"))),
+                right_expr: Box::new(BoundExpr::InputRef(BoundInputRef {
+                    index: 0,
+                    return_type: DataTypeKind::Varchar(None).not_null(),
+                })),
+                return_type: Some(DataTypeKind::Boolean.nullable()),
+            }),
+        );
+        assert_eq!(
+            sort_key_range(&expr, &column_descs),
+            (vec![string("ab")], vec![string("abc")])
+        );
     }
 }
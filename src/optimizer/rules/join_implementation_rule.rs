@@ -0,0 +1,37 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::sync::Arc;
+
+use super::*;
+use crate::binder::BoundJoinOperator;
+use crate::optimizer::logical_plan_rewriter::{sort_merge_join, swapped_hash_join};
+use crate::optimizer::plan_nodes::{PhysicalHashJoin, PhysicalNestedLoopJoin, PlanTreeNodeBinary};
+
+/// Implement joins by nested loop joins, hash joins built on either side, and sort merge joins
+/// if both sides are sorted by an equal key.
+pub struct JoinImplementationRule {}
+
+impl ImplementationRule for JoinImplementationRule {
+    fn implement(&self, plan: &PlanRef) -> Vec<PlanRef> {
+        let join = match plan.as_logical_join() {
+            Ok(join) => join,
+            Err(_) => return vec![],
+        };
+        let mut plans: Vec<PlanRef> = vec![Arc::new(PhysicalNestedLoopJoin::new(join.clone()))];
+        if join.join_op() == BoundJoinOperator::Inner {
+            plans.extend(sort_merge_join(join, join.left(), join.right()));
+        }
+        if !join.predicate().eq_keys().is_empty() {
+            plans.push(Arc::new(PhysicalHashJoin::new(join.clone())));
+            if !matches!(
+                join.join_op(),
+                BoundJoinOperator::LeftSemi
+                    | BoundJoinOperator::LeftAnti
+                    | BoundJoinOperator::LeftSingle
+            ) {
+                plans.push(swapped_hash_join(join, join.left(), join.right()));
+            }
+        }
+        plans
+    }
+}
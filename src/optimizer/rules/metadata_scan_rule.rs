@@ -0,0 +1,113 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::sync::Arc;
+
+use super::*;
+use crate::binder::{AggKind, BoundAggCall, BoundExpr};
+use crate::optimizer::plan_nodes::{LogicalMetadataScan, LogicalTableScan, PlanTreeNodeUnary};
+
+/// Answer `count(*)`, `min` and `max` without group keys on a table without filters from the
+/// metadata in the storage.
+pub struct MetadataScanRule {}
+
+impl Rule for MetadataScanRule {
+    fn apply(&self, plan: PlanRef) -> Result<PlanRef, ()> {
+        let agg = plan.as_logical_aggregate()?;
+        if !agg.group_keys().is_empty() {
+            return Err(());
+        }
+        let child = agg.child();
+        let scan = child.as_logical_table_scan()?;
+        if scan.expr().is_some() || !scan.begin_sort_key().is_empty() {
+            return Err(());
+        }
+        if !(agg.agg_calls().iter()).all(|agg_call| is_answered_by_metadata(agg_call, scan)) {
+            return Err(());
+        }
+        Ok(Arc::new(LogicalMetadataScan::new(
+            agg.agg_calls().to_vec(),
+            scan.clone(),
+        )))
+    }
+}
+
+/// Whether the aggregation can be answered from the row counts, or the minimum and maximum values
+/// of a column in the storage.
+fn is_answered_by_metadata(agg_call: &BoundAggCall, scan: &LogicalTableScan) -> bool {
+    match agg_call.kind {
+        AggKind::RowCount => true,
+        AggKind::Min | AggKind::Max => matches!(
+            &agg_call.args[..],
+            // the row handler is not a column in the storage
+            [BoundExpr::InputRef(input_ref)] if input_ref.index < scan.column_ids().len()
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binder::BoundInputRef;
+    use crate::catalog::TableRefId;
+    use crate::optimizer::plan_nodes::{IntoPlanRef, LogicalAggregate};
+    use crate::types::{DataTypeExt, DataTypeKind, DataValue};
+
+    #[test]
+    fn test_metadata_scan_rule() {
+        let ty = DataTypeKind::Int(None).nullable();
+        let scan = LogicalTableScan::new(
+            TableRefId {
+                database_id: 0,
+                schema_id: 0,
+                table_id: 0,
+            },
+            vec![0],
+            vec![ty.clone().to_column("v".to_string())],
+            false,
+            false,
+            None,
+        );
+        let agg_call = |kind| BoundAggCall {
+            kind,
+            args: vec![BoundExpr::InputRef(BoundInputRef {
+                index: 0,
+                return_type: ty.clone(),
+            })],
+            return_type: ty.clone(),
+            distinct: false,
+        };
+        let apply = |agg_calls, group_keys, scan: &LogicalTableScan| {
+            let agg = LogicalAggregate::new(agg_calls, group_keys, scan.clone().into_plan_ref());
+            MetadataScanRule {}.apply(agg.into_plan_ref())
+        };
+
+        let agg_calls = vec![
+            agg_call(AggKind::RowCount),
+            agg_call(AggKind::Min),
+            agg_call(AggKind::Max),
+        ];
+        let plan = apply(agg_calls.clone(), vec![], &scan).unwrap();
+        assert_eq!(
+            plan.as_logical_metadata_scan().unwrap().agg_calls().len(),
+            3
+        );
+
+        // aggregations other than `count(*)`, `min` and `max` can not be answered
+        assert!(apply(vec![agg_call(AggKind::Sum)], vec![], &scan).is_err());
+        assert!(apply(vec![agg_call(AggKind::Count)], vec![], &scan).is_err());
+        // the groups are unknown
+        let group_keys = agg_calls[1].args.clone();
+        assert!(apply(agg_calls.clone(), group_keys, &scan).is_err());
+        // the rows are filtered
+        let filtered_scan = LogicalTableScan::new(
+            scan.table_ref_id(),
+            scan.column_ids().to_vec(),
+            scan.column_descs().to_vec(),
+            false,
+            false,
+            Some(BoundExpr::Constant(DataValue::Bool(true))),
+        );
+        assert!(apply(agg_calls, vec![], &filtered_scan).is_err());
+    }
+}
@@ -2,17 +2,32 @@
 
 use super::plan_nodes::PlanRef;
 
+mod agg_implementation_rule;
 mod filter_agg_rule;
 mod filter_join_rule;
 mod filter_scan_rule;
+mod join_implementation_rule;
 mod limit_order_rule;
+mod metadata_scan_rule;
+pub use agg_implementation_rule::*;
 pub use filter_agg_rule::*;
 pub use filter_join_rule::*;
 pub use filter_scan_rule::*;
+pub use join_implementation_rule::*;
 pub use limit_order_rule::*;
+pub use metadata_scan_rule::*;
 
 pub trait Rule: Send + Sync + 'static {
     fn apply(&self, plan: PlanRef) -> Result<PlanRef, ()>;
 }
 
 pub(super) type BoxedRule = Box<dyn Rule>;
+
+/// A rule to implement a logical plan node by physical plans.
+pub trait ImplementationRule: Send + Sync + 'static {
+    /// Returns the physical plans of the logical plan node, whose children are physical plans
+    /// already. Returns an empty vector if the rule does not apply.
+    fn implement(&self, plan: &PlanRef) -> Vec<PlanRef>;
+}
+
+pub(super) type BoxedImplementationRule = Box<dyn ImplementationRule>;
@@ -0,0 +1,112 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::expr_utils::conjunctions;
+use super::logical_plan_rewriter::PlanRewriter;
+use super::plan_nodes::*;
+use crate::binder::{BoundBinaryOp, BoundExpr};
+use crate::catalog::TableRefId;
+use crate::parser::BinaryOperator;
+use crate::types::{ColumnId, DataValue};
+
+/// The number of rows assumed for a table without statistics.
+pub const DEFAULT_ROW_COUNT: usize = 1000;
+
+/// The selectivity assumed for a condition which can not be estimated.
+const DEFAULT_SELECTIVITY: f64 = 1.0 / 3.0;
+
+/// The statistics of a table, aggregated from the block statistics in the storage.
+#[derive(Debug, Clone, Default)]
+pub struct TableStatistics {
+    pub row_count: usize,
+    /// The number of distinct values of each column.
+    pub distinct_values: HashMap<ColumnId, usize>,
+}
+
+/// The statistics of the tables in a query.
+#[derive(Debug, Clone, Default)]
+pub struct Statistics {
+    tables: HashMap<TableRefId, TableStatistics>,
+}
+
+impl Statistics {
+    /// Add the statistics of a table.
+    pub fn add_table(&mut self, table_ref_id: TableRefId, statistics: TableStatistics) {
+        self.tables.insert(table_ref_id, statistics);
+    }
+
+    /// Get the statistics of a table.
+    pub fn table(&self, table_ref_id: TableRefId) -> Option<&TableStatistics> {
+        self.tables.get(&table_ref_id)
+    }
+
+    /// Attach the statistics of the tables to the table scans in the plan, from which the
+    /// cardinalities of all plan nodes are estimated.
+    pub fn annotate(&self, plan: PlanRef) -> PlanRef {
+        struct Annotator<'a>(&'a Statistics);
+        impl PlanRewriter for Annotator<'_> {
+            fn rewrite_logical_table_scan(&mut self, plan: &LogicalTableScan) -> PlanRef {
+                let statistics = self.0.table(plan.table_ref_id()).cloned();
+                Arc::new(plan.clone().with_statistics(statistics))
+            }
+        }
+        Annotator(self).rewrite(plan)
+    }
+}
+
+/// The estimated fraction of rows which satisfy the condition, where `distinct_values` returns
+/// the estimated number of distinct values of each input column.
+pub fn estimated_selectivity(
+    condition: &BoundExpr,
+    distinct_values: &dyn Fn(usize) -> usize,
+) -> f64 {
+    conjunctions(condition.clone())
+        .iter()
+        .map(|cond| selectivity_of_conjunct(cond, distinct_values))
+        .product()
+}
+
+fn selectivity_of_conjunct(cond: &BoundExpr, distinct_values: &dyn Fn(usize) -> usize) -> f64 {
+    use BoundExpr::{Constant, InputRef};
+
+    let ndv = |index: usize| distinct_values(index).max(1) as f64;
+    let BoundBinaryOp {
+        op,
+        left_expr,
+        right_expr,
+        ..
+    } = match cond {
+        Constant(DataValue::Bool(true)) => return 1.0,
+        Constant(_) => return 0.0,
+        BoundExpr::BinaryOp(binary_op) => binary_op,
+        _ => return DEFAULT_SELECTIVITY,
+    };
+    match (op, &**left_expr, &**right_expr) {
+        (BinaryOperator::Eq, InputRef(left), InputRef(right)) => {
+            1.0 / ndv(left.index).max(ndv(right.index))
+        }
+        (BinaryOperator::Eq, InputRef(input_ref), Constant(_))
+        | (BinaryOperator::Eq, Constant(_), InputRef(input_ref)) => 1.0 / ndv(input_ref.index),
+        (BinaryOperator::NotEq, InputRef(input_ref), Constant(_))
+        | (BinaryOperator::NotEq, Constant(_), InputRef(input_ref)) => {
+            1.0 - 1.0 / ndv(input_ref.index)
+        }
+        (BinaryOperator::Or, left, right) => {
+            let left = estimated_selectivity(left, distinct_values);
+            let right = estimated_selectivity(right, distinct_values);
+            left + right - left * right
+        }
+        _ => DEFAULT_SELECTIVITY,
+    }
+}
+
+/// Round the estimated number of rows, which is at least 1 unless the input is empty.
+pub(crate) fn round_rows(rows: f64) -> usize {
+    if rows > 0.0 {
+        rows.ceil() as usize
+    } else {
+        0
+    }
+}
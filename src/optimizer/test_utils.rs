@@ -0,0 +1,73 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+//! Plans and statistics shared by the tests of the optimizer, whose columns are all `INT NOT
+//! NULL`.
+
+use super::plan_nodes::{IntoPlanRef, LogicalTableScan, PlanRef};
+use super::{Statistics, TableStatistics};
+use crate::binder::{BoundBinaryOp, BoundExpr, BoundInputRef};
+use crate::catalog::TableRefId;
+use crate::parser::BinaryOperator;
+use crate::types::{DataType, DataTypeExt, DataTypeKind, TableId};
+
+/// The type of all columns.
+pub fn ty() -> DataType {
+    DataTypeKind::Int(None).not_null()
+}
+
+pub fn table_ref_id(table_id: TableId) -> TableRefId {
+    TableRefId {
+        database_id: 0,
+        schema_id: 0,
+        table_id,
+    }
+}
+
+/// Scan the columns `names` of the table `table_id`, whose ids start from 0.
+pub fn scan(table_id: TableId, names: &[&str]) -> PlanRef {
+    LogicalTableScan::new(
+        table_ref_id(table_id),
+        (0..names.len() as u32).collect(),
+        names
+            .iter()
+            .map(|name| ty().to_column(name.to_string()))
+            .collect(),
+        false,
+        false,
+        None,
+    )
+    .into_plan_ref()
+}
+
+pub fn input_ref(index: usize) -> BoundExpr {
+    BoundExpr::InputRef(BoundInputRef {
+        index,
+        return_type: ty(),
+    })
+}
+
+/// The condition that the `left`-th column equals the `right`-th column.
+pub fn eq(left: usize, right: usize) -> BoundExpr {
+    BoundExpr::BinaryOp(BoundBinaryOp {
+        op: BinaryOperator::Eq,
+        left_expr: Box::new(input_ref(left)),
+        right_expr: Box::new(input_ref(right)),
+        return_type: Some(DataTypeKind::Boolean.nullable()),
+    })
+}
+
+/// The statistics of the tables, given by their ids, row counts and the numbers of distinct
+/// values of their columns.
+pub fn statistics(tables: &[(TableId, usize, &[usize])]) -> Statistics {
+    let mut statistics = Statistics::default();
+    for (table_id, row_count, distinct_values) in tables {
+        statistics.add_table(
+            table_ref_id(*table_id),
+            TableStatistics {
+                row_count: *row_count,
+                distinct_values: (0..).zip(distinct_values.iter().copied()).collect(),
+            },
+        );
+    }
+    statistics
+}
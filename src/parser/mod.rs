@@ -5,11 +5,132 @@
 
 pub use sqlparser::ast::*;
 use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::keywords::Keyword;
 use sqlparser::parser::Parser;
 pub use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, Tokenizer};
 
 /// Parse the SQL string into a list of ASTs.
+///
+/// `DISTINCT ON (...)` is rewritten before parsing, and its expressions are returned by
+/// [`distinct_on`]. `SIMILAR TO` is not supported.
 pub fn parse(sql: &str) -> Result<Vec<Statement>, ParserError> {
     let dialect = PostgreSqlDialect {};
-    Parser::parse_sql(&dialect, sql)
+    let mut tokens = Tokenizer::new(&dialect, sql).tokenize()?;
+    reject_similar_to(&tokens)?;
+    rewrite_distinct_on(&mut tokens)?;
+
+    let mut parser = Parser::new(tokens, &dialect);
+    let mut stmts = vec![];
+    let mut expecting_statement_delimiter = false;
+    loop {
+        while parser.consume_token(&Token::SemiColon) {
+            expecting_statement_delimiter = false;
+        }
+        if parser.peek_token() == Token::EOF {
+            break;
+        }
+        if expecting_statement_delimiter {
+            return Err(ParserError::ParserError(format!(
+                "Expected end of statement, found: {}",
+                parser.peek_token()
+            )));
+        }
+        stmts.push(parser.parse_statement()?);
+        expecting_statement_delimiter = true;
+    }
+    Ok(stmts)
+}
+
+/// Returns an error for `SIMILAR TO`, which would otherwise be reported as an unexpected token by
+/// the parser.
+fn reject_similar_to(tokens: &[Token]) -> Result<(), ParserError> {
+    let keywords: Vec<Keyword> = (tokens.iter())
+        .filter(|token| !matches!(token, Token::Whitespace(_)))
+        .map(|token| match token {
+            Token::Word(w) => w.keyword,
+            _ => Keyword::NoKeyword,
+        })
+        .collect();
+    if keywords
+        .windows(2)
+        .any(|w| w == [Keyword::SIMILAR, Keyword::TO])
+    {
+        return Err(ParserError::ParserError(
+            "SIMILAR TO is not supported".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Rewrite `SELECT DISTINCT ON (...)` into `SELECT DISTINCT TOP ((...))`.
+///
+/// The parser does not support `DISTINCT ON`, so its expressions are carried by the `TOP` clause
+/// of the select, which is read by [`distinct_on`]. `TOP` written in the SQL is not supported.
+fn rewrite_distinct_on(tokens: &mut Vec<Token>) -> Result<(), ParserError> {
+    let positions: Vec<usize> = (0..tokens.len())
+        .filter(|&i| !matches!(tokens[i], Token::Whitespace(_)))
+        .collect();
+    let is_keyword = |i: usize, keyword: Keyword| match positions.get(i).map(|&p| &tokens[p]) {
+        Some(Token::Word(w)) => w.keyword == keyword,
+        _ => false,
+    };
+    // the replaced `ON` and the positions to insert parentheses, in the original tokens
+    let mut replaced = vec![];
+    let mut inserted = vec![];
+    for i in 0..positions.len() {
+        if !is_keyword(i, Keyword::SELECT) {
+            continue;
+        }
+        let quantified = is_keyword(i + 1, Keyword::DISTINCT) || is_keyword(i + 1, Keyword::ALL);
+        if is_keyword(i + 1, Keyword::TOP) || quantified && is_keyword(i + 2, Keyword::TOP) {
+            return Err(ParserError::ParserError("TOP is not supported".into()));
+        }
+        if !is_keyword(i + 1, Keyword::DISTINCT)
+            || !is_keyword(i + 2, Keyword::ON)
+            || positions.get(i + 3).map(|&p| &tokens[p]) != Some(&Token::LParen)
+        {
+            continue;
+        }
+        // find the matching right parenthesis, or leave the error to the parser
+        let mut depth = 0;
+        for &position in &positions[i + 3..] {
+            match tokens[position] {
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                replaced.push(positions[i + 2]);
+                inserted.push((positions[i + 3], Token::LParen));
+                inserted.push((position + 1, Token::RParen));
+                break;
+            }
+        }
+    }
+    for position in replaced {
+        tokens[position] = Token::make_keyword("TOP");
+    }
+    inserted.sort_by_key(|(position, _)| *position);
+    for (position, token) in inserted.into_iter().rev() {
+        tokens.insert(position, token);
+    }
+    Ok(())
+}
+
+/// Returns the expressions of `DISTINCT ON` of the select, which are carried by its `TOP` clause.
+///
+/// See [`parse`] for details.
+pub fn distinct_on(select: &Select) -> Option<&[Expr]> {
+    match &select.top {
+        Some(Top {
+            quantity: Some(Expr::Tuple(exprs)),
+            ..
+        }) if select.distinct => Some(exprs),
+        Some(Top {
+            quantity: Some(Expr::Nested(expr)),
+            ..
+        }) if select.distinct => Some(std::slice::from_ref(expr)),
+        _ => None,
+    }
 }
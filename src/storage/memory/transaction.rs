@@ -58,8 +58,11 @@ impl InMemoryTransaction {
 }
 
 /// If primary key is found in [`ColumnCatalog`], sort all in-memory data using that key.
+///
+/// The deleted rows are removed from the sorted data, as the positions of rows are changed.
 fn sort_datachunk_by_pk(
     chunks: &Arc<Vec<DataChunk>>,
+    deleted_rows: &HashSet<usize>,
     column_infos: &[ColumnCatalog],
 ) -> Arc<Vec<DataChunk>> {
     if let Some(sort_key_id) = find_sort_key_id(column_infos) {
@@ -82,7 +85,9 @@ fn sort_datachunk_by_pk(
             .into_iter()
             .map(|builder| builder.finish())
             .collect_vec();
-        let sorted_index = arrays[sort_key_id].get_sorted_indices();
+        let sorted_index = (arrays[sort_key_id].get_sorted_indices().into_iter())
+            .filter(|index| !deleted_rows.contains(index))
+            .collect_vec();
 
         let chunk = arrays
             .into_iter()
@@ -137,17 +142,16 @@ impl Transaction for InMemoryTransaction {
                 "sort_key is not supported in InMemoryEngine for now"
             );
 
-            let snapshot = if is_sorted {
-                sort_datachunk_by_pk(&self.snapshot, &self.column_infos)
+            let (snapshot, deleted_rows) = if is_sorted {
+                (
+                    sort_datachunk_by_pk(&self.snapshot, &self.deleted_rows, &self.column_infos),
+                    Default::default(),
+                )
             } else {
-                self.snapshot.clone()
+                (self.snapshot.clone(), self.deleted_rows.clone())
             };
 
-            Ok(InMemoryTxnIterator::new(
-                snapshot,
-                self.deleted_rows.clone(),
-                col_idx,
-            ))
+            Ok(InMemoryTxnIterator::new(snapshot, deleted_rows, col_idx))
         }
     }
 
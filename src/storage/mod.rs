@@ -50,6 +50,13 @@ impl StorageImpl {
             Self::InMemoryStorage(_) => false,
         }
     }
+
+    pub fn enable_metadata_scan(&self) -> bool {
+        match self {
+            Self::SecondaryStorage(_) => true,
+            Self::InMemoryStorage(_) => false,
+        }
+    }
 }
 
 /// Represents a storage engine.
@@ -42,7 +42,7 @@ impl<T: PrimitiveFixedWidthEncode> BlockBuilder<T::ArrayType> for PlainPrimitive
     }
 
     fn get_statistics(&self) -> Vec<BlockStatistics> {
-        let mut stats_builder = StatisticsBuilder::new();
+        let mut stats_builder = StatisticsBuilder::with_order(T::cmp_encoded);
         for item in self.data.chunks(T::WIDTH) {
             stats_builder.add_item(Some(item));
         }
@@ -56,7 +56,7 @@ impl<T: PrimitiveFixedWidthEncode> BlockBuilder<T::ArrayType>
     }
 
     fn get_statistics(&self) -> Vec<BlockStatistics> {
-        let mut stats_builder = StatisticsBuilder::new();
+        let mut stats_builder = StatisticsBuilder::with_order(T::cmp_encoded);
         for (idx, item) in enumerate(self.data.chunks(T::WIDTH)) {
             if self.bitmap[idx] {
                 stats_builder.add_item(Some(item));
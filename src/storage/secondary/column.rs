@@ -41,6 +41,7 @@ use super::{Block, BlockCacheKey, BlockHeader, ColumnIndex, BLOCK_HEADER_SIZE};
 use crate::array::Array;
 use crate::storage::secondary::verify_checksum;
 use crate::storage::{StorageResult, TracedStorageError};
+use crate::types::DataValue;
 
 /// Builds a column. [`ColumnBuilder`] will automatically chunk [`Array`] into
 /// blocks, calls `BlockBuilder` to generate a block, and builds index for a
@@ -82,11 +83,11 @@ pub trait ColumnIterator<A: Array> {
 }
 
 /// When creating an iterator, a [`ColumnSeekPosition`] should be set as the initial location.
-#[derive(PartialEq, Eq, Clone, Copy)]
+#[derive(PartialEq, Clone)]
 pub enum ColumnSeekPosition {
     RowId(u32),
-    #[allow(dead_code)]
-    SortKey(()),
+    /// A key of the sort key column, which locates the first block that may contain the key.
+    SortKey(DataValue),
 }
 
 impl ColumnSeekPosition {
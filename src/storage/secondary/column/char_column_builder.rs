@@ -117,6 +117,8 @@ impl ColumnBuilder<Utf8Array> for CharColumnBuilder {
                             ),
                         ));
                     }
+                    // TODO: there are no nullable varchar blocks yet, so a null string panics
+                    // in the blob block builder even if the column is nullable.
                     (None, _, true) => {
                         let builder =
                             PlainBlobBlockBuilder::new(self.options.target_block_size - 16);
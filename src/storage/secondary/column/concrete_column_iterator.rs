@@ -69,7 +69,7 @@ impl<A: Array, F: BlockIteratorFactory<A>> ConcreteColumnIterator<A, F> {
     pub async fn new(column: Column, start_pos: u32, factory: F) -> StorageResult<Self> {
         let current_block_id = column
             .index()
-            .block_of_seek_position(ColumnSeekPosition::RowId(start_pos));
+            .block_of_seek_position(&ColumnSeekPosition::RowId(start_pos));
         let (header, block) = column.get_block(current_block_id).await?;
         Ok(Self {
             block_iterator: factory.get_iterator_for(
@@ -1,5 +1,7 @@
 // Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
 
+use std::cmp::Ordering;
+
 use bytes::{Buf, BufMut};
 use rust_decimal::Decimal;
 
@@ -7,10 +9,12 @@ use crate::array::{
     Array, BlobArray, BoolArray, DateArray, DecimalArray, F64Array, I32Array, I64Array,
     IntervalArray, Utf8Array,
 };
-use crate::types::{BlobRef, Date, Interval};
+use crate::types::{BlobRef, DataValue, Date, Interval, PhysicalDataTypeKind};
 
 /// Encode a primitive value into fixed-width buffer
-pub trait PrimitiveFixedWidthEncode: Copy + Clone + 'static + Send + Sync + PartialEq {
+pub trait PrimitiveFixedWidthEncode:
+    Copy + Clone + 'static + Send + Sync + PartialEq + PartialOrd
+{
     /// Width of each element
     const WIDTH: usize;
     const DEFAULT_VALUE: &'static Self;
@@ -22,6 +26,16 @@ pub trait PrimitiveFixedWidthEncode: Copy + Clone + 'static + Send + Sync + Part
 
     /// Decode a data from a bytes array.
     fn decode(buffer: &mut impl Buf) -> Self;
+
+    /// Compare two values in a total order.
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+
+    /// Compare two encoded values in a total order.
+    fn cmp_encoded(mut a: &[u8], mut b: &[u8]) -> Ordering {
+        Self::decode(&mut a).total_cmp(&Self::decode(&mut b))
+    }
 }
 
 impl PrimitiveFixedWidthEncode for bool {
@@ -81,6 +95,11 @@ impl PrimitiveFixedWidthEncode for f64 {
     fn decode(buffer: &mut impl Buf) -> Self {
         buffer.get_f64_le()
     }
+
+    /// `NaN` is greater than any other value.
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        (self.partial_cmp(other)).unwrap_or_else(|| self.is_nan().cmp(&other.is_nan()))
+    }
 }
 
 impl PrimitiveFixedWidthEncode for Decimal {
@@ -174,3 +193,23 @@ impl BlobEncode for str {
         self.as_bytes()
     }
 }
+
+/// Decode a value which is encoded in the same way as the items of the blocks, such as the first
+/// key and the min and max values of a block.
+pub fn decode_value(kind: &PhysicalDataTypeKind, mut bytes: &[u8]) -> DataValue {
+    match kind {
+        PhysicalDataTypeKind::Int32 => DataValue::Int32(i32::decode(&mut bytes)),
+        PhysicalDataTypeKind::Int64 => DataValue::Int64(i64::decode(&mut bytes)),
+        PhysicalDataTypeKind::Float64 => DataValue::Float64(f64::decode(&mut bytes)),
+        PhysicalDataTypeKind::Bool => DataValue::Bool(bool::decode(&mut bytes)),
+        PhysicalDataTypeKind::String => {
+            // fixed-width chars are padded with `\0`
+            let len = bytes.iter().position(|x| *x == 0).unwrap_or(bytes.len());
+            DataValue::String(String::from_utf8_lossy(&bytes[..len]).into_owned())
+        }
+        PhysicalDataTypeKind::Blob => DataValue::Blob(bytes.into()),
+        PhysicalDataTypeKind::Decimal => DataValue::Decimal(Decimal::decode(&mut bytes)),
+        PhysicalDataTypeKind::Date => DataValue::Date(Date::decode(&mut bytes)),
+        PhysicalDataTypeKind::Interval => DataValue::Interval(Interval::decode(&mut bytes)),
+    }
+}
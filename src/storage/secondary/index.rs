@@ -8,8 +8,10 @@ use risinglight_proto::rowset::block_checksum::ChecksumType;
 use risinglight_proto::rowset::BlockIndex;
 
 use super::{ColumnSeekPosition, SECONDARY_INDEX_MAGIC};
+use crate::storage::secondary::encode::decode_value;
 use crate::storage::secondary::{verify_checksum, INDEX_FOOTER_SIZE};
 use crate::storage::{StorageResult, TracedStorageError};
+use crate::types::DataValue;
 
 #[derive(Clone)]
 pub struct ColumnIndex {
@@ -55,13 +57,35 @@ impl ColumnIndex {
         })
     }
 
-    pub fn block_of_seek_position(&self, seek_pos: ColumnSeekPosition) -> u32 {
+    pub fn block_of_seek_position(&self, seek_pos: &ColumnSeekPosition) -> u32 {
         match seek_pos {
-            ColumnSeekPosition::RowId(row_id) => self.block_of_row(row_id),
-            ColumnSeekPosition::SortKey(_) => todo!(),
+            ColumnSeekPosition::RowId(row_id) => self.block_of_row(*row_id),
+            ColumnSeekPosition::SortKey(key) => self.block_of_sort_key(key),
         }
     }
 
+    /// Find the first block which may contain the key, on a sorted column whose first keys are
+    /// recorded in the index. Returns the first block if the first keys are not recorded.
+    pub fn block_of_sort_key(&self, key: &DataValue) -> u32 {
+        let kind = match key.data_type() {
+            Some(data_type) => data_type.physical_kind(),
+            None => return 0,
+        };
+        if (self.indexes.iter()).any(|index| index.first_key.is_empty() && !index.is_first_key_null)
+        {
+            return 0;
+        }
+        // As there might be duplicated keys, the key can also be at the end of the block before
+        // the first block whose first key equals to the key. Therefore, we partition the blocks
+        // by `first_key < key`, and the key can only be found from the block at
+        // `partition_point - 1`.
+        let pp = self.indexes.partition_point(|index| {
+            index.is_first_key_null || decode_value(&kind, &index.first_key) < *key
+        }) as u32;
+
+        pp.saturating_sub(1)
+    }
+
     /// Find corresponding block of a row.
     pub fn block_of_row(&self, rowid: u32) -> u32 {
         // For example, there are 3 blocks, each of which has a first rowid of `233`, `2333`,
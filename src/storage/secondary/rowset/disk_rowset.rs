@@ -1,21 +1,23 @@
 // Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
 
-use std::borrow::Borrow;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 use bytes::Bytes;
 use itertools::Itertools;
 use moka::future::Cache;
+use risinglight_proto::rowset::block_statistics::BlockStatisticsType;
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncReadExt;
 
 use super::super::{Block, BlockCacheKey, Column, ColumnIndex, ColumnSeekPosition, IOBackend};
 use super::{path_of_data_column, path_of_index_column, RowSetIterator};
 use crate::binder::BoundExpr;
-use crate::catalog::ColumnCatalog;
+use crate::catalog::{find_sort_key_id, ColumnCatalog};
 use crate::storage::secondary::column::ColumnReadableFile;
-use crate::storage::secondary::encode::PrimitiveFixedWidthEncode;
+use crate::storage::secondary::statistics::{
+    create_statistics_global_aggregator, filter_may_match,
+};
 use crate::storage::secondary::DeleteVector;
 use crate::storage::{StorageColumnRef, StorageResult};
 use crate::types::DataValue;
@@ -138,6 +140,31 @@ impl DiskRowset {
         .await
     }
 
+    /// Returns `false` if no row of the rowset can satisfy the filter on `column_refs`, according
+    /// to the minimum and maximum values of the columns.
+    pub fn may_match(&self, column_refs: &[StorageColumnRef], expr: &BoundExpr) -> bool {
+        filter_may_match(expr, &|index| {
+            let column_id = match column_refs[index] {
+                StorageColumnRef::Idx(idx) => idx as usize,
+                StorageColumnRef::RowHandler => return None,
+            };
+            let data_type = self.column_info(column_id).datatype();
+            let index = self.columns[column_id].index();
+            let mut min = create_statistics_global_aggregator(
+                BlockStatisticsType::MinValue,
+                data_type.clone(),
+            );
+            let mut max =
+                create_statistics_global_aggregator(BlockStatisticsType::MaxValue, data_type);
+            min.apply_batch(index);
+            max.apply_batch(index);
+            match (min.get_output(), max.get_output()) {
+                (DataValue::Null, _) | (_, DataValue::Null) => None,
+                range => Some(range),
+            }
+        })
+    }
+
     pub fn on_disk_size(&self) -> u64 {
         self.columns
             .iter()
@@ -148,42 +175,38 @@ impl DiskRowset {
 
     /// Get the start row id to begin with for later table scanning.
     /// If `begin_keys` is empty, we return `ColumnSeekPosition::RowId(0)` to indicate scanning
-    /// from the beginning, otherwise we scan the rowsets' first column indexes, find the first
-    /// block who contains data greater than or equal to `begin_key` and return the row id of
-    /// the block's first key. Currently, only the first column of the rowsets can be used to get
-    /// the start row id and this column should be primary key.
+    /// from the beginning, otherwise we binary search the first keys of the sort key column, find
+    /// the first block which may contain `begin_key` and return the row id of the block's first
+    /// key. Currently, only the first key of `begin_keys` can be used to get the start row id.
     /// If `begin_key` is greater than all blocks' `first_key`, we return the `first_key` of the
     /// last block.
     /// Todo: support multi sort-keys range filter
     pub async fn start_rowid(&self, begin_keys: &[DataValue]) -> ColumnSeekPosition {
-        if begin_keys.is_empty() {
-            return ColumnSeekPosition::RowId(0);
+        match begin_keys.first() {
+            Some(begin_key) => ColumnSeekPosition::RowId(self.row_id_of_sort_key(begin_key)),
+            None => ColumnSeekPosition::RowId(0),
         }
+    }
 
-        // for now, we only use the first column to get the start row id, which means the length
-        // of `begin_keys` can only be 0 or 1.
-        let begin_key = begin_keys[0].borrow();
-        let column = self.column(0);
-        let column_index = column.index();
-
-        let start_row_id = match *begin_key {
-            DataValue::Int32(begin_val) => {
-                let mut pre_block_first_key = 0;
-                for index in column_index.indexes() {
-                    let mut first_key: &[u8] = &index.first_key;
-                    let first_val: i32 = PrimitiveFixedWidthEncode::decode(&mut first_key);
-
-                    if first_val > begin_val {
-                        break;
-                    }
-                    pre_block_first_key = index.first_rowid;
-                }
-                pre_block_first_key
-            }
-            // Todo: support ohter type
-            _ => panic!("for now support range-filter scan by sort key type of int32"),
+    /// Get the storage column id of the sort key column.
+    pub fn sort_key_id(&self) -> Option<usize> {
+        find_sort_key_id(&self.column_infos)
+    }
+
+    /// Get the first row id of the first block which may contain the key in the sort key column.
+    pub fn row_id_of_sort_key(&self, key: &DataValue) -> u32 {
+        let sort_key = match self.sort_key_id() {
+            Some(sort_key) => sort_key,
+            None => return 0,
         };
-        ColumnSeekPosition::RowId(start_row_id)
+        // the key can only be compared with the values of the same type
+        let kind = self.column_infos[sort_key].datatype().physical_kind();
+        if key.data_type().map(|ty| ty.physical_kind()) != Some(kind) {
+            return 0;
+        }
+        let index = self.columns[sort_key].index();
+        let block_id = index.block_of_seek_position(&ColumnSeekPosition::SortKey(key.clone()));
+        index.index(block_id).first_rowid
     }
 }
 
@@ -31,7 +31,12 @@ impl RowsetBuilder {
             builders: columns
                 .iter()
                 .map(|column| {
-                    ColumnBuilderImpl::new_from_datatype(&column.datatype(), column_options.clone())
+                    // the first keys of the sort key column are used to seek by sort keys
+                    let options = ColumnBuilderOptions {
+                        record_first_key: column_options.record_first_key || column.is_primary(),
+                        ..column_options.clone()
+                    };
+                    ColumnBuilderImpl::new_from_datatype(&column.datatype(), options)
                 })
                 .collect_vec(),
             columns,
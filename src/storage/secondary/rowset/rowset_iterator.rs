@@ -5,13 +5,14 @@ use std::sync::Arc;
 use bitvec::prelude::BitVec;
 use smallvec::smallvec;
 
-use super::super::{ColumnIteratorImpl, ColumnSeekPosition, SecondaryIteratorImpl};
+use super::super::{ColumnIndex, ColumnIteratorImpl, ColumnSeekPosition, SecondaryIteratorImpl};
 use super::DiskRowset;
 use crate::array::{Array, ArrayImpl};
 use crate::binder::BoundExpr;
+use crate::storage::secondary::statistics::{block_min_max, filter_may_match};
 use crate::storage::secondary::DeleteVector;
 use crate::storage::{PackedVec, StorageChunk, StorageColumnRef, StorageResult};
-use crate::types::DataValue;
+use crate::types::{DataType, DataValue};
 
 /// When `expected_size` is not specified, we should limit the maximum size of the chunk.
 const ROWSET_MAX_OUTPUT: usize = 2048;
@@ -22,6 +23,12 @@ pub struct RowSetIterator {
     dvs: Vec<Arc<DeleteVector>>,
     column_iterators: Vec<ColumnIteratorImpl>,
     filter_expr: Option<(BoundExpr, BitVec)>,
+    /// The index and data type of each column in the filter, by which the blocks that can not
+    /// match the filter are skipped.
+    filter_column_indexes: Vec<(usize, ColumnIndex, DataType)>,
+    /// The position of the sort key column in `column_refs`, by which the rows out of the range
+    /// of `start_keys` and `end_keys` are filtered.
+    sort_key_position: Option<usize>,
     start_keys: Vec<DataValue>,
     end_keys: Vec<DataValue>,
     meet_start_key_before: bool,
@@ -39,7 +46,7 @@ impl RowSetIterator {
     ) -> StorageResult<Self> {
         let start_row_id = match seek_pos {
             ColumnSeekPosition::RowId(row_id) => row_id,
-            _ => todo!(),
+            ColumnSeekPosition::SortKey(key) => rowset.row_id_of_sort_key(&key),
         };
 
         if column_refs.len() == 0 {
@@ -96,11 +103,30 @@ impl RowSetIterator {
             None
         };
 
+        let sort_key_position = rowset.sort_key_id().and_then(|sort_key| {
+            (column_refs.iter()).position(|x| *x == StorageColumnRef::Idx(sort_key as u32))
+        });
+
+        let mut filter_column_indexes = vec![];
+        if let Some((_, filter_column)) = &filter_expr {
+            for id in filter_column.iter_ones() {
+                if let StorageColumnRef::Idx(idx) = column_refs[id] {
+                    filter_column_indexes.push((
+                        id,
+                        rowset.column(idx as usize).index().clone(),
+                        rowset.column_info(idx as usize).datatype(),
+                    ));
+                }
+            }
+        }
+
         Ok(Self {
             column_refs,
             dvs,
             column_iterators,
             filter_expr,
+            filter_column_indexes,
+            sort_key_position,
             start_keys: start_keys.to_vec(),
             end_keys: end_keys.to_vec(),
             meet_end_key_before: false,
@@ -117,6 +143,9 @@ impl RowSetIterator {
         if self.meet_end_key_before {
             return Ok((true, None));
         }
+        if self.skip_unmatched_blocks() {
+            return Ok((false, None));
+        }
         let filter_context = self.filter_expr.as_ref();
         // It's guaranteed that `expected_size` <= the number of items left
         // in the current block, if provided
@@ -280,51 +309,45 @@ impl RowSetIterator {
                     arrays[id] = Some(array);
                 }
             }
-            // For now, we only support range-filter scan by first column.
-            if id == 0 {
-                if !self.start_keys.is_empty() && !self.meet_start_key_before {
-                    // find the first row in range to begin with
-                    self.meet_start_key_before = true;
-                    let array = arrays[0].as_ref().unwrap();
-                    let len = array.len();
-                    let start_key = &self.start_keys[0];
-                    let start_row_id =
-                        (0..len).position(|idx| start_key - &array.get(idx) <= DataValue::Int32(0));
-                    if start_row_id.is_none() {
-                        // the `begin_key` is greater than all of the data, so on item survives in
-                        // this scan
-                        return Ok((true, None));
-                    }
-                    let start_row_id = start_row_id.unwrap();
-                    let new_bitmap =
-                        Self::mark_inaccessible(visibility_map.as_ref(), 0, start_row_id, len)
-                            .await;
-                    visibility_map = Some(new_bitmap);
-                }
+        }
+
+        if common_chunk_range.is_none() {
+            return Ok((true, None));
+        };
 
-                if !self.end_keys.is_empty() && arrays[0].is_some() {
-                    let array = arrays[0].as_ref().unwrap();
-                    let len = array.len();
-                    let end_key = &self.end_keys[0];
-                    if end_key - &array.get(len - 1) < DataValue::Int32(0) {
-                        // this block's last key is greater than the `end_key`,
-                        // so we will finish scan after scan this block
-                        meet_end_key = true;
-                        let end_row_id = (0..len)
-                            .position(|idx| end_key - &array.get(idx) < DataValue::Int32(0))
-                            .unwrap();
+        // For now, we only support range-filter scan by the sort key column.
+        if let Some(array) = self.sort_key_position.and_then(|id| arrays[id].as_ref()) {
+            let len = array.len();
+            if !self.start_keys.is_empty() && !self.meet_start_key_before {
+                // find the first row in range to begin with
+                let start_key = &self.start_keys[0];
+                match (0..len).position(|idx| array.get(idx) >= *start_key) {
+                    Some(start_row_id) => {
+                        self.meet_start_key_before = true;
                         let new_bitmap =
-                            Self::mark_inaccessible(visibility_map.as_ref(), end_row_id, len, len)
+                            Self::mark_inaccessible(visibility_map.as_ref(), 0, start_row_id, len)
                                 .await;
                         visibility_map = Some(new_bitmap);
                     }
+                    // the `begin_key` is greater than all of the data in this batch
+                    None => return Ok((false, None)),
                 }
             }
-        }
 
-        if common_chunk_range.is_none() {
-            return Ok((true, None));
-        };
+            if !self.end_keys.is_empty() {
+                let end_key = &self.end_keys[0];
+                if array.get(len - 1) > *end_key {
+                    // this batch's last key is greater than the `end_key`,
+                    // so we will finish scan after scan this batch
+                    meet_end_key = true;
+                    let end_row_id = (0..len).position(|idx| array.get(idx) > *end_key).unwrap();
+                    let new_bitmap =
+                        Self::mark_inaccessible(visibility_map.as_ref(), end_row_id, len, len)
+                            .await;
+                    visibility_map = Some(new_bitmap);
+                }
+            }
+        }
 
         Ok((
             meet_end_key,
@@ -354,6 +377,36 @@ impl RowSetIterator {
         }
     }
 
+    /// Skip the rows in the current blocks of the filter columns if none of them can satisfy the
+    /// filter, according to the minimum and maximum values of the blocks. Returns whether any row
+    /// is skipped.
+    fn skip_unmatched_blocks(&mut self) -> bool {
+        let expr = match &self.filter_expr {
+            Some((expr, _)) if !self.filter_column_indexes.is_empty() => expr,
+            _ => return false,
+        };
+        let row_id = self.column_iterators[0].fetch_current_row_id();
+        // the statistics are valid for the rows before the end of the first finished block
+        let mut end_row_id = u32::MAX;
+        let mut min_max = vec![None; self.column_refs.len()];
+        for (id, index, data_type) in &self.filter_column_indexes {
+            let block = index.index(index.block_of_row(row_id));
+            let block_end_row_id = block.first_rowid + block.row_count;
+            if row_id >= block_end_row_id {
+                return false;
+            }
+            end_row_id = end_row_id.min(block_end_row_id);
+            min_max[*id] = block_min_max(block, data_type);
+        }
+        if filter_may_match(expr, &|id| min_max[id].clone()) {
+            return false;
+        }
+        for iter in &mut self.column_iterators {
+            iter.skip((end_row_id - row_id) as usize);
+        }
+        true
+    }
+
     /// mark all positions between `start_id`(include) and `end_id`(not include) false in a new
     /// `BitVec`, the len of this `BitVec` is `len`, and if a position is marked false in
     /// `bitmap`, we just keep in false in the new `Bitvec`
@@ -398,7 +451,7 @@ mod tests {
         helper_build_rowset, helper_build_rowset_with_first_key_recorded,
     };
     use crate::storage::secondary::SecondaryRowHandler;
-    use crate::types::{DataType, DataValue, PhysicalDataTypeKind};
+    use crate::types::{DataType, DataTypeExt, DataValue, PhysicalDataTypeKind};
 
     #[tokio::test]
     async fn test_rowset_iterator() {
@@ -817,6 +870,82 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_rowset_iterator_seek_by_sort_key() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let rowset = Arc::new(helper_build_rowset_with_first_key_recorded(&tempdir).await);
+        // the sort key column `v1` is not the first column to scan
+        let mut it = rowset
+            .iter(
+                vec![StorageColumnRef::Idx(1), StorageColumnRef::Idx(0)].into(),
+                vec![],
+                ColumnSeekPosition::SortKey(DataValue::Int32(100)),
+                None,
+                &[DataValue::Int32(100)],
+                &[DataValue::Int32(120)],
+            )
+            .await
+            .unwrap();
+
+        let mut column0 = vec![];
+        let mut column1 = vec![];
+        while let Some(chunk) = it.next_batch(None).await.unwrap() {
+            data_from_chunk(&chunk, &mut column0, 0).await;
+            data_from_chunk(&chunk, &mut column1, 1).await;
+        }
+        assert_eq!(column0, (101..=121).collect_vec());
+        assert_eq!(column1, (100..=120).collect_vec());
+    }
+
+    #[tokio::test]
+    async fn test_rowset_iterator_skip_blocks_by_min_max() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let rowset = Arc::new(helper_build_rowset_with_first_key_recorded(&tempdir).await);
+        // v2 >= 250: the first 8 blocks of v2, whose values are in 1..=224, are skipped.
+        let expr = BoundExpr::BinaryOp(BoundBinaryOp {
+            op: BinaryOperator::GtEq,
+            left_expr: Box::new(BoundExpr::InputRef(BoundInputRef {
+                index: 1,
+                return_type: DataTypeKind::Int(None).not_null(),
+            })),
+            right_expr: Box::new(BoundExpr::Constant(DataValue::Int32(250))),
+            return_type: Some(DataTypeKind::Boolean.nullable()),
+        });
+        let mut it = rowset
+            .iter(
+                vec![StorageColumnRef::Idx(0), StorageColumnRef::Idx(1)].into(),
+                vec![],
+                ColumnSeekPosition::RowId(0),
+                Some(expr),
+                &[],
+                &[],
+            )
+            .await
+            .unwrap();
+        assert!(it.skip_unmatched_blocks());
+
+        let mut column0 = vec![];
+        let mut column1 = vec![];
+        while let Some(chunk) = it.next_batch(None).await.unwrap() {
+            data_from_chunk(&chunk, &mut column0, 0).await;
+            data_from_chunk(&chunk, &mut column1, 1).await;
+        }
+        assert_eq!(column0, (249..=279).collect_vec());
+        assert_eq!(column1, (250..=280).collect_vec());
+        assert!(!rowset.may_match(
+            &[StorageColumnRef::Idx(0)],
+            &BoundExpr::BinaryOp(BoundBinaryOp {
+                op: BinaryOperator::Gt,
+                left_expr: Box::new(BoundExpr::InputRef(BoundInputRef {
+                    index: 0,
+                    return_type: DataTypeKind::Int(None).not_null(),
+                })),
+                right_expr: Box::new(BoundExpr::Constant(DataValue::Int32(279))),
+                return_type: Some(DataTypeKind::Boolean.nullable()),
+            }),
+        ));
+    }
+
     async fn data_from_chunk(chunk: &StorageChunk, column: &mut Vec<i32>, index: usize) {
         if let ArrayImpl::Int32(array) = chunk.array_at(index) {
             let bit_map = match chunk.visibility() {
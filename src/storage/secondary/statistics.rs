@@ -10,16 +10,26 @@
 //!
 //! RowCount is NOT a precise statistics. It simply adds up the row counts of all blocks. As there
 //! might be rows deleted in deletion vector, the aggregated RowCount is not always accurate.
+//!
+//! ## `MinValue` and `MaxValue`
+//!
+//! The minimum and maximum non-NULL values of a block, encoded in the same way as the items of the
+//! block. They are absent if all items of the block are NULL. As the values of deleted rows are
+//! still included, they can only be used to skip the blocks and RowSets which can not match a
+//! filter.
 
 use risinglight_proto::rowset::block_statistics::BlockStatisticsType;
 
 use super::index::ColumnIndex;
-use crate::types::DataValue;
+use crate::types::{DataType, DataValue};
 
 mod row_count;
 use row_count::*;
 mod distinct_value;
 use distinct_value::*;
+mod min_max;
+pub use min_max::{block_has_min_max, block_min_max, filter_may_match};
+use min_max::{MaxValueGlobalAgg, MinValueGlobalAgg};
 mod statistics_builder;
 pub use statistics_builder::*;
 
@@ -31,9 +41,12 @@ pub trait StatisticsGlobalAgg {
 
 pub fn create_statistics_global_aggregator(
     ty: BlockStatisticsType,
+    data_type: DataType,
 ) -> Box<dyn StatisticsGlobalAgg> {
     match ty {
         BlockStatisticsType::RowCount => Box::new(RowCountGlobalAgg::create()),
         BlockStatisticsType::DistinctValue => Box::new(DistinctValueGlobalAgg::create()),
+        BlockStatisticsType::MinValue => Box::new(MinValueGlobalAgg::create(data_type)),
+        BlockStatisticsType::MaxValue => Box::new(MaxValueGlobalAgg::create(data_type)),
     }
 }
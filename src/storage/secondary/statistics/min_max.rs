@@ -0,0 +1,273 @@
+// Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
+
+use std::cmp::Ordering;
+use std::mem::discriminant;
+
+use risinglight_proto::rowset::block_statistics::BlockStatisticsType;
+use risinglight_proto::rowset::BlockIndex;
+
+use super::StatisticsGlobalAgg;
+use crate::array::LikePattern;
+use crate::binder::{BoundBinaryOp, BoundExpr};
+use crate::parser::BinaryOperator;
+use crate::storage::secondary::encode::decode_value;
+use crate::storage::secondary::index::ColumnIndex;
+use crate::types::{DataType, DataValue};
+
+/// Gather the minimum value from column index.
+pub struct MinValueGlobalAgg {
+    data_type: DataType,
+    min: DataValue,
+}
+
+impl MinValueGlobalAgg {
+    pub fn create(data_type: DataType) -> Self {
+        Self {
+            data_type,
+            min: DataValue::Null,
+        }
+    }
+}
+
+impl StatisticsGlobalAgg for MinValueGlobalAgg {
+    fn apply_batch(&mut self, index: &ColumnIndex) {
+        for index in index.indexes() {
+            if let Some(value) = block_stat(index, BlockStatisticsType::MinValue, &self.data_type) {
+                if self.min == DataValue::Null || value < self.min {
+                    self.min = value;
+                }
+            }
+        }
+    }
+
+    fn get_output(&self) -> DataValue {
+        self.min.clone()
+    }
+}
+
+/// Gather the maximum value from column index.
+pub struct MaxValueGlobalAgg {
+    data_type: DataType,
+    max: DataValue,
+}
+
+impl MaxValueGlobalAgg {
+    pub fn create(data_type: DataType) -> Self {
+        Self {
+            data_type,
+            max: DataValue::Null,
+        }
+    }
+}
+
+impl StatisticsGlobalAgg for MaxValueGlobalAgg {
+    fn apply_batch(&mut self, index: &ColumnIndex) {
+        for index in index.indexes() {
+            if let Some(value) = block_stat(index, BlockStatisticsType::MaxValue, &self.data_type) {
+                // NULL is less than any non-NULL values
+                if value > self.max {
+                    self.max = value;
+                }
+            }
+        }
+    }
+
+    fn get_output(&self) -> DataValue {
+        self.max.clone()
+    }
+}
+
+/// Get the minimum and maximum values of a block, if recorded.
+pub fn block_min_max(index: &BlockIndex, data_type: &DataType) -> Option<(DataValue, DataValue)> {
+    let min = block_stat(index, BlockStatisticsType::MinValue, data_type)?;
+    let max = block_stat(index, BlockStatisticsType::MaxValue, data_type)?;
+    Some((min, max))
+}
+
+/// Whether the minimum and maximum values of a block are known.
+///
+/// They are absent if all items of the block are NULL, which has no distinct values, or if the
+/// block was written before they were recorded.
+pub fn block_has_min_max(index: &BlockIndex) -> bool {
+    (index.stats.iter()).any(|stat| match stat.block_stat_type() {
+        BlockStatisticsType::MinValue => true,
+        BlockStatisticsType::DistinctValue => {
+            u64::from_le_bytes(stat.body.clone().try_into().unwrap()) == 0
+        }
+        _ => false,
+    })
+}
+
+fn block_stat(
+    index: &BlockIndex,
+    ty: BlockStatisticsType,
+    data_type: &DataType,
+) -> Option<DataValue> {
+    (index.stats.iter())
+        .find(|stat| stat.block_stat_type() == ty)
+        .map(|stat| decode_value(&data_type.physical_kind, &stat.body))
+}
+
+/// Returns `false` if no row can satisfy the filter, where `min_max` returns the minimum and
+/// maximum values of the column referred by each input ref, if known.
+///
+/// Only the comparisons between a column and a constant, and the `LIKE` patterns with literal
+/// prefixes are checked.
+pub fn filter_may_match(
+    expr: &BoundExpr,
+    min_max: &dyn Fn(usize) -> Option<(DataValue, DataValue)>,
+) -> bool {
+    use BoundExpr::{Constant, InputRef};
+
+    let BoundBinaryOp {
+        op,
+        left_expr,
+        right_expr,
+        ..
+    } = match expr {
+        BoundExpr::BinaryOp(binary_op) => binary_op,
+        _ => return true,
+    };
+    match (op, &**left_expr, &**right_expr) {
+        (BinaryOperator::And, left, right) => {
+            filter_may_match(left, min_max) && filter_may_match(right, min_max)
+        }
+        (BinaryOperator::Or, left, right) => {
+            filter_may_match(left, min_max) || filter_may_match(right, min_max)
+        }
+        (BinaryOperator::Like, InputRef(input_ref), Constant(DataValue::String(pattern))) => {
+            match (min_max(input_ref.index), LikePattern::new(pattern).range()) {
+                (Some((min, max)), Some((lower, upper))) => {
+                    range_may_match(&BinaryOperator::GtEq, &min, &max, &DataValue::String(lower))
+                        && upper.map_or(true, |upper| {
+                            let upper = DataValue::String(upper);
+                            range_may_match(&BinaryOperator::LtEq, &min, &max, &upper)
+                        })
+                }
+                _ => true,
+            }
+        }
+        (op, InputRef(input_ref), Constant(value)) => match min_max(input_ref.index) {
+            Some((min, max)) => range_may_match(op, &min, &max, value),
+            None => true,
+        },
+        (op, Constant(value), InputRef(input_ref)) => {
+            let op = match op {
+                BinaryOperator::Gt => BinaryOperator::Lt,
+                BinaryOperator::GtEq => BinaryOperator::LtEq,
+                BinaryOperator::Lt => BinaryOperator::Gt,
+                BinaryOperator::LtEq => BinaryOperator::GtEq,
+                op => op.clone(),
+            };
+            match min_max(input_ref.index) {
+                Some((min, max)) => range_may_match(&op, &min, &max, value),
+                None => true,
+            }
+        }
+        _ => true,
+    }
+}
+
+/// Returns `false` if no value in `[min, max]` satisfies `value op constant`.
+fn range_may_match(
+    op: &BinaryOperator,
+    min: &DataValue,
+    max: &DataValue,
+    constant: &DataValue,
+) -> bool {
+    // values of different types are not comparable
+    if discriminant(min) != discriminant(constant) || discriminant(max) != discriminant(constant) {
+        return true;
+    }
+    // incomparable values such as NaN always pass the checks
+    let (min, max) = (min.partial_cmp(constant), max.partial_cmp(constant));
+    match op {
+        BinaryOperator::Eq => max != Some(Ordering::Less) && min != Some(Ordering::Greater),
+        BinaryOperator::Gt => !matches!(max, Some(Ordering::Less | Ordering::Equal)),
+        BinaryOperator::GtEq => max != Some(Ordering::Less),
+        BinaryOperator::Lt => !matches!(min, Some(Ordering::Greater | Ordering::Equal)),
+        BinaryOperator::LtEq => min != Some(Ordering::Greater),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::StatisticsBuilder;
+    use super::*;
+    use crate::binder::BoundInputRef;
+    use crate::types::{DataTypeExt, DataTypeKind};
+
+    #[test]
+    fn test_filter_may_match() {
+        let input_ref = || {
+            Box::new(BoundExpr::InputRef(BoundInputRef {
+                index: 0,
+                return_type: DataTypeKind::Int(None).not_null(),
+            }))
+        };
+        let constant = |value| Box::new(BoundExpr::Constant(DataValue::Int32(value)));
+        let binary_op = |op, left_expr, right_expr| {
+            BoundExpr::BinaryOp(BoundBinaryOp {
+                op,
+                left_expr,
+                right_expr,
+                return_type: Some(DataTypeKind::Boolean.nullable()),
+            })
+        };
+        let min_max = |_| Some((DataValue::Int32(10), DataValue::Int32(20)));
+
+        let gt = binary_op(BinaryOperator::Gt, input_ref(), constant(20));
+        assert!(!filter_may_match(&gt, &min_max));
+        let lt = binary_op(BinaryOperator::Lt, constant(15), input_ref());
+        assert!(filter_may_match(&lt, &min_max));
+        let eq = binary_op(BinaryOperator::Eq, input_ref(), constant(5));
+        assert!(!filter_may_match(&eq, &min_max));
+        let or = binary_op(BinaryOperator::Or, Box::new(eq.clone()), Box::new(lt));
+        assert!(filter_may_match(&or, &min_max));
+        let and = binary_op(BinaryOperator::And, Box::new(eq.clone()), Box::new(or));
+        assert!(!filter_may_match(&and, &min_max));
+        assert!(filter_may_match(&eq, &|_| None));
+
+        let like = |pattern: &str| {
+            binary_op(
+                BinaryOperator::Like,
+                input_ref(),
+                Box::new(BoundExpr::Constant(DataValue::String(pattern.into()))),
+            )
+        };
+        let min_max = |_| {
+            Some((
+                DataValue::String("apple".into()),
+                DataValue::String("banana".into()),
+            ))
+        };
+        assert!(filter_may_match(&like("b%"), &min_max));
+        assert!(!filter_may_match(&like("c%"), &min_max));
+        assert!(!filter_may_match(&like("ap"), &min_max));
+        assert!(filter_may_match(&like("%c"), &min_max));
+    }
+
+    #[test]
+    fn test_block_has_min_max() {
+        let block_index = |items: &[Option<&[u8]>]| {
+            let mut builder = StatisticsBuilder::new();
+            for item in items {
+                builder.add_item(*item);
+            }
+            BlockIndex {
+                stats: builder.get_statistics(),
+                ..Default::default()
+            }
+        };
+        assert!(block_has_min_max(&block_index(&[Some(b"1"), None])));
+        assert!(block_has_min_max(&block_index(&[None, None])));
+
+        // a block written before the minimum and maximum values are recorded
+        let mut index = block_index(&[Some(b"1"), None]);
+        index
+            .stats
+            .retain(|stat| stat.block_stat_type() == BlockStatisticsType::DistinctValue);
+        assert!(!block_has_min_max(&index));
+    }
+}
@@ -1,5 +1,6 @@
 // Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
 
+use std::cmp::Ordering;
 use std::collections::HashSet;
 
 use risinglight_proto::rowset::block_statistics::BlockStatisticsType;
@@ -7,18 +8,37 @@ use risinglight_proto::rowset::BlockStatistics;
 
 pub struct StatisticsBuilder<'a> {
     distinct_values: HashSet<&'a [u8]>,
+    min_value: Option<&'a [u8]>,
+    max_value: Option<&'a [u8]>,
+    /// The order of the encoded items.
+    cmp: fn(&[u8], &[u8]) -> Ordering,
 }
 
 impl<'a> StatisticsBuilder<'a> {
+    /// Create a builder whose items are ordered by bytes.
     pub fn new() -> Self {
+        Self::with_order(<[u8] as Ord>::cmp)
+    }
+
+    /// Create a builder whose items are ordered by `cmp`.
+    pub fn with_order(cmp: fn(&[u8], &[u8]) -> Ordering) -> Self {
         Self {
             distinct_values: HashSet::<&'a [u8]>::new(),
+            min_value: None,
+            max_value: None,
+            cmp,
         }
     }
 
     pub fn add_item(&mut self, data: Option<&'a [u8]>) {
         if let Some(data) = data {
             self.distinct_values.insert(data);
+            if (self.min_value).map_or(true, |min| (self.cmp)(data, min) == Ordering::Less) {
+                self.min_value = Some(data);
+            }
+            if (self.max_value).map_or(true, |max| (self.cmp)(data, max) == Ordering::Greater) {
+                self.max_value = Some(data);
+            }
         }
     }
 
@@ -28,7 +48,19 @@ impl<'a> StatisticsBuilder<'a> {
             block_stat_type: BlockStatisticsType::DistinctValue as i32,
             body: distinct_count.to_le_bytes().to_vec(),
         };
-        vec![distinct_stat]
+        let mut stats = vec![distinct_stat];
+        // the min and max values are encoded in the same way as the items
+        if let (Some(min), Some(max)) = (self.min_value, self.max_value) {
+            stats.push(BlockStatistics {
+                block_stat_type: BlockStatisticsType::MinValue as i32,
+                body: min.to_vec(),
+            });
+            stats.push(BlockStatistics {
+                block_stat_type: BlockStatisticsType::MaxValue as i32,
+                body: max.to_vec(),
+            });
+        }
+        stats
     }
 }
 
@@ -37,6 +69,7 @@ mod tests {
     use bytes::Buf;
 
     use super::*;
+    use crate::storage::secondary::encode::PrimitiveFixedWidthEncode;
 
     #[test]
     fn test_distinct_values() {
@@ -54,4 +87,19 @@ mod tests {
         let mut body = &stats[0].body[..];
         assert_eq!(body.get_u64_le(), 3);
     }
+
+    #[test]
+    fn test_min_max_values() {
+        let items = [3, -1, 2, 10, -5].map(|x: i32| x.to_le_bytes());
+        let mut builder = StatisticsBuilder::with_order(i32::cmp_encoded);
+        for item in &items {
+            builder.add_item(Some(&item[..]));
+        }
+        builder.add_item(None);
+        let stats = builder.get_statistics();
+        assert_eq!(stats[1].block_stat_type(), BlockStatisticsType::MinValue);
+        assert_eq!(stats[1].body, (-5i32).to_le_bytes());
+        assert_eq!(stats[2].block_stat_type(), BlockStatisticsType::MaxValue);
+        assert_eq!(stats[2].body, 10i32.to_le_bytes());
+    }
 }
@@ -19,7 +19,9 @@ use super::{
 use crate::array::DataChunk;
 use crate::binder::BoundExpr;
 use crate::catalog::find_sort_key_id;
-use crate::storage::secondary::statistics::create_statistics_global_aggregator;
+use crate::storage::secondary::statistics::{
+    block_has_min_max, create_statistics_global_aggregator,
+};
 use crate::storage::{StorageColumnRef, StorageResult, Transaction};
 use crate::types::DataValue;
 
@@ -237,15 +239,14 @@ impl SecondaryTransaction {
                 let rowset = self.version.get_rowset(self.table.table_id(), *rowset_id);
 
                 // Get DV id and read DVs
-                let dvs = self
-                    .snapshot
-                    .get_dvs_of(self.table.table_id(), *rowset_id)
-                    .map(|dvs| {
-                        dvs.iter()
-                            .map(|dv_id| self.version.get_dv(self.table.table_id(), *dv_id))
-                            .collect_vec()
-                    })
-                    .unwrap_or_default();
+                let dvs = self.dvs_of(*rowset_id);
+
+                // skip the rowset if no row can satisfy the filter
+                if let Some(expr) = &expr {
+                    if !rowset.may_match(col_idx, expr) {
+                        continue;
+                    }
+                }
 
                 let start_rowid = rowset.start_rowid(begin_keys).await;
                 iters.push(
@@ -295,20 +296,43 @@ impl SecondaryTransaction {
         &self,
         ty: &[(BlockStatisticsType, StorageColumnRef)],
     ) -> Vec<DataValue> {
+        self.aggregate_block_stat_of(ty, |_| true)
+    }
+
+    /// Aggregate block statistics of the rowsets whose statistics are exact, see
+    /// [`Self::has_exact_block_stat`].
+    pub fn aggregate_exact_block_stat(
+        &self,
+        ty: &[(BlockStatisticsType, StorageColumnRef)],
+    ) -> Vec<DataValue> {
+        self.aggregate_block_stat_of(ty, |rowset_id| self.has_exact_block_stat(rowset_id, ty))
+    }
+
+    fn aggregate_block_stat_of(
+        &self,
+        ty: &[(BlockStatisticsType, StorageColumnRef)],
+        filter: impl Fn(u32) -> bool,
+    ) -> Vec<DataValue> {
+        let user_col_idx = ty
+            .iter()
+            .map(|(_, col_idx)| match col_idx {
+                StorageColumnRef::Idx(idx) => *idx as usize,
+                _ => panic!("unsupported column ref for block aggregation"),
+            })
+            .collect_vec();
         let mut agg = ty
             .iter()
-            .map(|(ty, _)| create_statistics_global_aggregator(*ty))
+            .zip(&user_col_idx)
+            .map(|((ty, _), idx)| {
+                create_statistics_global_aggregator(*ty, self.table.columns[*idx].datatype())
+            })
             .collect_vec();
 
         if let Some(rowsets) = self.snapshot.get_rowsets_of(self.table.table_id()) {
-            for rowset_id in rowsets {
+            for rowset_id in rowsets.iter().filter(|id| filter(**id)) {
                 let rowset = self.version.get_rowset(self.table.table_id(), *rowset_id);
-                for ((_, col_idx), agg) in ty.iter().zip(agg.iter_mut()) {
-                    let user_col_idx = match col_idx {
-                        StorageColumnRef::Idx(idx) => idx,
-                        _ => panic!("unsupported column ref for block aggregation"),
-                    };
-                    let column = rowset.column(*user_col_idx as usize);
+                for (idx, agg) in user_col_idx.iter().zip(agg.iter_mut()) {
+                    let column = rowset.column(*idx);
                     agg.apply_batch(column.index());
                 }
             }
@@ -317,6 +341,68 @@ impl SecondaryTransaction {
         agg.into_iter().map(|agg| agg.get_output()).collect_vec()
     }
 
+    /// Scan the columns of `ty` in the rowsets whose statistics can not be used in
+    /// [`Self::aggregate_exact_block_stat`].
+    pub async fn scan_rowsets_without_exact_block_stat(
+        &self,
+        ty: &[(BlockStatisticsType, StorageColumnRef)],
+    ) -> StorageResult<SecondaryTableTxnIterator> {
+        let col_idx = ty.iter().map(|(_, column)| *column).collect_vec();
+        let mut iters = vec![];
+        if let Some(rowsets) = self.snapshot.get_rowsets_of(self.table.table_id()) {
+            for rowset_id in rowsets {
+                if self.has_exact_block_stat(*rowset_id, ty) {
+                    continue;
+                }
+                let dvs = self.dvs_of(*rowset_id);
+                let rowset = self.version.get_rowset(self.table.table_id(), *rowset_id);
+                let start_rowid = rowset.start_rowid(&[]).await;
+                iters.push(
+                    rowset
+                        .iter(col_idx.as_slice().into(), dvs, start_rowid, None, &[], &[])
+                        .await?,
+                );
+            }
+        }
+        Ok(SecondaryTableTxnIterator::new(
+            ConcatIterator::new(iters).into(),
+        ))
+    }
+
+    /// Whether the block statistics `ty` of a rowset are exact.
+    ///
+    /// The statistics of a rowset still cover its deleted rows, and the minimum and maximum values
+    /// are absent in the rowsets written before they were recorded.
+    fn has_exact_block_stat(
+        &self,
+        rowset_id: u32,
+        ty: &[(BlockStatisticsType, StorageColumnRef)],
+    ) -> bool {
+        if !self.dvs_of(rowset_id).is_empty() {
+            return false;
+        }
+        let rowset = self.version.get_rowset(self.table.table_id(), rowset_id);
+        ty.iter().all(|(ty, column)| match (ty, column) {
+            (
+                BlockStatisticsType::MinValue | BlockStatisticsType::MaxValue,
+                StorageColumnRef::Idx(idx),
+            ) => (rowset.column(*idx as usize).index().indexes().iter()).all(block_has_min_max),
+            _ => true,
+        })
+    }
+
+    /// Get the delete vectors of a rowset in the snapshot.
+    fn dvs_of(&self, rowset_id: u32) -> Vec<Arc<DeleteVector>> {
+        self.snapshot
+            .get_dvs_of(self.table.table_id(), rowset_id)
+            .map(|dvs| {
+                dvs.iter()
+                    .map(|dv_id| self.version.get_dv(self.table.table_id(), *dv_id))
+                    .collect_vec()
+            })
+            .unwrap_or_default()
+    }
+
     pub async fn append_inner(&mut self, columns: DataChunk) -> StorageResult<()> {
         if self.read_only {
             panic!("Txn is read-only but append is called");
@@ -13,7 +13,7 @@ use crate::storage::{StorageChunk, StorageResult, TxnIterator};
 pub enum SecondaryIterator {
     Concat(ConcatIterator),
     Merge(MergeIterator),
-    RowSet(RowSetIterator),
+    RowSet(Box<RowSetIterator>),
     #[cfg(test)]
     Test(super::tests::TestIterator),
 }
@@ -21,6 +21,12 @@ pub enum SecondaryIterator {
 #[enum_dispatch(SecondaryIterator)]
 pub trait SecondaryIteratorImpl {}
 
+impl From<RowSetIterator> for SecondaryIterator {
+    fn from(iter: RowSetIterator) -> Self {
+        Self::RowSet(Box::new(iter))
+    }
+}
+
 /// An iterator over all data in a transaction.
 ///
 /// TODO: Lifetime of the iterator should be bound to the transaction.
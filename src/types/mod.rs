@@ -1,5 +1,6 @@
 // Copyright 2022 RisingLight Project Authors. Licensed under Apache-2.0.
 
+use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
 
 use num_traits::ToPrimitive;
@@ -172,7 +173,9 @@ impl Hash for DataValue {
             Self::Bool(b) => b.hash(state),
             Self::Int32(i) => i.hash(state),
             Self::Int64(i) => i.hash(state),
-            Self::Float64(_) => panic!("do not support hashing f64"),
+            // `0.0` and `-0.0` are equal, so they must have the same hash
+            Self::Float64(f) if *f == 0.0 => 0.0f64.to_bits().hash(state),
+            Self::Float64(f) => f.to_bits().hash(state),
             Self::String(s) => s.hash(state),
             Self::Blob(v) => v.hash(state),
             Self::Decimal(v) => v.hash(state),
@@ -213,6 +216,21 @@ impl_arith_for_datavalue!(Div, div);
 impl_arith_for_datavalue!(Rem, rem);
 
 impl DataValue {
+    /// Compare two values in a total order, where `NaN` is greater than any other float.
+    ///
+    /// NULL is less than any non-NULL values, as in [`PartialOrd`].
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Float64(x), Self::Float64(y)) => match (x.is_nan(), y.is_nan()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => x.partial_cmp(y).unwrap(),
+            },
+            _ => self.partial_cmp(other).unwrap(),
+        }
+    }
+
     /// Whether the value is divisible by another.
     pub fn is_divisible_by(&self, other: &DataValue) -> bool {
         use DataValue::*;
@@ -329,6 +347,8 @@ pub enum ConvertError {
     Cast(String, &'static str),
     #[error("constant {0:?} overflows {1:?}")]
     Overflow(DataValue, DataTypeKind),
+    #[error("failed to execute function: {0}")]
+    Function(#[from] crate::function::FunctionError),
 }
 
 /// memory table row type
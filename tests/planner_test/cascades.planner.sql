@@ -0,0 +1,121 @@
+-- prepare
+create table t1(v1 int not null, v2 int not null);
+create table t2(v3 int not null, v4 int not null);
+insert into t1 values (1, 10), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60), (7, 70), (8, 80);
+insert into t2 values (1, 100), (3, 300), (5, 500), (7, 700), (9, 900), (11, 1100);
+
+/*
+
+*/
+
+-- equi-join implemented by hash join, with the filter pushed down
+explain select * from t1 join t2 on v1 = v3 where v2 > 15
+
+/*
+PhysicalProjection:
+    InputRef #0
+    InputRef #1
+    InputRef #2
+    InputRef #3
+    estimated rows: 3
+  PhysicalHashJoin:
+      op Inner,
+      predicate: Eq(InputRef #0, InputRef #2)
+      estimated rows: 3
+    PhysicalTableScan:
+        table #0,
+        columns [0, 1],
+        with_row_handler: false,
+        is_sorted: false,
+        expr: Gt(InputRef #1, Int32(15) (const))
+        estimated rows: 3
+    PhysicalTableScan:
+        table #1,
+        columns [0, 1],
+        with_row_handler: false,
+        is_sorted: false,
+        expr: None
+        estimated rows: 6
+*/
+
+-- non-equi join implemented by nested loop join
+explain select * from t1 join t2 on v1 < v3
+
+/*
+PhysicalProjection:
+    InputRef #0
+    InputRef #1
+    InputRef #2
+    InputRef #3
+    estimated rows: 16
+  PhysicalNestedLoopJoin:
+      op Inner,
+      predicate: Lt(InputRef #0, InputRef #2)
+      estimated rows: 16
+    PhysicalTableScan:
+        table #0,
+        columns [0, 1],
+        with_row_handler: false,
+        is_sorted: false,
+        expr: None
+        estimated rows: 8
+    PhysicalTableScan:
+        table #1,
+        columns [0, 1],
+        with_row_handler: false,
+        is_sorted: false,
+        expr: None
+        estimated rows: 6
+*/
+
+-- right outer join with an equal key is implemented by hash join
+explain select * from t1 right join t2 on v1 = v3
+
+/*
+PhysicalProjection:
+    InputRef #0
+    InputRef #1
+    InputRef #2
+    InputRef #3
+    estimated rows: 6
+  PhysicalHashJoin:
+      op Right Outer,
+      predicate: Eq(InputRef #0, InputRef #2)
+      estimated rows: 6
+    PhysicalTableScan:
+        table #0,
+        columns [0, 1],
+        with_row_handler: false,
+        is_sorted: false,
+        expr: None
+        estimated rows: 8
+    PhysicalTableScan:
+        table #1,
+        columns [0, 1],
+        with_row_handler: false,
+        is_sorted: false,
+        expr: None
+        estimated rows: 6
+*/
+
+-- aggregation implemented by hash aggregation
+explain select v2, count(*) from t1 group by v2
+
+/*
+PhysicalProjection:
+    InputRef #0
+    InputRef #1
+    estimated rows: 8
+  PhysicalHashAgg:
+      InputRef #0
+      count(InputRef #0) -> INT
+      estimated rows: 8
+    PhysicalTableScan:
+        table #0,
+        columns [1],
+        with_row_handler: false,
+        is_sorted: false,
+        expr: None
+        estimated rows: 8
+*/
+
@@ -4,14 +4,10 @@ explain select count(*) from t
 /*
 PhysicalProjection:
     InputRef #0
-  PhysicalSimpleAgg:
+    estimated rows: 1
+  PhysicalMetadataScan: table #0
       count(InputRef #0) -> INT
-    PhysicalTableScan:
-        table #0,
-        columns [],
-        with_row_handler: true,
-        is_sorted: false,
-        expr: None
+      estimated rows: 1
 */
 
 -- count(*) with projection
@@ -20,13 +16,9 @@ explain select count(*) + 1 from t
 /*
 PhysicalProjection:
     (InputRef #0 + 1)
-  PhysicalSimpleAgg:
+    estimated rows: 1
+  PhysicalMetadataScan: table #0
       count(InputRef #0) -> INT
-    PhysicalTableScan:
-        table #0,
-        columns [],
-        with_row_handler: true,
-        is_sorted: false,
-        expr: None
+      estimated rows: 1
 */
 
@@ -0,0 +1,52 @@
+-- prepare
+create table t(v1 int not null, v2 int not null);
+
+/*
+
+*/
+
+-- the sort key is computed by the projection below the order, and pruned by the one above
+explain select v1 from t order by v1 * 2 - v2 desc
+
+/*
+PhysicalProjection:
+    InputRef #0
+    estimated rows: 0
+  PhysicalOrder:
+      [InputRef #1 (desc)]
+      estimated rows: 0
+    PhysicalProjection:
+        InputRef #0
+        ((InputRef #0 * 2) - InputRef #1)
+        estimated rows: 0
+      PhysicalTableScan:
+          table #0,
+          columns [0, 1],
+          with_row_handler: false,
+          is_sorted: false,
+          expr: None
+          estimated rows: 0
+*/
+
+-- the sort key of top n refers to the alias of an output column
+explain select v1 * 10 + v2 as s from t order by -s limit 2
+
+/*
+PhysicalProjection:
+    InputRef #0
+    estimated rows: 0
+  PhysicalTopN: offset: 0, limit: 2, order by [InputRef #1 (asc)]
+      estimated rows: 0
+    PhysicalProjection:
+        ((InputRef #0 * 10) + InputRef #1) (alias to s)
+        BoundUnaryOp { op: Minus, expr: Plus(Multiply(InputRef #0, Int32(10) (const)), InputRef #1), return_type: Some(Int(None) (null)) }
+        estimated rows: 0
+      PhysicalTableScan:
+          table #0,
+          columns [0, 1],
+          with_row_handler: false,
+          is_sorted: false,
+          expr: None
+          estimated rows: 0
+*/
+
@@ -0,0 +1,211 @@
+-- prepare
+create table a(k int not null, v int not null, primary key(k));
+create table b(k int not null, w int not null, primary key(k));
+insert into a values (1, 10), (2, 20), (3, 30);
+insert into b values (1, 100), (2, 200), (4, 400);
+
+/*
+
+*/
+
+-- aggregation on the scan sorted by the primary key is implemented by sort aggregation
+explain select k, sum(v) from a group by k order by k
+
+/*
+PhysicalProjection:
+    InputRef #0
+    InputRef #1
+    estimated rows: 3
+  PhysicalSortAgg:
+      InputRef #0
+      sum(InputRef #1) -> INT
+      estimated rows: 3
+    PhysicalTableScan:
+        table #0,
+        columns [0, 1],
+        with_row_handler: false,
+        is_sorted: true,
+        expr: None
+        estimated rows: 3
+*/
+
+-- the memo-based optimizer also implements aggregation on the sorted scan by sort aggregation
+explain select k, sum(v) from a group by k order by k
+
+/*
+PhysicalProjection:
+    InputRef #0
+    InputRef #1
+    estimated rows: 3
+  PhysicalSortAgg:
+      InputRef #0
+      sum(InputRef #1) -> INT
+      estimated rows: 3
+    PhysicalTableScan:
+        table #0,
+        columns [0, 1],
+        with_row_handler: false,
+        is_sorted: true,
+        expr: None
+        estimated rows: 3
+*/
+
+-- aggregation on the unsorted scan is implemented by hash aggregation
+explain select k, sum(v) from a group by k
+
+/*
+PhysicalProjection:
+    InputRef #0
+    InputRef #1
+    estimated rows: 3
+  PhysicalHashAgg:
+      InputRef #0
+      sum(InputRef #1) -> INT
+      estimated rows: 3
+    PhysicalTableScan:
+        table #0,
+        columns [0, 1],
+        with_row_handler: false,
+        is_sorted: false,
+        expr: None
+        estimated rows: 3
+*/
+
+-- aggregation grouped by a column other than the primary key is implemented by hash aggregation
+explain select v, sum(k) from a group by v order by v
+
+/*
+PhysicalOrder:
+    [InputRef #0 (asc)]
+    estimated rows: 3
+  PhysicalProjection:
+      InputRef #0
+      InputRef #1
+      estimated rows: 3
+    PhysicalHashAgg:
+        InputRef #0
+        sum(InputRef #1) -> INT
+        estimated rows: 3
+      PhysicalTableScan:
+          table #0,
+          columns [1, 0],
+          with_row_handler: false,
+          is_sorted: false,
+          expr: None
+          estimated rows: 3
+*/
+
+-- equi-join on the primary keys of sorted scans is implemented by sort merge join
+explain select a.k, b.w from a join b on a.k = b.k order by a.k
+
+/*
+PhysicalProjection:
+    InputRef #0
+    InputRef #2
+    estimated rows: 3
+  PhysicalSortMergeJoin:
+      op Inner,
+      predicate: Eq(InputRef #0, InputRef #1)
+      estimated rows: 3
+    PhysicalTableScan:
+        table #0,
+        columns [0],
+        with_row_handler: false,
+        is_sorted: true,
+        expr: None
+        estimated rows: 3
+    PhysicalTableScan:
+        table #1,
+        columns [0, 1],
+        with_row_handler: false,
+        is_sorted: true,
+        expr: None
+        estimated rows: 3
+*/
+
+-- the other join conditions are evaluated by a filter above the sort merge join
+explain select a.k, b.w from a join b on a.k = b.k and a.v < b.w order by a.k
+
+/*
+PhysicalProjection:
+    InputRef #0
+    InputRef #3
+    estimated rows: 1
+  PhysicalFilter: expr Lt(InputRef #1, InputRef #3)
+      estimated rows: 1
+    PhysicalSortMergeJoin:
+        op Inner,
+        predicate: Eq(InputRef #0, InputRef #2)
+        estimated rows: 3
+      PhysicalTableScan:
+          table #0,
+          columns [0, 1],
+          with_row_handler: false,
+          is_sorted: true,
+          expr: None
+          estimated rows: 3
+      PhysicalTableScan:
+          table #1,
+          columns [0, 1],
+          with_row_handler: false,
+          is_sorted: true,
+          expr: None
+          estimated rows: 3
+*/
+
+-- equi-join on unsorted scans is implemented by hash join
+explain select a.k, b.w from a join b on a.k = b.k
+
+/*
+PhysicalProjection:
+    InputRef #0
+    InputRef #2
+    estimated rows: 3
+  PhysicalHashJoin:
+      op Inner,
+      predicate: Eq(InputRef #0, InputRef #1)
+      estimated rows: 3
+    PhysicalTableScan:
+        table #0,
+        columns [0],
+        with_row_handler: false,
+        is_sorted: false,
+        expr: None
+        estimated rows: 3
+    PhysicalTableScan:
+        table #1,
+        columns [0, 1],
+        with_row_handler: false,
+        is_sorted: false,
+        expr: None
+        estimated rows: 3
+*/
+
+-- equi-join on keys other than the primary keys is implemented by hash join
+explain select a.k, b.w from a join b on a.v = b.k order by a.k
+
+/*
+PhysicalProjection:
+    InputRef #1
+    InputRef #3
+    estimated rows: 3
+  PhysicalHashJoin:
+      op Inner,
+      predicate: Eq(InputRef #0, InputRef #2)
+      estimated rows: 3
+    PhysicalTableScan:
+        table #0,
+        columns [1, 0],
+        with_row_handler: false,
+        is_sorted: true,
+        expr: None
+        estimated rows: 3
+    PhysicalTableScan:
+        table #1,
+        columns [0, 1],
+        with_row_handler: false,
+        is_sorted: true,
+        expr: None
+        estimated rows: 3
+*/
+
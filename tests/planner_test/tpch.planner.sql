@@ -114,6 +114,7 @@ order by
 /*
 PhysicalOrder:
     [InputRef #0 (asc), InputRef #1 (asc)]
+    estimated rows: 0
   PhysicalProjection:
       InputRef #0
       InputRef #1
@@ -125,6 +126,7 @@ PhysicalOrder:
       (InputRef #3 / InputRef #9) (alias to avg_price)
       (InputRef #10 / InputRef #11) (alias to avg_disc)
       InputRef #12 (alias to count_order)
+      estimated rows: 0
     PhysicalHashAgg:
         InputRef #1
         InputRef #2
@@ -139,12 +141,219 @@ PhysicalOrder:
         sum(InputRef #5) -> NUMERIC(15,2)
         count(InputRef #5) -> INT
         count(InputRef #0) -> INT
+        estimated rows: 0
       PhysicalTableScan:
           table #7,
           columns [10, 8, 9, 4, 5, 6, 7],
           with_row_handler: false,
           is_sorted: false,
           expr: LtEq(InputRef #0, Date(Date(10490)) (const))
+          estimated rows: 0
+*/
+
+-- tpch-q2: TPC-H Q2
+explain select
+    s_acctbal,
+    s_name,
+    n_name,
+    p_partkey,
+    p_mfgr,
+    s_address,
+    s_phone,
+    s_comment
+from
+    part,
+    supplier,
+    partsupp,
+    nation,
+    region
+where
+    p_partkey = ps_partkey
+    and s_suppkey = ps_suppkey
+    and p_size = 31
+    and p_type like '%TIN'
+    and s_nationkey = n_nationkey
+    and n_regionkey = r_regionkey
+    and r_name = 'AMERICA'
+    and ps_supplycost = (
+        select
+            min(ps_supplycost)
+        from
+            partsupp,
+            supplier,
+            nation,
+            region
+        where
+            p_partkey = ps_partkey
+            and s_suppkey = ps_suppkey
+            and s_nationkey = n_nationkey
+            and n_regionkey = r_regionkey
+            and r_name = 'AMERICA'
+    )
+order by
+    s_acctbal desc,
+    n_name,
+    s_name,
+    p_partkey
+limit 100;
+
+/*
+PhysicalTopN: offset: 0, limit: 100, order by [InputRef #0 (desc), InputRef #2 (asc), InputRef #1 (asc), InputRef #3 (asc)]
+    estimated rows: 0
+  PhysicalProjection:
+      InputRef #6
+      InputRef #7
+      InputRef #16
+      InputRef #0
+      InputRef #3
+      InputRef #8
+      InputRef #9
+      InputRef #10
+      estimated rows: 0
+    PhysicalFilter: expr Eq(InputRef #13, InputRef #19)
+        estimated rows: 0
+      PhysicalProjection:
+          InputRef #0
+          InputRef #1
+          InputRef #2
+          InputRef #3
+          InputRef #4
+          InputRef #5
+          InputRef #6
+          InputRef #7
+          InputRef #8
+          InputRef #9
+          InputRef #10
+          InputRef #11
+          InputRef #12
+          InputRef #13
+          InputRef #14
+          InputRef #15
+          InputRef #16
+          InputRef #17
+          InputRef #18
+          InputRef #20
+          estimated rows: 0
+        PhysicalHashJoin:
+            op Left Outer,
+            predicate: Eq(InputRef #0, InputRef #19)
+            estimated rows: 0
+          PhysicalProjection:
+              InputRef #0
+              InputRef #1
+              InputRef #2
+              InputRef #3
+              InputRef #7
+              InputRef #8
+              InputRef #9
+              InputRef #10
+              InputRef #11
+              InputRef #12
+              InputRef #13
+              InputRef #4
+              InputRef #5
+              InputRef #6
+              InputRef #14
+              InputRef #15
+              InputRef #16
+              InputRef #17
+              InputRef #18
+              estimated rows: 0
+            PhysicalHashJoin:
+                op Inner,
+                predicate: Eq(InputRef #15, InputRef #17)
+                estimated rows: 0
+              PhysicalHashJoin:
+                  op Inner,
+                  predicate: Eq(InputRef #8, InputRef #14)
+                  estimated rows: 0
+                PhysicalHashJoin:
+                    op Inner,
+                    predicate: Eq(InputRef #5, InputRef #7)
+                    estimated rows: 0
+                  PhysicalHashJoin:
+                      op Inner,
+                      predicate: Eq(InputRef #0, InputRef #4)
+                      estimated rows: 0
+                    PhysicalTableScan:
+                        table #2,
+                        columns [0, 5, 4, 2],
+                        with_row_handler: false,
+                        is_sorted: false,
+                        expr: And(Eq(InputRef #1, Int32(31) (const)), Like(InputRef #2, String("%TIN") (const)))
+                        estimated rows: 0
+                    PhysicalTableScan:
+                        table #4,
+                        columns [0, 1, 3],
+                        with_row_handler: false,
+                        is_sorted: false,
+                        expr: None
+                        estimated rows: 0
+                  PhysicalTableScan:
+                      table #3,
+                      columns [0, 3, 5, 1, 2, 4, 6],
+                      with_row_handler: false,
+                      is_sorted: false,
+                      expr: None
+                      estimated rows: 0
+                PhysicalTableScan:
+                    table #0,
+                    columns [0, 2, 1],
+                    with_row_handler: false,
+                    is_sorted: false,
+                    expr: None
+                    estimated rows: 0
+              PhysicalTableScan:
+                  table #1,
+                  columns [0, 1],
+                  with_row_handler: false,
+                  is_sorted: false,
+                  expr: Eq(InputRef #1, String("AMERICA") (const))
+                  estimated rows: 0
+          PhysicalHashAgg:
+              InputRef #0
+              min(InputRef #2) -> NUMERIC(15,2)
+              estimated rows: 0
+            PhysicalHashJoin:
+                op Inner,
+                predicate: Eq(InputRef #6, InputRef #7)
+                estimated rows: 0
+              PhysicalHashJoin:
+                  op Inner,
+                  predicate: Eq(InputRef #4, InputRef #5)
+                  estimated rows: 0
+                PhysicalHashJoin:
+                    op Inner,
+                    predicate: Eq(InputRef #1, InputRef #3)
+                    estimated rows: 0
+                  PhysicalTableScan:
+                      table #4,
+                      columns [0, 1, 3],
+                      with_row_handler: false,
+                      is_sorted: false,
+                      expr: None
+                      estimated rows: 0
+                  PhysicalTableScan:
+                      table #3,
+                      columns [0, 3],
+                      with_row_handler: false,
+                      is_sorted: false,
+                      expr: None
+                      estimated rows: 0
+                PhysicalTableScan:
+                    table #0,
+                    columns [0, 2],
+                    with_row_handler: false,
+                    is_sorted: false,
+                    expr: None
+                    estimated rows: 0
+              PhysicalTableScan:
+                  table #1,
+                  columns [0, 1],
+                  with_row_handler: false,
+                  is_sorted: false,
+                  expr: Eq(InputRef #1, String("AMERICA") (const))
+                  estimated rows: 0
 */
 
 -- tpch-q3: TPC-H Q3
@@ -174,40 +383,122 @@ limit 10;
 
 /*
 PhysicalTopN: offset: 0, limit: 10, order by [InputRef #1 (desc), InputRef #2 (asc)]
+    estimated rows: 0
   PhysicalProjection:
       InputRef #0
       InputRef #3 (alias to revenue)
       InputRef #1
       InputRef #2
+      estimated rows: 0
     PhysicalHashAgg:
         InputRef #6
         InputRef #4
         InputRef #5
         sum((InputRef #8 * (1 - InputRef #9))) -> NUMERIC(15,2) (null)
+        estimated rows: 0
       PhysicalHashJoin:
           op Inner,
           predicate: Eq(InputRef #3, InputRef #6)
+          estimated rows: 0
         PhysicalHashJoin:
             op Inner,
             predicate: Eq(InputRef #1, InputRef #2)
+            estimated rows: 0
           PhysicalTableScan:
               table #5,
               columns [6, 0],
               with_row_handler: false,
               is_sorted: false,
               expr: Eq(InputRef #0, String("BUILDING") (const))
+              estimated rows: 0
           PhysicalTableScan:
               table #6,
               columns [1, 0, 4, 7],
               with_row_handler: false,
               is_sorted: false,
               expr: Lt(InputRef #2, Date(Date(9204)) (const))
+              estimated rows: 0
         PhysicalTableScan:
             table #7,
             columns [0, 10, 5, 6],
             with_row_handler: false,
             is_sorted: false,
             expr: Gt(InputRef #1, Date(Date(9204)) (const))
+            estimated rows: 0
+*/
+
+-- tpch-q4: TPC-H Q4
+explain select
+    o_orderpriority,
+    count(*) as order_count
+from
+    orders
+where
+    o_orderdate >= date '1993-07-01'
+    and o_orderdate < date '1993-07-01' + interval '3' month
+    and exists (
+        select
+            *
+        from
+            lineitem
+        where
+            l_orderkey = o_orderkey
+            and l_commitdate < l_receiptdate
+    )
+group by
+    o_orderpriority
+order by
+    o_orderpriority;
+
+/*
+PhysicalOrder:
+    [InputRef #0 (asc)]
+    estimated rows: 0
+  PhysicalProjection:
+      InputRef #0
+      InputRef #1 (alias to order_count)
+      estimated rows: 0
+    PhysicalHashAgg:
+        InputRef #2
+        count(InputRef #0) -> INT
+        estimated rows: 0
+      PhysicalHashJoin:
+          op Left Semi,
+          predicate: Eq(InputRef #1, InputRef #19)
+          estimated rows: 0
+        PhysicalTableScan:
+            table #6,
+            columns [4, 0, 5],
+            with_row_handler: false,
+            is_sorted: false,
+            expr: And(GtEq(InputRef #0, Date(Date(8582)) (const)), Lt(InputRef #0, Date(Date(8674)) (const)))
+            estimated rows: 0
+        PhysicalProjection:
+            InputRef #0
+            InputRef #3
+            InputRef #4
+            InputRef #5
+            InputRef #6
+            InputRef #7
+            InputRef #8
+            InputRef #9
+            InputRef #10
+            InputRef #11
+            InputRef #12
+            InputRef #1
+            InputRef #2
+            InputRef #13
+            InputRef #14
+            InputRef #15
+            InputRef #0
+            estimated rows: 0
+          PhysicalTableScan:
+              table #7,
+              columns [0, 11, 12, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 13, 14, 15],
+              with_row_handler: false,
+              is_sorted: false,
+              expr: Lt(InputRef #1, InputRef #2)
+              estimated rows: 0
 */
 
 -- tpch-q5: TPC-H Q5
@@ -239,63 +530,77 @@ order by
 /*
 PhysicalOrder:
     [InputRef #1 (desc)]
+    estimated rows: 0
   PhysicalProjection:
       InputRef #0
       InputRef #1 (alias to revenue)
+      estimated rows: 0
     PhysicalHashAgg:
         InputRef #13
         sum((InputRef #7 * (1 - InputRef #8))) -> NUMERIC(15,2) (null)
+        estimated rows: 0
       PhysicalHashJoin:
           op Inner,
           predicate: Eq(InputRef #12, InputRef #14)
+          estimated rows: 0
         PhysicalHashJoin:
             op Inner,
             predicate: Eq(InputRef #10, InputRef #11)
+            estimated rows: 0
           PhysicalHashJoin:
               op Inner,
               predicate: And(Eq(InputRef #6, InputRef #9), Eq(InputRef #1, InputRef #10))
+              estimated rows: 0
             PhysicalHashJoin:
                 op Inner,
                 predicate: Eq(InputRef #3, InputRef #5)
+                estimated rows: 0
               PhysicalHashJoin:
                   op Inner,
                   predicate: Eq(InputRef #0, InputRef #2)
+                  estimated rows: 0
                 PhysicalTableScan:
                     table #5,
                     columns [0, 3],
                     with_row_handler: false,
                     is_sorted: false,
                     expr: None
+                    estimated rows: 0
                 PhysicalTableScan:
                     table #6,
                     columns [1, 0, 4],
                     with_row_handler: false,
                     is_sorted: false,
                     expr: And(GtEq(InputRef #2, Date(Date(8766)) (const)), Lt(InputRef #2, Date(Date(9131)) (const)))
+                    estimated rows: 0
               PhysicalTableScan:
                   table #7,
                   columns [0, 2, 5, 6],
                   with_row_handler: false,
                   is_sorted: false,
                   expr: None
+                  estimated rows: 0
             PhysicalTableScan:
                 table #3,
                 columns [0, 3],
                 with_row_handler: false,
                 is_sorted: false,
                 expr: None
+                estimated rows: 0
           PhysicalTableScan:
               table #0,
               columns [0, 2, 1],
               with_row_handler: false,
               is_sorted: false,
               expr: None
+              estimated rows: 0
         PhysicalTableScan:
             table #1,
             columns [0, 1],
             with_row_handler: false,
             is_sorted: false,
             expr: Eq(InputRef #1, String("AFRICA") (const))
+            estimated rows: 0
 */
 
 -- tpch-q6
@@ -312,14 +617,17 @@ where
 /*
 PhysicalProjection:
     InputRef #0 (alias to revenue)
+    estimated rows: 1
   PhysicalSimpleAgg:
       sum((InputRef #3 * InputRef #1)) -> NUMERIC(15,2) (null)
+      estimated rows: 1
     PhysicalTableScan:
         table #7,
         columns [10, 6, 4, 5],
         with_row_handler: false,
         is_sorted: false,
         expr: And(And(And(GtEq(InputRef #0, Date(Date(8766)) (const)), Lt(InputRef #0, Date(Date(9131)) (const))), And(GtEq(InputRef #1, Decimal(0.07) (const)), LtEq(InputRef #1, Decimal(0.09) (const)))), Lt(InputRef #2, Decimal(24) (const)))
+        estimated rows: 0
 */
 
 -- tpch-q10: TPC-H Q10
@@ -358,6 +666,7 @@ limit 20;
 
 /*
 PhysicalTopN: offset: 0, limit: 20, order by [InputRef #2 (desc)]
+    estimated rows: 0
   PhysicalProjection:
       InputRef #0
       InputRef #1
@@ -367,6 +676,7 @@ PhysicalTopN: offset: 0, limit: 20, order by [InputRef #2 (desc)]
       InputRef #5
       InputRef #3
       InputRef #6
+      estimated rows: 0
     PhysicalHashAgg:
         InputRef #0
         InputRef #2
@@ -376,38 +686,505 @@ PhysicalTopN: offset: 0, limit: 20, order by [InputRef #2 (desc)]
         InputRef #4
         InputRef #6
         sum((InputRef #12 * (1 - InputRef #13))) -> NUMERIC(15,2) (null)
+        estimated rows: 0
       PhysicalHashJoin:
           op Inner,
           predicate: Eq(InputRef #1, InputRef #14)
+          estimated rows: 0
         PhysicalHashJoin:
             op Inner,
             predicate: Eq(InputRef #8, InputRef #10)
+            estimated rows: 0
           PhysicalHashJoin:
               op Inner,
               predicate: Eq(InputRef #0, InputRef #7)
+              estimated rows: 0
             PhysicalTableScan:
                 table #5,
                 columns [0, 3, 1, 5, 2, 4, 7],
                 with_row_handler: false,
                 is_sorted: false,
                 expr: None
+                estimated rows: 0
             PhysicalTableScan:
                 table #6,
                 columns [1, 0, 4],
                 with_row_handler: false,
                 is_sorted: false,
                 expr: And(GtEq(InputRef #2, Date(Date(8674)) (const)), Lt(InputRef #2, Date(Date(8766)) (const)))
+                estimated rows: 0
           PhysicalTableScan:
               table #7,
               columns [0, 8, 5, 6],
               with_row_handler: false,
               is_sorted: false,
               expr: Eq(InputRef #1, String("R") (const))
+              estimated rows: 0
         PhysicalTableScan:
             table #0,
             columns [0, 1],
             with_row_handler: false,
             is_sorted: false,
             expr: None
+            estimated rows: 0
+*/
+
+-- tpch-q17: TPC-H Q17
+explain select
+    sum(l_extendedprice) / 7.0 as avg_yearly
+from
+    lineitem,
+    part
+where
+    p_partkey = l_partkey
+    and p_brand = 'Brand#11'
+    and p_container = 'MED BAG'
+    and l_quantity < (
+        select
+            0.2 * avg(l_quantity)
+        from
+            lineitem
+        where
+            l_partkey = p_partkey
+    );
+
+/*
+PhysicalProjection:
+    (InputRef #0 / 7.00) (alias to avg_yearly)
+    estimated rows: 1
+  PhysicalSimpleAgg:
+      sum(InputRef #2) -> NUMERIC(15,2)
+      estimated rows: 1
+    PhysicalFilter: expr Lt(InputRef #1, InputRef #6)
+        estimated rows: 0
+      PhysicalProjection:
+          InputRef #0
+          InputRef #1
+          InputRef #2
+          InputRef #3
+          InputRef #4
+          InputRef #5
+          (0.20 * (InputRef #7 / case when BoundUnaryOp { op: Not, expr: BoundIsNull { expr: InputRef #8 } (isnull), return_type: Some(Boolean) } then InputRef #8 when BoundUnaryOp { op: Not, expr: BoundIsNull { expr: Int32(0) (const) } (isnull), return_type: Some(Boolean) } then 0 end))
+          estimated rows: 0
+        PhysicalHashJoin:
+            op Left Outer,
+            predicate: Eq(InputRef #3, InputRef #6)
+            estimated rows: 0
+          PhysicalHashJoin:
+              op Inner,
+              predicate: Eq(InputRef #0, InputRef #3)
+              estimated rows: 0
+            PhysicalTableScan:
+                table #7,
+                columns [1, 4, 5],
+                with_row_handler: false,
+                is_sorted: false,
+                expr: None
+                estimated rows: 0
+            PhysicalTableScan:
+                table #2,
+                columns [0, 3, 6],
+                with_row_handler: false,
+                is_sorted: false,
+                expr: And(Eq(InputRef #1, String("Brand#11") (const)), Eq(InputRef #2, String("MED BAG") (const)))
+                estimated rows: 0
+          PhysicalHashAgg:
+              InputRef #0
+              sum(InputRef #1) -> NUMERIC(15,2)
+              count(InputRef #1) -> INT
+              estimated rows: 0
+            PhysicalTableScan:
+                table #7,
+                columns [1, 4],
+                with_row_handler: false,
+                is_sorted: false,
+                expr: None
+                estimated rows: 0
+*/
+
+-- tpch-q20: TPC-H Q20
+explain select
+    s_name,
+    s_address
+from
+    supplier,
+    nation
+where
+    s_suppkey in (
+        select
+            ps_suppkey
+        from
+            partsupp
+        where
+            ps_partkey in (
+                select
+                    p_partkey
+                from
+                    part
+                where
+                    p_name like 'white%'
+            )
+            and ps_availqty > (
+                select
+                    0.5 * sum(l_quantity)
+                from
+                    lineitem
+                where
+                    l_partkey = ps_partkey
+                    and l_suppkey = ps_suppkey
+                    and l_shipdate >= date '1994-01-01'
+                    and l_shipdate < date '1994-01-01' + interval '1' year
+            )
+    )
+    and s_nationkey = n_nationkey
+    and n_name = 'PERU'
+order by
+    s_name;
+
+/*
+PhysicalOrder:
+    [InputRef #0 (asc)]
+    estimated rows: 0
+  PhysicalProjection:
+      InputRef #2
+      InputRef #3
+      estimated rows: 0
+    PhysicalHashJoin:
+        op Left Semi,
+        predicate: Eq(InputRef #0, InputRef #6)
+        estimated rows: 0
+      PhysicalHashJoin:
+          op Inner,
+          predicate: Eq(InputRef #1, InputRef #4)
+          estimated rows: 0
+        PhysicalTableScan:
+            table #3,
+            columns [0, 3, 1, 2],
+            with_row_handler: false,
+            is_sorted: false,
+            expr: None
+            estimated rows: 0
+        PhysicalTableScan:
+            table #0,
+            columns [0, 1],
+            with_row_handler: false,
+            is_sorted: false,
+            expr: Eq(InputRef #1, String("PERU") (const))
+            estimated rows: 0
+      PhysicalProjection:
+          InputRef #2
+          estimated rows: 0
+        PhysicalFilter: expr Gt(InputRef #1 as Decimal(Some(15), Some(2)), InputRef #3)
+            estimated rows: 0
+          PhysicalProjection:
+              InputRef #0
+              InputRef #1
+              InputRef #2
+              (0.50 * InputRef #5)
+              estimated rows: 0
+            PhysicalHashJoin:
+                op Left Outer,
+                predicate: And(Eq(InputRef #0, InputRef #3), Eq(InputRef #2, InputRef #4))
+                estimated rows: 0
+              PhysicalHashJoin:
+                  op Left Semi,
+                  predicate: Eq(InputRef #0, InputRef #3)
+                  estimated rows: 0
+                PhysicalTableScan:
+                    table #4,
+                    columns [0, 2, 1],
+                    with_row_handler: false,
+                    is_sorted: false,
+                    expr: None
+                    estimated rows: 0
+                PhysicalProjection:
+                    InputRef #1
+                    estimated rows: 0
+                  PhysicalTableScan:
+                      table #2,
+                      columns [1, 0],
+                      with_row_handler: false,
+                      is_sorted: false,
+                      expr: Like(InputRef #0, String("white%") (const))
+                      estimated rows: 0
+              PhysicalHashAgg:
+                  InputRef #0
+                  InputRef #1
+                  sum(InputRef #3) -> NUMERIC(15,2)
+                  estimated rows: 0
+                PhysicalTableScan:
+                    table #7,
+                    columns [1, 2, 10, 4],
+                    with_row_handler: false,
+                    is_sorted: false,
+                    expr: And(GtEq(InputRef #2, Date(Date(8766)) (const)), Lt(InputRef #2, Date(Date(9131)) (const)))
+                    estimated rows: 0
+*/
+
+-- tpch-q21: TPC-H Q21
+explain select
+    s_name,
+    count(*) as numwait
+from
+    supplier,
+    lineitem l1,
+    orders,
+    nation
+where
+    s_suppkey = l1.l_suppkey
+    and o_orderkey = l1.l_orderkey
+    and o_orderstatus = 'F'
+    and l1.l_receiptdate > l1.l_commitdate
+    and exists (
+        select
+            *
+        from
+            lineitem l2
+        where
+            l2.l_orderkey = l1.l_orderkey
+            and l2.l_suppkey <> l1.l_suppkey
+    )
+    and not exists (
+        select
+            *
+        from
+            lineitem l3
+        where
+            l3.l_orderkey = l1.l_orderkey
+            and l3.l_suppkey <> l1.l_suppkey
+            and l3.l_receiptdate > l3.l_commitdate
+    )
+    and s_nationkey = n_nationkey
+    and n_name = 'SAUDI ARABIA'
+group by
+    s_name
+order by
+    numwait desc,
+    s_name
+limit 100;
+
+/*
+PhysicalTopN: offset: 0, limit: 100, order by [InputRef #1 (desc), InputRef #0 (asc)]
+    estimated rows: 0
+  PhysicalProjection:
+      InputRef #0
+      InputRef #1 (alias to numwait)
+      estimated rows: 0
+    PhysicalHashAgg:
+        InputRef #2
+        count(InputRef #0) -> INT
+        estimated rows: 0
+      PhysicalHashJoin:
+          op Left Anti,
+          predicate: And(NotEq(InputRef #28, InputRef #3), Eq(InputRef #4, InputRef #27))
+          estimated rows: 0
+        PhysicalHashJoin:
+            op Left Semi,
+            predicate: And(NotEq(InputRef #28, InputRef #3), Eq(InputRef #4, InputRef #27))
+            estimated rows: 0
+          PhysicalHashJoin:
+              op Inner,
+              predicate: Eq(InputRef #1, InputRef #9)
+              estimated rows: 0
+            PhysicalHashJoin:
+                op Inner,
+                predicate: Eq(InputRef #4, InputRef #7)
+                estimated rows: 0
+              PhysicalHashJoin:
+                  op Inner,
+                  predicate: Eq(InputRef #0, InputRef #3)
+                  estimated rows: 0
+                PhysicalTableScan:
+                    table #3,
+                    columns [0, 3, 1],
+                    with_row_handler: false,
+                    is_sorted: false,
+                    expr: None
+                    estimated rows: 0
+                PhysicalTableScan:
+                    table #7,
+                    columns [2, 0, 12, 11],
+                    with_row_handler: false,
+                    is_sorted: false,
+                    expr: Gt(InputRef #2, InputRef #3)
+                    estimated rows: 0
+              PhysicalTableScan:
+                  table #6,
+                  columns [0, 2],
+                  with_row_handler: false,
+                  is_sorted: false,
+                  expr: Eq(InputRef #1, String("F") (const))
+                  estimated rows: 0
+            PhysicalTableScan:
+                table #0,
+                columns [0, 1],
+                with_row_handler: false,
+                is_sorted: false,
+                expr: Eq(InputRef #1, String("SAUDI ARABIA") (const))
+                estimated rows: 0
+          PhysicalProjection:
+              InputRef #0
+              InputRef #2
+              InputRef #1
+              InputRef #3
+              InputRef #4
+              InputRef #5
+              InputRef #6
+              InputRef #7
+              InputRef #8
+              InputRef #9
+              InputRef #10
+              InputRef #11
+              InputRef #12
+              InputRef #13
+              InputRef #14
+              InputRef #15
+              InputRef #0
+              InputRef #1
+              estimated rows: 0
+            PhysicalTableScan:
+                table #7,
+                columns [0, 2, 1, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+                with_row_handler: false,
+                is_sorted: false,
+                expr: None
+                estimated rows: 0
+        PhysicalProjection:
+            InputRef #0
+            InputRef #4
+            InputRef #1
+            InputRef #5
+            InputRef #6
+            InputRef #7
+            InputRef #8
+            InputRef #9
+            InputRef #10
+            InputRef #11
+            InputRef #12
+            InputRef #3
+            InputRef #2
+            InputRef #13
+            InputRef #14
+            InputRef #15
+            InputRef #0
+            InputRef #1
+            estimated rows: 0
+          PhysicalTableScan:
+              table #7,
+              columns [0, 2, 12, 11, 1, 3, 4, 5, 6, 7, 8, 9, 10, 13, 14, 15],
+              with_row_handler: false,
+              is_sorted: false,
+              expr: Gt(InputRef #2, InputRef #3)
+              estimated rows: 0
+*/
+
+-- tpch-q22: TPC-H Q22
+explain with custsale as (
+    select
+        substring(c_phone from 1 for 2) as cntrycode,
+        c_acctbal
+    from
+        customer
+    where
+        substring(c_phone from 1 for 2) in
+            ('13', '31', '23', '29', '30', '18', '17')
+        and c_acctbal > (
+            select
+                avg(c_acctbal)
+            from
+                customer
+            where
+                c_acctbal > 0.00
+                and substring(c_phone from 1 for 2) in
+                    ('13', '31', '23', '29', '30', '18', '17')
+        )
+        and not exists (
+            select
+                *
+            from
+                orders
+            where
+                o_custkey = c_custkey
+        )
+)
+select
+    cntrycode,
+    count(*) as numcust,
+    sum(c_acctbal) as totacctbal
+from
+    custsale
+group by
+    cntrycode
+order by
+    cntrycode;
+
+/*
+PhysicalOrder:
+    [InputRef #0 (asc)]
+    estimated rows: 0
+  PhysicalProjection:
+      InputRef #0
+      InputRef #1 (alias to numcust)
+      InputRef #2 (alias to totacctbal)
+      estimated rows: 0
+    PhysicalHashAgg:
+        InputRef #0
+        count(InputRef #0) -> INT
+        sum(InputRef #1) -> NUMERIC(15,2)
+        estimated rows: 0
+      PhysicalProjection:
+          substring(InputRef #0, 1, 2) (alias to cntrycode)
+          InputRef #1
+          estimated rows: 0
+        PhysicalHashJoin:
+            op Left Anti,
+            predicate: Eq(InputRef #2, InputRef #13)
+            estimated rows: 0
+          PhysicalFilter: expr Gt(InputRef #1, InputRef #3)
+              estimated rows: 0
+            PhysicalNestedLoopJoin:
+                op Left Single,
+                predicate: Bool(true) (const)
+                estimated rows: 0
+              PhysicalTableScan:
+                  table #5,
+                  columns [4, 5, 0],
+                  with_row_handler: false,
+                  is_sorted: false,
+                  expr: substring(InputRef #0, Int32(1) (const), Int32(2) (const)) -> Char(Some(15)) in (String("13") (const), String("31") (const), String("23") (const), String("29") (const), String("30") (const), String("18") (const), String("17") (const))
+                  estimated rows: 0
+              PhysicalProjection:
+                  (InputRef #0 / InputRef #1)
+                  estimated rows: 1
+                PhysicalSimpleAgg:
+                    sum(InputRef #0) -> NUMERIC(15,2)
+                    count(InputRef #0) -> INT
+                    estimated rows: 1
+                  PhysicalTableScan:
+                      table #5,
+                      columns [5, 4],
+                      with_row_handler: false,
+                      is_sorted: false,
+                      expr: And(Gt(InputRef #0, Decimal(0.00) (const)), substring(InputRef #1, Int32(1) (const), Int32(2) (const)) -> Char(Some(15)) in (String("13") (const), String("31") (const), String("23") (const), String("29") (const), String("30") (const), String("18") (const), String("17") (const)))
+                      estimated rows: 0
+          PhysicalProjection:
+              InputRef #1
+              InputRef #0
+              InputRef #2
+              InputRef #3
+              InputRef #4
+              InputRef #5
+              InputRef #6
+              InputRef #7
+              InputRef #8
+              InputRef #0
+              estimated rows: 0
+            PhysicalTableScan:
+                table #6,
+                columns [1, 0, 2, 3, 4, 5, 6, 7, 8],
+                with_row_handler: false,
+                is_sorted: false,
+                expr: None
+                estimated rows: 0
 */
 
@@ -7,9 +7,11 @@ use risinglight::array::*;
 use risinglight::storage::SecondaryStorageOptions;
 use risinglight::{Database, Error};
 
-pub async fn test_mem(name: &str) {
+pub async fn test_mem(name: &str, enable_cascades: bool) {
     init_logger();
-    let db = Arc::new(Database::new_in_memory());
+    let mut db = Database::new_in_memory();
+    db.set_enable_cascades(enable_cascades);
+    let db = Arc::new(db);
     let mut tester = sqllogictest::Runner::new(DatabaseWrapper { db: db.clone() });
     tester.enable_testdir();
 
@@ -25,9 +27,10 @@ pub async fn test_mem(name: &str) {
     db.shutdown().await.unwrap();
 }
 
-pub async fn test_disk(name: &str) {
+pub async fn test_disk(name: &str, enable_cascades: bool) {
     init_logger();
-    let db = Database::new_on_disk(SecondaryStorageOptions::default_for_test()).await;
+    let mut db = Database::new_on_disk(SecondaryStorageOptions::default_for_test()).await;
+    db.set_enable_cascades(enable_cascades);
     let db = Arc::new(db);
     let mut tester = sqllogictest::Runner::new(DatabaseWrapper { db: db.clone() });
     tester.enable_testdir();
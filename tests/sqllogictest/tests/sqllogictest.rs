@@ -8,7 +8,7 @@ use tokio::runtime::Runtime;
 
 fn main() {
     const PATTERN: &str = "../sql/**/[!_]*.slt"; // ignore files start with '_'
-    const MEM_BLOCKLIST: &[&str] = &["statistics.slt"];
+    const MEM_BLOCKLIST: &[&str] = &["statistics.slt", "metadata_scan.slt"];
     const DISK_BLOCKLIST: &[&str] = &[];
 
     let paths = glob::glob(PATTERN).expect("failed to find test files");
@@ -19,29 +19,27 @@ fn main() {
     for entry in paths {
         let path = entry.expect("failed to read glob entry");
         let subpath = path.strip_prefix("../sql").unwrap().to_str().unwrap();
-        if !MEM_BLOCKLIST.iter().any(|p| subpath.contains(p)) {
-            tests.push(Test {
-                name: format!(
-                    "mem_{}",
-                    subpath.strip_suffix(".slt").unwrap().replace('/', "_")
-                ),
-                kind: "".into(),
-                is_ignored: false,
-                is_bench: false,
-                data: ("mem", subpath.to_string()),
-            });
-        }
-        if !DISK_BLOCKLIST.iter().any(|p| subpath.contains(p)) {
-            tests.push(Test {
-                name: format!(
-                    "disk_{}",
-                    subpath.strip_suffix(".slt").unwrap().replace('/', "_")
-                ),
-                kind: "".into(),
-                is_ignored: false,
-                is_bench: false,
-                data: ("disk", subpath.to_string()),
-            });
+        let name = subpath.strip_suffix(".slt").unwrap().replace('/', "_");
+        // each test runs with both the heuristic and the memo-based optimizer
+        for (optimizer, enable_cascades) in [("", false), ("cascades_", true)] {
+            if !MEM_BLOCKLIST.iter().any(|p| subpath.contains(p)) {
+                tests.push(Test {
+                    name: format!("mem_{}{}", optimizer, name),
+                    kind: "".into(),
+                    is_ignored: false,
+                    is_bench: false,
+                    data: ("mem", enable_cascades, subpath.to_string()),
+                });
+            }
+            if !DISK_BLOCKLIST.iter().any(|p| subpath.contains(p)) {
+                tests.push(Test {
+                    name: format!("disk_{}{}", optimizer, name),
+                    kind: "".into(),
+                    is_ignored: false,
+                    is_bench: false,
+                    data: ("disk", enable_cascades, subpath.to_string()),
+                });
+            }
         }
     }
 
@@ -60,12 +58,12 @@ fn main() {
     }
 
     run_tests(&args, tests, |test| match &test.data {
-        ("mem", case) => {
-            build_runtime().block_on(test_mem(case));
+        ("mem", enable_cascades, case) => {
+            build_runtime().block_on(test_mem(case, *enable_cascades));
             Outcome::Passed
         }
-        ("disk", case) => {
-            build_runtime().block_on(test_disk(case));
+        ("disk", enable_cascades, case) => {
+            build_runtime().block_on(test_disk(case, *enable_cascades));
             Outcome::Passed
         }
         _ => unreachable!(),
@@ -13,7 +13,9 @@ pub struct DatabaseWrapper;
 impl sqlplannertest::PlannerTestRunner for DatabaseWrapper {
     async fn run(&mut self, test_case: &ParsedTestCase) -> Result<String, Error> {
         if !test_case.tasks.is_empty() {
-            let db = Database::new_on_disk(SecondaryStorageOptions::default_for_test()).await;
+            let mut db = Database::new_on_disk(SecondaryStorageOptions::default_for_test()).await;
+            // the plans are optimized by the memo-based optimizer with the `cascades` task
+            db.set_enable_cascades(test_case.tasks.iter().any(|task| task == "cascades"));
             for sql in &test_case.before_sql {
                 db.run(sql).await?;
             }